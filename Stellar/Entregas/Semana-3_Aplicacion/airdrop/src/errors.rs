@@ -0,0 +1,47 @@
+// src/errors.rs
+use soroban_sdk::contracterror;
+
+/// Enum de errores personalizados del airdrop
+///
+/// Cada error tiene un código único para debugging en el ledger
+/// Los códigos empiezan en 1 (0 está reservado para "sin error")
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AirdropError {
+    /// El contrato ya fue inicializado
+    /// Se lanza si se intenta llamar initialize() dos veces
+    AlreadyInitialized = 1,
+
+    /// El contrato no ha sido inicializado
+    /// Debe llamarse initialize() primero
+    NotInitialized = 2,
+
+    /// Amount debe ser mayor a 0
+    /// Una hoja del árbol con amount <= 0 no se puede reclamar
+    InvalidAmount = 3,
+
+    /// El índice ya fue reclamado
+    /// Cada hoja del árbol se paga una sola vez
+    AlreadyClaimed = 4,
+
+    /// La prueba Merkle no corresponde a la raíz guardada
+    /// (index, account, amount) no está en el árbol
+    InvalidProof = 5,
+
+    /// Deadline inválido
+    /// initialize() con un deadline que no es posterior al ledger actual
+    InvalidDeadline = 6,
+
+    /// El período de claims terminó
+    /// claim() después del ledger deadline
+    ClaimPeriodEnded = 7,
+
+    /// El período de claims sigue abierto
+    /// reclaim() antes de que pase el deadline
+    ClaimPeriodActive = 8,
+
+    /// No quedan tokens sin cobrar
+    /// reclaim() con el balance del contrato en 0
+    NothingToReclaim = 9,
+}
@@ -0,0 +1,253 @@
+// src/lib.rs
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractevent, contractimpl, token, xdr::ToXdr, Address, Bytes, BytesN, Env, Vec,
+};
+
+mod errors;
+mod storage;
+
+use errors::AirdropError;
+use storage::DataKey;
+
+// EVENTOS
+
+#[contractevent]
+pub struct AirdropInitEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub merkle_root: BytesN<32>,
+    pub deadline: u32,
+}
+
+#[contractevent]
+pub struct ClaimedEvent {
+    pub index: u32,
+    pub account: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct ReclaimedEvent {
+    pub to: Address,
+    pub amount: i128,
+}
+
+/// Contrato de airdrop por árbol Merkle
+///
+/// En lugar de un mint por dirección, el contrato guarda solo la raíz
+/// del árbol de (index, account, amount). Cada usuario presenta su
+/// prueba y cobra una sola vez desde el balance pre-fondeado del
+/// contrato (el distribuidor), vía `transfer` del token. Pasado el
+/// ledger `deadline` los claims se cierran y el admin recupera el saldo
+/// que nadie cobró con `reclaim`.
+#[contract]
+pub struct MerkleAirdrop;
+
+/// Interfaz pública del airdrop
+pub trait AirdropTrait {
+    /// Configura el airdrop (solo una vez)
+    ///
+    /// - Admin: quien despliega; firma la configuración y puede hacer `reclaim`
+    /// - Token: contrato SEP-41 a distribuir (ej: TokenBDB)
+    /// - Merkle root: raíz generada con la herramienta `airdrop_tool`
+    /// - Deadline: último ledger en que se aceptan claims (> ledger actual)
+    ///
+    /// El contrato debe recibir luego los tokens a repartir
+    /// (mint o transfer hacia la dirección del contrato).
+    fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        merkle_root: BytesN<32>,
+        deadline: u32,
+    ) -> Result<(), AirdropError>;
+
+    /// Reclama la hoja `index` del árbol
+    ///
+    /// Verifica la prueba contra la raíz guardada y transfiere `amount`
+    /// tokens del contrato a `account`. No requiere la firma de `account`:
+    /// los tokens solo pueden ir a la cuenta incluida en la hoja.
+    /// Falla con `ClaimPeriodEnded` pasado el deadline.
+    fn claim(
+        env: Env,
+        index: u32,
+        account: Address,
+        amount: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<(), AirdropError>;
+
+    /// Recupera los tokens no cobrados (solo admin, pasado el deadline)
+    ///
+    /// Transfiere todo el balance del contrato a `to` y devuelve el monto.
+    fn reclaim(env: Env, to: Address) -> Result<i128, AirdropError>;
+
+    /// Consulta si la hoja `index` ya fue reclamada
+    fn is_claimed(env: Env, index: u32) -> bool;
+
+    // Métodos de consulta (getters)
+    fn admin(env: Env) -> Result<Address, AirdropError>;
+    fn token(env: Env) -> Result<Address, AirdropError>;
+    fn merkle_root(env: Env) -> Result<BytesN<32>, AirdropError>;
+    fn deadline(env: Env) -> Result<u32, AirdropError>;
+}
+
+/// Hash de una hoja: sha256 del XDR de (index, account, amount)
+///
+/// La herramienta `airdrop_tool` calcula exactamente el mismo hash
+/// al construir el árbol desde el CSV.
+pub fn leaf_hash(env: &Env, index: u32, account: &Address, amount: i128) -> BytesN<32> {
+    let leaf = (index, account.clone(), amount).to_xdr(env);
+    env.crypto().sha256(&leaf).into()
+}
+
+/// Hash de un nodo interno: sha256(menor || mayor)
+///
+/// Ordenar el par evita guardar en la prueba de qué lado va cada hermano.
+fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (left, right) = if a.to_array() <= b.to_array() { (a, b) } else { (b, a) };
+    let mut data = Bytes::from_array(env, &left.to_array());
+    data.extend_from_array(&right.to_array());
+    env.crypto().sha256(&data).into()
+}
+
+#[contractimpl]
+impl AirdropTrait for MerkleAirdrop {
+    fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        merkle_root: BytesN<32>,
+        deadline: u32,
+    ) -> Result<(), AirdropError> {
+        // 1. Verificar que no esté inicializado
+        if env.storage().instance().has(&DataKey::MerkleRoot) {
+            return Err(AirdropError::AlreadyInitialized);
+        }
+
+        // 2. El admin firma la configuración: nadie puede adelantarse
+        // con otra raíz entre el deploy y el initialize
+        admin.require_auth();
+
+        // 3. Validar deadline
+        if deadline <= env.ledger().sequence() {
+            return Err(AirdropError::InvalidDeadline);
+        }
+
+        // 4. Guardar configuración en instance storage
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage().instance().set(&DataKey::MerkleRoot, &merkle_root);
+        env.storage().instance().set(&DataKey::Deadline, &deadline);
+        env.storage().instance().extend_ttl(100_000, 200_000);
+
+        // 5. Emitir evento
+        AirdropInitEvent { admin, token, merkle_root, deadline }.publish(&env);
+
+        Ok(())
+    }
+
+    fn claim(
+        env: Env,
+        index: u32,
+        account: Address,
+        amount: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<(), AirdropError> {
+        // 1. Verificar inicialización
+        let merkle_root = Self::merkle_root(env.clone())?;
+        let token = Self::token(env.clone())?;
+
+        // 2. Validaciones
+        if env.ledger().sequence() > Self::deadline(env.clone())? {
+            return Err(AirdropError::ClaimPeriodEnded);
+        }
+        if amount <= 0 {
+            return Err(AirdropError::InvalidAmount);
+        }
+        if storage::is_claimed(&env, index) {
+            return Err(AirdropError::AlreadyClaimed);
+        }
+
+        // 3. Recalcular la raíz desde la hoja y la prueba
+        let mut computed = leaf_hash(&env, index, &account, amount);
+        for sibling in proof.iter() {
+            computed = hash_pair(&env, &computed, &sibling);
+        }
+        if computed != merkle_root {
+            return Err(AirdropError::InvalidProof);
+        }
+
+        // 4. Marcar como reclamado antes de transferir
+        storage::set_claimed(&env, index);
+
+        // 5. Pagar desde el balance del contrato con transfer del token
+        token::TokenClient::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &account,
+            &amount,
+        );
+
+        // 6. Emitir evento
+        ClaimedEvent { index, account, amount }.publish(&env);
+
+        Ok(())
+    }
+
+    fn reclaim(env: Env, to: Address) -> Result<i128, AirdropError> {
+        // 1. Verificar inicialización y autorización del admin
+        let admin = Self::admin(env.clone())?;
+        admin.require_auth();
+
+        // 2. Los claims tienen que estar cerrados
+        if env.ledger().sequence() <= Self::deadline(env.clone())? {
+            return Err(AirdropError::ClaimPeriodActive);
+        }
+
+        // 3. Transferir todo el saldo no cobrado
+        let token = token::TokenClient::new(&env, &Self::token(env.clone())?);
+        let contract = env.current_contract_address();
+        let amount = token.balance(&contract);
+        if amount <= 0 {
+            return Err(AirdropError::NothingToReclaim);
+        }
+        token.transfer(&contract, &to, &amount);
+
+        // 4. Emitir evento
+        ReclaimedEvent { to, amount }.publish(&env);
+
+        Ok(amount)
+    }
+
+    fn is_claimed(env: Env, index: u32) -> bool {
+        storage::is_claimed(&env, index)
+    }
+
+    fn admin(env: Env) -> Result<Address, AirdropError> {
+        env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(AirdropError::NotInitialized)
+    }
+
+    fn token(env: Env) -> Result<Address, AirdropError> {
+        env.storage().instance()
+            .get(&DataKey::Token)
+            .ok_or(AirdropError::NotInitialized)
+    }
+
+    fn merkle_root(env: Env) -> Result<BytesN<32>, AirdropError> {
+        env.storage().instance()
+            .get(&DataKey::MerkleRoot)
+            .ok_or(AirdropError::NotInitialized)
+    }
+
+    fn deadline(env: Env) -> Result<u32, AirdropError> {
+        env.storage().instance()
+            .get(&DataKey::Deadline)
+            .ok_or(AirdropError::NotInitialized)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,37 @@
+// src/storage.rs
+use soroban_sdk::{contracttype, Env};
+
+/// Enum que define todas las claves de almacenamiento
+///
+/// - Instance Storage: configuración del airdrop
+/// - Persistent Storage: índices ya reclamados (requiere TTL)
+#[contracttype]
+pub enum DataKey {
+    /// Cuenta que configuró el airdrop y puede hacer reclaim - Instance Storage
+    Admin,
+
+    /// Contrato del token a distribuir - Instance Storage
+    Token,
+
+    /// Raíz del árbol Merkle de (index, account, amount) - Instance Storage
+    MerkleRoot,
+
+    /// Último ledger en que se aceptan claims - Instance Storage
+    Deadline,
+
+    /// Índice ya reclamado - Persistent Storage
+    /// Solo existe si la hoja `index` ya se pagó
+    Claimed(u32),
+}
+
+/// Indica si la hoja `index` ya fue reclamada
+pub fn is_claimed(env: &Env, index: u32) -> bool {
+    env.storage().persistent().has(&DataKey::Claimed(index))
+}
+
+/// Marca la hoja `index` como reclamada
+pub fn set_claimed(env: &Env, index: u32) {
+    let key = DataKey::Claimed(index);
+    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+}
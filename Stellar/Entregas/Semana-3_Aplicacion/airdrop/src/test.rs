@@ -0,0 +1,358 @@
+// src/test.rs
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger},
+    vec, Address, BytesN, Env, Event, IntoVal, String, Symbol,
+};
+
+// TokenBDB compilado a wasm: el airdrop paga con su transfer()
+// Regenerar con: ../fixtures.sh build
+mod token_bdb {
+    soroban_sdk::contractimport!(file = "testdata/token_bdb.wasm");
+}
+
+/// Raíz y pruebas generadas por `airdrop_tool` para `tool/ejemplo.csv`
+///
+/// Fijarlas acá verifica que la herramienta y el contrato calculan
+/// el mismo hash de hoja y de nodo.
+const ROOT: &str = "671814ce2d81c10592ee18f5b10fc46c1845f197e06fe66bdb35f64ba3f0cbae";
+const CLAIMS: [(u32, &str, i128, &[&str]); 6] = [
+    (0, "GCXFUTUSBO6G6L2UQFVVMTHHQAFK47SZHBYAZ3KDMDWBMVZKBJ3SZSIJ", 1000, &[
+        "bf1b3d98a8f38342908b515201942d1587f4e24daae7905b3d4c9ffa8e38c1fa", 
+        "785283207d8fab743fb9d070dea83287b5fe20d4156f28aeacccd7964f7c2be1", 
+        "48007ee665fa782539bad7d4311d12211c76f782978fd6a0167cc27a163c0402",
+    ]),
+    (1, "GDMWZZDWHRTNEMANEJSPVVDXHUGDUTMTLGP3BHDRV2WBJD2ODVVSHAVY", 2500, &[
+        "80f917938a264dcf6ef74501360b3e5ca8699f42e5f6818f22c21f17bab4e21e", 
+        "785283207d8fab743fb9d070dea83287b5fe20d4156f28aeacccd7964f7c2be1", 
+        "48007ee665fa782539bad7d4311d12211c76f782978fd6a0167cc27a163c0402",
+    ]),
+    (2, "GDUX334BSORLOMTZKOUU2AD64NVY7AHHMIQUCSGVAPC66SXPI2SODPBM", 750, &[
+        "6160553db663b2fb79f22a026c4810987e96fa0fbe99b23ba00a0324ee552d77", 
+        "0b1661bb8b6dbde87727a4bd8eb441f6da71f903f916abbcff11921852da9633", 
+        "48007ee665fa782539bad7d4311d12211c76f782978fd6a0167cc27a163c0402",
+    ]),
+    (3, "GBAV2RZJGRCZQKZATFPFYKM4MFHA5GKVBV4Y7B7CX6Y2X2Q34IGVWIDV", 12000, &[
+        "07268965da28a6f8e5dc979d6537a8cbc4049dcd4db9769eb0996da975659e39", 
+        "0b1661bb8b6dbde87727a4bd8eb441f6da71f903f916abbcff11921852da9633", 
+        "48007ee665fa782539bad7d4311d12211c76f782978fd6a0167cc27a163c0402",
+    ]),
+    (4, "GDTJZ2IAWIMNXNU676TOTGECMWGDD4EVUBWEQWCPSI6GHBHONL3W2OG6", 300, &[
+        "94ba3e45fae70b31b8e380c8d38a8537c23287c6bb200c70e86e580cb589ebb3", 
+        "cf388b84369a8f7e6c6a644ff0ef8f9b574ff174ad3f42d1aa8e43470977690d",
+    ]),
+    (5, "CAYLEP4DFSLEUP3BYFYVNARIQ4Y4XZPGQ3WGJ65AIT4IVLYHT6UFAJD6", 5000, &[
+        "3470e3fb48cf3a42289f3d32ad6a3850e73a9a147482483933736508802898fc", 
+        "cf388b84369a8f7e6c6a644ff0ef8f9b574ff174ad3f42d1aa8e43470977690d",
+    ]),
+];
+const TOTAL: i128 = 21_550;
+
+/// Último ledger con claims abiertos en los tests
+const DEADLINE: u32 = 1_000;
+
+fn hash_from_hex(env: &Env, hex: &str) -> BytesN<32> {
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+    }
+    BytesN::from_array(env, &bytes)
+}
+
+fn proof_from_hex(env: &Env, proof: &[&str]) -> Vec<BytesN<32>> {
+    let mut result = Vec::new(env);
+    for node in proof {
+        result.push_back(hash_from_hex(env, node));
+    }
+    result
+}
+
+/// Helper: TokenBDB + airdrop inicializado con la raíz del ejemplo
+///
+/// El contrato del airdrop recibe `funding` tokens (distribuidor pre-fondeado)
+fn setup(env: &Env, funding: i128) -> (MerkleAirdropClient<'_>, token_bdb::Client<'_>) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let token_id = env.register(token_bdb::WASM, ());
+    let token = token_bdb::Client::new(env, &token_id);
+    token.initialize(
+        &admin,
+        &String::from_str(env, "Builder Token"),
+        &String::from_str(env, "BDB"),
+        &7,
+        &None
+    );
+
+    let airdrop_id = env.register(MerkleAirdrop, ());
+    let airdrop = MerkleAirdropClient::new(env, &airdrop_id);
+    airdrop.initialize(&admin, &token_id, &hash_from_hex(env, ROOT), &DEADLINE);
+
+    if funding > 0 {
+        token.mint(&admin, &airdrop_id, &funding);
+    }
+
+    (airdrop, token)
+}
+
+/// Test: claim con la prueba generada por la herramienta
+#[test]
+fn test_claim_with_tool_proof() {
+    let env = Env::default();
+    let (airdrop, token) = setup(&env, TOTAL);
+    let (index, account, amount, proof) = CLAIMS[3];
+    let account = Address::from_str(&env, account);
+
+    airdrop.claim(&index, &account, &amount, &proof_from_hex(&env, proof));
+
+    let event = ClaimedEvent {
+        index,
+        account: account.clone(),
+        amount,
+    };
+    // El último evento es el del airdrop (antes va el transfer del token)
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![&env, (airdrop.address.clone(), event.topics(&env), event.data(&env))]
+    );
+
+    assert_eq!(token.balance(&account), amount);
+    assert_eq!(token.balance(&airdrop.address), TOTAL - amount);
+    assert!(airdrop.is_claimed(&index));
+}
+
+/// Test: todas las cuentas del CSV cobran y el distribuidor queda en 0
+#[test]
+fn test_claim_all() {
+    let env = Env::default();
+    let (airdrop, token) = setup(&env, TOTAL);
+
+    for (index, account, amount, proof) in CLAIMS {
+        let account = Address::from_str(&env, account);
+        airdrop.claim(&index, &account, &amount, &proof_from_hex(&env, proof));
+        assert_eq!(token.balance(&account), amount);
+    }
+
+    assert_eq!(token.balance(&airdrop.address), 0);
+    assert_eq!(token.total_supply(), TOTAL);
+}
+
+/// Test: cada índice se cobra una sola vez
+#[test]
+fn test_claim_twice_fails() {
+    let env = Env::default();
+    let (airdrop, token) = setup(&env, TOTAL);
+    let (index, account, amount, proof) = CLAIMS[0];
+    let account = Address::from_str(&env, account);
+    let proof = proof_from_hex(&env, proof);
+
+    airdrop.claim(&index, &account, &amount, &proof);
+    assert_eq!(
+        airdrop.try_claim(&index, &account, &amount, &proof),
+        Err(Ok(AirdropError::AlreadyClaimed))
+    );
+    assert_eq!(token.balance(&account), amount);
+}
+
+/// Test: pruebas que no corresponden a la hoja fallan con InvalidProof
+#[test]
+fn test_invalid_proof() {
+    let env = Env::default();
+    let (airdrop, _token) = setup(&env, TOTAL);
+    let (index, account, amount, proof) = CLAIMS[1];
+    let account = Address::from_str(&env, account);
+    let proof = proof_from_hex(&env, proof);
+
+    // Otro amount
+    assert_eq!(
+        airdrop.try_claim(&index, &account, &(amount + 1), &proof),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+    // Otra cuenta
+    assert_eq!(
+        airdrop.try_claim(&index, &Address::generate(&env), &amount, &proof),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+    // Otro índice
+    assert_eq!(
+        airdrop.try_claim(&0, &account, &amount, &proof),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+    // Prueba de otra hoja
+    assert_eq!(
+        airdrop.try_claim(&index, &account, &amount, &proof_from_hex(&env, CLAIMS[4].3)),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+    assert!(!airdrop.is_claimed(&index));
+}
+
+/// Test: sin fondos en el distribuidor el claim se revierte completo
+#[test]
+fn test_claim_underfunded_reverts() {
+    let env = Env::default();
+    let (airdrop, token) = setup(&env, 100);
+    let (index, account, amount, proof) = CLAIMS[0];
+    let account = Address::from_str(&env, account);
+
+    assert!(airdrop
+        .try_claim(&index, &account, &amount, &proof_from_hex(&env, proof))
+        .is_err());
+    assert!(!airdrop.is_claimed(&index));
+    assert_eq!(token.balance(&account), 0);
+}
+
+/// Test: initialize solo una vez, y claim requiere inicialización
+#[test]
+fn test_initialize_errors() {
+    let env = Env::default();
+    let (airdrop, token) = setup(&env, 0);
+
+    assert_eq!(
+        airdrop.try_initialize(
+            &Address::generate(&env),
+            &token.address,
+            &hash_from_hex(&env, ROOT),
+            &DEADLINE
+        ),
+        Err(Ok(AirdropError::AlreadyInitialized))
+    );
+    assert_eq!(airdrop.token(), token.address);
+    assert_eq!(airdrop.merkle_root(), hash_from_hex(&env, ROOT));
+    assert_eq!(airdrop.deadline(), DEADLINE);
+
+    let uninitialized = MerkleAirdropClient::new(&env, &env.register(MerkleAirdrop, ()));
+    assert_eq!(
+        uninitialized.try_claim(&0, &Address::generate(&env), &1, &Vec::new(&env)),
+        Err(Ok(AirdropError::NotInitialized))
+    );
+}
+
+/// Test: una hoja con amount <= 0 se rechaza antes de verificar
+#[test]
+fn test_claim_invalid_amount() {
+    let env = Env::default();
+    let (airdrop, _token) = setup(&env, TOTAL);
+
+    assert_eq!(
+        airdrop.try_claim(&0, &Address::generate(&env), &0, &Vec::new(&env)),
+        Err(Ok(AirdropError::InvalidAmount))
+    );
+}
+
+/// Test: initialize exige la firma del admin y un deadline futuro
+#[test]
+fn test_initialize_requires_admin() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let root = hash_from_hex(&env, ROOT);
+    let airdrop = MerkleAirdropClient::new(&env, &env.register(MerkleAirdrop, ()));
+
+    // Sin firma del admin no se puede configurar
+    assert!(airdrop.try_initialize(&admin, &token, &root, &DEADLINE).is_err());
+    assert_eq!(airdrop.try_merkle_root(), Err(Ok(AirdropError::NotInitialized)));
+
+    // Deadline en el ledger actual o anterior
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(DEADLINE);
+    assert_eq!(
+        airdrop.try_initialize(&admin, &token, &root, &DEADLINE),
+        Err(Ok(AirdropError::InvalidDeadline))
+    );
+
+    airdrop.initialize(&admin, &token, &root, &(DEADLINE + 1));
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    airdrop.address.clone(),
+                    Symbol::new(&env, "initialize"),
+                    (admin.clone(), token.clone(), root.clone(), DEADLINE + 1).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(airdrop.admin(), admin);
+}
+
+/// Test: el deadline es el último ledger con claims abiertos
+#[test]
+fn test_claim_after_deadline_fails() {
+    let env = Env::default();
+    let (airdrop, token) = setup(&env, TOTAL);
+    let (index, account, amount, proof) = CLAIMS[0];
+    let account = Address::from_str(&env, account);
+    let proof = proof_from_hex(&env, proof);
+
+    env.ledger().set_sequence_number(DEADLINE + 1);
+    assert_eq!(
+        airdrop.try_claim(&index, &account, &amount, &proof),
+        Err(Ok(AirdropError::ClaimPeriodEnded))
+    );
+
+    env.ledger().set_sequence_number(DEADLINE);
+    airdrop.claim(&index, &account, &amount, &proof);
+    assert_eq!(token.balance(&account), amount);
+}
+
+/// Test: pasado el deadline el admin recupera lo no cobrado
+#[test]
+fn test_reclaim_after_deadline() {
+    let env = Env::default();
+    let (airdrop, token) = setup(&env, TOTAL);
+    let admin = airdrop.admin();
+    let treasury = Address::generate(&env);
+    let (index, account, amount, proof) = CLAIMS[2];
+    let account = Address::from_str(&env, account);
+    airdrop.claim(&index, &account, &amount, &proof_from_hex(&env, proof));
+
+    // Antes del deadline no se puede
+    assert_eq!(
+        airdrop.try_reclaim(&treasury),
+        Err(Ok(AirdropError::ClaimPeriodActive))
+    );
+
+    env.ledger().set_sequence_number(DEADLINE + 1);
+    assert_eq!(airdrop.reclaim(&treasury), TOTAL - amount);
+
+    let event = ReclaimedEvent {
+        to: treasury.clone(),
+        amount: TOTAL - amount,
+    };
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![&env, (airdrop.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(env.auths()[0].0, admin);
+
+    assert_eq!(token.balance(&treasury), TOTAL - amount);
+    assert_eq!(token.balance(&airdrop.address), 0);
+    assert_eq!(token.balance(&account), amount);
+
+    // Ya no queda nada
+    assert_eq!(
+        airdrop.try_reclaim(&treasury),
+        Err(Ok(AirdropError::NothingToReclaim))
+    );
+}
+
+/// Test: reclaim exige la firma del admin
+#[test]
+fn test_reclaim_requires_admin() {
+    let env = Env::default();
+    let (airdrop, token) = setup(&env, TOTAL);
+    env.ledger().set_sequence_number(DEADLINE + 1);
+    env.set_auths(&[]);
+
+    assert!(airdrop.try_reclaim(&Address::generate(&env)).is_err());
+    assert_eq!(token.balance(&airdrop.address), TOTAL);
+}
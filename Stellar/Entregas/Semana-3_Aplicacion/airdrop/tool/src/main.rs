@@ -0,0 +1,105 @@
+// src/main.rs
+//! Herramienta host-side del airdrop Merkle
+//!
+//! Lee un CSV `account,amount` (una cuenta por línea, el índice es el
+//! número de fila empezando en 0) y escribe en stdout un JSON con la
+//! raíz del árbol y la prueba de cada cuenta, listo para llamar a
+//! `initialize` y `claim` del contrato.
+//!
+//! Uso: cargo run -- cuentas.csv > airdrop.json
+
+use std::{env, fs, process};
+
+mod merkle;
+
+use merkle::{Claim, MerkleTree};
+
+/// Parsea el CSV (la cabecera `account,amount` es opcional)
+fn parse_csv(content: &str) -> Result<Vec<Claim>, String> {
+    let mut claims = Vec::new();
+
+    for (line_number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || (line_number == 0 && line.starts_with("account")) {
+            continue;
+        }
+
+        let (account, amount) = line
+            .split_once(',')
+            .ok_or(format!("línea {}: se esperaba account,amount", line_number + 1))?;
+        let amount: i128 = amount
+            .trim()
+            .parse()
+            .map_err(|_| format!("línea {}: amount inválido", line_number + 1))?;
+        if amount <= 0 {
+            return Err(format!("línea {}: amount debe ser mayor a 0", line_number + 1));
+        }
+
+        claims.push(Claim {
+            index: claims.len() as u32,
+            account: account.trim().to_string(),
+            amount,
+        });
+    }
+
+    Ok(claims)
+}
+
+/// Arma el JSON de salida (raíz + prueba por cuenta)
+///
+/// Los amounts van como string porque i128 no entra en un número JSON.
+fn build_output(claims: &[Claim]) -> Result<String, String> {
+    let leaves = claims
+        .iter()
+        .map(merkle::leaf_hash)
+        .collect::<Result<Vec<_>, _>>()?;
+    let tree = MerkleTree::new(leaves.clone())?;
+
+    let mut out = String::new();
+    out.push_str("{\n");
+    out.push_str(&format!("  \"merkle_root\": \"{}\",\n", hex::encode(tree.root())));
+    out.push_str("  \"claims\": [\n");
+    for (i, claim) in claims.iter().enumerate() {
+        let proof = tree.proof(i);
+        if !merkle::verify(&tree.root(), leaves[i], &proof) {
+            return Err(format!("la prueba de la fila {i} no verifica"));
+        }
+        let proof = proof
+            .iter()
+            .map(|node| format!("\"{}\"", hex::encode(node)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let separator = if i + 1 < claims.len() { "," } else { "" };
+        out.push_str(&format!(
+            "    {{ \"index\": {}, \"account\": \"{}\", \"amount\": \"{}\", \"proof\": [{}] }}{}\n",
+            claim.index, claim.account, claim.amount, proof, separator
+        ));
+    }
+    out.push_str("  ]\n");
+    out.push_str("}\n");
+
+    Ok(out)
+}
+
+fn main() {
+    let path = env::args().nth(1).unwrap_or_else(|| {
+        eprintln!("Uso: airdrop_tool <cuentas.csv>");
+        process::exit(1);
+    });
+
+    let result = fs::read_to_string(&path)
+        .map_err(|e| format!("no se pudo leer {path}: {e}"))
+        .and_then(|content| parse_csv(&content))
+        .and_then(|claims| build_output(&claims));
+
+    match result {
+        Ok(json) => print!("{json}"),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
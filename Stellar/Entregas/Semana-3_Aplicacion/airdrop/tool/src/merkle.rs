@@ -0,0 +1,106 @@
+// src/merkle.rs
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::{
+    Int128Parts, Limits, ScAddress, ScVal, ScVec, VecM, WriteXdr,
+};
+
+/// Una hoja del airdrop: (index, account, amount)
+#[derive(Clone, Debug)]
+pub struct Claim {
+    pub index: u32,
+    pub account: String,
+    pub amount: i128,
+}
+
+/// Hash de una hoja: sha256 del XDR de (index, account, amount)
+///
+/// Debe coincidir con `leaf_hash` del contrato, que serializa la tupla
+/// como un ScVal::Vec de [U32, Address, I128].
+pub fn leaf_hash(claim: &Claim) -> Result<[u8; 32], String> {
+    let address: ScAddress = claim.account.parse()
+        .map_err(|_| format!("cuenta inválida: {}", claim.account))?;
+    if !matches!(address, ScAddress::Account(_) | ScAddress::Contract(_)) {
+        return Err(format!("solo se aceptan cuentas G... o contratos C...: {}", claim.account));
+    }
+
+    let amount = Int128Parts {
+        hi: (claim.amount >> 64) as i64,
+        lo: claim.amount as u64,
+    };
+    let items: VecM<ScVal> = vec![
+        ScVal::U32(claim.index),
+        ScVal::Address(address),
+        ScVal::I128(amount),
+    ]
+    .try_into()
+    .map_err(|_| "error armando la tupla XDR".to_string())?;
+    let xdr = ScVal::Vec(Some(ScVec(items)))
+        .to_xdr(Limits::none())
+        .map_err(|e| format!("error serializando XDR: {e}"))?;
+
+    Ok(Sha256::digest(&xdr).into())
+}
+
+/// Hash de un nodo interno: sha256(menor || mayor)
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Árbol Merkle completo, nivel por nivel (levels[0] son las hojas)
+///
+/// Si un nivel tiene cantidad impar de nodos, el último sube sin
+/// hermano al nivel siguiente (y no aparece en la prueba).
+pub struct MerkleTree {
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: Vec<[u8; 32]>) -> Result<Self, String> {
+        if leaves.is_empty() {
+            return Err("el CSV no tiene cuentas".to_string());
+        }
+
+        let mut levels = vec![leaves];
+        while levels.last().map_or(0, Vec::len) > 1 {
+            let level = levels.last().unwrap();
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_pair(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Ok(Self { levels })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().unwrap()[0]
+    }
+
+    /// Prueba de la hoja `index`: los hermanos desde la hoja hasta la raíz
+    pub fn proof(&self, mut index: usize) -> Vec<[u8; 32]> {
+        let mut proof = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = index ^ 1;
+            if sibling < level.len() {
+                proof.push(level[sibling]);
+            }
+            index /= 2;
+        }
+        proof
+    }
+}
+
+/// Recalcula la raíz desde una hoja y su prueba (igual que el contrato)
+pub fn verify(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    let computed = proof.iter().fold(leaf, |acc, sibling| hash_pair(&acc, sibling));
+    &computed == root
+}
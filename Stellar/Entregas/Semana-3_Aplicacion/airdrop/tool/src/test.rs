@@ -0,0 +1,53 @@
+// src/test.rs
+use super::*;
+use merkle::verify;
+
+const EJEMPLO: &str = include_str!("../ejemplo.csv");
+
+/// Test: cada prueba generada verifica contra la raíz
+#[test]
+fn test_every_proof_verifies() {
+    let claims = parse_csv(EJEMPLO).unwrap();
+    assert_eq!(claims.len(), 6);
+
+    let leaves: Vec<_> = claims.iter().map(|c| merkle::leaf_hash(c).unwrap()).collect();
+    let tree = MerkleTree::new(leaves.clone()).unwrap();
+
+    for (i, leaf) in leaves.iter().enumerate() {
+        assert!(verify(&tree.root(), *leaf, &tree.proof(i)));
+    }
+}
+
+/// Test: una hoja con otro amount no verifica
+#[test]
+fn test_tampered_amount_fails() {
+    let claims = parse_csv(EJEMPLO).unwrap();
+    let leaves: Vec<_> = claims.iter().map(|c| merkle::leaf_hash(c).unwrap()).collect();
+    let tree = MerkleTree::new(leaves).unwrap();
+
+    let mut tampered = claims[1].clone();
+    tampered.amount += 1;
+    let leaf = merkle::leaf_hash(&tampered).unwrap();
+    assert!(!verify(&tree.root(), leaf, &tree.proof(1)));
+}
+
+/// Test: una sola cuenta es su propia raíz, con prueba vacía
+#[test]
+fn test_single_leaf_tree() {
+    let claims = parse_csv("GCXFUTUSBO6G6L2UQFVVMTHHQAFK47SZHBYAZ3KDMDWBMVZKBJ3SZSIJ,1").unwrap();
+    let leaf = merkle::leaf_hash(&claims[0]).unwrap();
+    let tree = MerkleTree::new(vec![leaf]).unwrap();
+
+    assert_eq!(tree.root(), leaf);
+    assert!(tree.proof(0).is_empty());
+}
+
+/// Test: errores de formato del CSV
+#[test]
+fn test_invalid_csv() {
+    assert!(parse_csv("GCXFUTUSBO6G6L2UQFVVMTHHQAFK47SZHBYAZ3KDMDWBMVZKBJ3SZSIJ").is_err());
+    assert!(parse_csv("GCXFUTUSBO6G6L2UQFVVMTHHQAFK47SZHBYAZ3KDMDWBMVZKBJ3SZSIJ,0").is_err());
+    assert!(parse_csv("GCXFUTUSBO6G6L2UQFVVMTHHQAFK47SZHBYAZ3KDMDWBMVZKBJ3SZSIJ,abc").is_err());
+    assert!(build_output(&parse_csv("no-es-una-cuenta,10").unwrap()).is_err());
+    assert!(build_output(&[]).is_err());
+}
@@ -0,0 +1,32 @@
+// src/errors.rs
+use soroban_sdk::contracterror;
+
+/// Enum de errores personalizados de la factory
+///
+/// Cada error tiene un código único para debugging en el ledger
+/// Los códigos empiezan en 1 (0 está reservado para "sin error")
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FactoryError {
+    /// El contrato ya fue inicializado
+    /// Se lanza si se intenta llamar initialize() dos veces
+    AlreadyInitialized = 1,
+
+    /// El contrato no ha sido inicializado
+    /// Debe llamarse initialize() primero
+    NotInitialized = 2,
+
+    /// El token no está en el registro
+    TokenNotFound = 3,
+
+    /// Ya existe un token desplegado con ese deployer y salt
+    AlreadyDeployed = 4,
+
+    /// El initialize del token falló (metadatos o tope inválidos)
+    /// El deploy se revierte completo
+    InitializationFailed = 5,
+
+    /// Overflow en un contador del registro
+    OverflowError = 7,
+}
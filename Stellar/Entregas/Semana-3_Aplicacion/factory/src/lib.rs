@@ -0,0 +1,362 @@
+// src/lib.rs
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contractevent, contractimpl, xdr::ToXdr, Address, BytesN, Env,
+    String, Vec,
+};
+
+mod errors;
+mod storage;
+
+pub use errors::FactoryError;
+pub use storage::{TokenParams, TokenRecord};
+use storage::DataKey;
+
+// EVENTOS
+
+#[contractevent]
+pub struct TokenDeployedEvent {
+    pub token: Address,
+    pub deployer: Address,
+    pub admin: Address,
+    pub symbol: String,
+    pub index: u32,
+}
+
+#[contractevent]
+pub struct WasmHashUpdatedEvent {
+    pub admin: Address,
+    pub wasm_hash: BytesN<32>,
+}
+
+#[contractevent]
+pub struct TokenVerifiedEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub verified: bool,
+}
+
+/// Máximo de entradas por página del registro
+///
+/// Un `limit` mayor se recorta a este valor, igual que `holders()` en
+/// los tokens.
+const MAX_PAGE_SIZE: u32 = 50;
+
+/// initialize() de TokenBDB, llamado sobre cada instancia desplegada
+#[contractclient(name = "TokenInitClient")]
+pub trait TokenInitInterface {
+    fn initialize(
+        env: Env,
+        admin: Address,
+        name: String,
+        symbol: String,
+        decimals: u32,
+        max_supply: Option<i128>
+    );
+}
+
+/// Factory y registro de tokens
+///
+/// Despliega instancias del wasm de TokenBDB ya subido a la red, las
+/// inicializa en la misma invocación y las anota en un registro
+/// enumerable. Reemplaza copiar el crate para lanzar un token nuevo.
+#[contract]
+pub struct TokenFactory;
+
+/// Interfaz pública de la factory
+pub trait FactoryTrait {
+    /// Configura admin y wasm (solo una vez)
+    ///
+    /// Requiere autorización de `admin`
+    fn initialize(env: Env, admin: Address, wasm_hash: BytesN<32>) -> Result<(), FactoryError>;
+
+    /// Reemplaza el wasm de los próximos deploys (solo admin)
+    ///
+    /// Los tokens ya desplegados no cambian
+    fn set_wasm_hash(env: Env, wasm_hash: BytesN<32>) -> Result<(), FactoryError>;
+
+    /// Despliega e inicializa un token nuevo y devuelve su dirección
+    ///
+    /// Requiere autorización de `deployer` y de `params.admin` (nadie
+    /// registra tokens a nombre de otro admin). La dirección depende solo
+    /// de la factory, `deployer` y `salt` (ver deployed_address).
+    fn deploy(
+        env: Env,
+        deployer: Address,
+        salt: BytesN<32>,
+        params: TokenParams
+    ) -> Result<Address, FactoryError>;
+
+    /// Dirección que tendría el token de `deployer` con `salt`
+    fn deployed_address(env: Env, deployer: Address, salt: BytesN<32>) -> Address;
+
+    /// Marca o desmarca un token como verificado (solo admin)
+    fn set_verified(env: Env, token: Address, verified: bool) -> Result<(), FactoryError>;
+
+    /// Página del registro en orden de deploy
+    fn tokens(env: Env, start: u32, limit: u32) -> Vec<TokenRecord>;
+
+    /// Entrada del registro de `token`
+    fn token(env: Env, token: Address) -> Result<TokenRecord, FactoryError>;
+
+    /// Página de los tokens registrados con `symbol` (puede haber más de uno)
+    fn tokens_by_symbol(
+        env: Env,
+        symbol: String,
+        start: u32,
+        limit: u32
+    ) -> Vec<TokenRecord>;
+
+    /// Página de los tokens registrados con `admin` como admin inicial
+    fn tokens_by_admin(
+        env: Env,
+        admin: Address,
+        start: u32,
+        limit: u32
+    ) -> Vec<TokenRecord>;
+
+    /// Cantidad de tokens registrados con `symbol`
+    fn symbol_token_count(env: Env, symbol: String) -> u32;
+
+    /// Cantidad de tokens registrados con `admin` como admin inicial
+    fn admin_token_count(env: Env, admin: Address) -> u32;
+
+    // Métodos de consulta (getters)
+    fn token_count(env: Env) -> u32;
+    fn wasm_hash(env: Env) -> Result<BytesN<32>, FactoryError>;
+    fn admin(env: Env) -> Result<Address, FactoryError>;
+}
+
+/// Salt efectivo del deploy: sha256(XDR((deployer, salt)))
+///
+/// Incluir al deployer evita que otra cuenta ocupe su dirección
+/// adelantándose con el mismo salt.
+fn deploy_salt(env: &Env, deployer: &Address, salt: &BytesN<32>) -> BytesN<32> {
+    let payload = (deployer.clone(), salt.clone()).to_xdr(env);
+    env.crypto().sha256(&payload).into()
+}
+
+/// Lee el admin de la factory y exige su autorización
+fn require_admin(env: &Env) -> Result<Address, FactoryError> {
+    let admin: Address = env.storage().instance()
+        .get(&DataKey::Admin)
+        .ok_or(FactoryError::NotInitialized)?;
+    admin.require_auth();
+
+    Ok(admin)
+}
+
+#[contractimpl]
+impl FactoryTrait for TokenFactory {
+    fn initialize(env: Env, admin: Address, wasm_hash: BytesN<32>) -> Result<(), FactoryError> {
+        // 1. Verificar que no esté inicializado
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(FactoryError::AlreadyInitialized);
+        }
+
+        // 2. Verificar autorización
+        admin.require_auth();
+
+        // 3. Guardar configuración
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::WasmHash, &wasm_hash);
+        env.storage().instance().set(&DataKey::TokenCount, &0u32);
+        env.storage().instance().extend_ttl(100_000, 200_000);
+
+        Ok(())
+    }
+
+    fn set_wasm_hash(env: Env, wasm_hash: BytesN<32>) -> Result<(), FactoryError> {
+        // 1. Solo el admin cambia el wasm
+        let admin = require_admin(&env)?;
+
+        // 2. Guardar el nuevo hash
+        env.storage().instance().set(&DataKey::WasmHash, &wasm_hash);
+
+        // 3. Emitir evento
+        WasmHashUpdatedEvent { admin, wasm_hash }.publish(&env);
+
+        Ok(())
+    }
+
+    fn deploy(
+        env: Env,
+        deployer: Address,
+        salt: BytesN<32>,
+        params: TokenParams
+    ) -> Result<Address, FactoryError> {
+        // 1. Verificar inicialización y autorización del deployer y del
+        // admin del token (si son la misma cuenta, una sola firma)
+        let wasm_hash = Self::wasm_hash(env.clone())?;
+        deployer.require_auth();
+        if params.admin != deployer {
+            params.admin.require_auth();
+        }
+
+        // 2. Una dirección solo se despliega una vez
+        let address = Self::deployed_address(env.clone(), deployer.clone(), salt.clone());
+        if env.storage().persistent().has(&DataKey::TokenIndex(address.clone())) {
+            return Err(FactoryError::AlreadyDeployed);
+        }
+
+        // 3. Desplegar e inicializar en la misma invocación
+        let token = env.deployer()
+            .with_current_contract(deploy_salt(&env, &deployer, &salt))
+            .deploy_v2(wasm_hash, ());
+        let initialized = TokenInitClient::new(&env, &token).try_initialize(
+            &params.admin,
+            &params.name,
+            &params.symbol,
+            &params.decimals,
+            &params.max_supply,
+        );
+        if !matches!(initialized, Ok(Ok(()))) {
+            return Err(FactoryError::InitializationFailed);
+        }
+
+        // 4. Registrar el token y sus índices
+        let index = Self::token_count(env.clone());
+        storage::write_token(&env, index, &TokenRecord {
+            address: token.clone(),
+            deployer: deployer.clone(),
+            admin: params.admin.clone(),
+            name: params.name,
+            symbol: params.symbol.clone(),
+            deployed_ledger: env.ledger().sequence(),
+            verified: false,
+        });
+        let index_key = DataKey::TokenIndex(token.clone());
+        env.storage().persistent().set(&index_key, &index);
+        env.storage().persistent().extend_ttl(&index_key, 100_000, 200_000);
+        storage::push_index(
+            &env,
+            &DataKey::SymbolCount(params.symbol.clone()),
+            |n| DataKey::BySymbol(params.symbol.clone(), n),
+            index,
+        )?;
+        storage::push_index(
+            &env,
+            &DataKey::AdminCount(params.admin.clone()),
+            |n| DataKey::ByAdmin(params.admin.clone(), n),
+            index,
+        )?;
+        let count = index.checked_add(1).ok_or(FactoryError::OverflowError)?;
+        env.storage().instance().set(&DataKey::TokenCount, &count);
+        env.storage().instance().extend_ttl(100_000, 200_000);
+
+        // 5. Emitir evento
+        TokenDeployedEvent {
+            token: token.clone(),
+            deployer,
+            admin: params.admin,
+            symbol: params.symbol,
+            index,
+        }.publish(&env);
+
+        Ok(token)
+    }
+
+    fn deployed_address(env: Env, deployer: Address, salt: BytesN<32>) -> Address {
+        env.deployer()
+            .with_current_contract(deploy_salt(&env, &deployer, &salt))
+            .deployed_address()
+    }
+
+    fn set_verified(env: Env, token: Address, verified: bool) -> Result<(), FactoryError> {
+        // 1. Solo el admin verifica tokens
+        let admin = require_admin(&env)?;
+
+        // 2. Actualizar la entrada del registro
+        let index: u32 = env.storage().persistent()
+            .get(&DataKey::TokenIndex(token.clone()))
+            .ok_or(FactoryError::TokenNotFound)?;
+        let mut record = storage::read_token(&env, index)
+            .ok_or(FactoryError::TokenNotFound)?;
+        record.verified = verified;
+        storage::write_token(&env, index, &record);
+
+        // 3. Emitir evento
+        TokenVerifiedEvent { admin, token, verified }.publish(&env);
+
+        Ok(())
+    }
+
+    fn tokens(env: Env, start: u32, limit: u32) -> Vec<TokenRecord> {
+        let end = Self::token_count(env.clone())
+            .min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        let mut records = Vec::new(&env);
+        for index in start..end {
+            if let Some(record) = storage::read_token(&env, index) {
+                records.push_back(record);
+            }
+        }
+        records
+    }
+
+    fn token(env: Env, token: Address) -> Result<TokenRecord, FactoryError> {
+        let index: u32 = env.storage().persistent()
+            .get(&DataKey::TokenIndex(token))
+            .ok_or(FactoryError::TokenNotFound)?;
+        storage::read_token(&env, index).ok_or(FactoryError::TokenNotFound)
+    }
+
+    fn tokens_by_symbol(
+        env: Env,
+        symbol: String,
+        start: u32,
+        limit: u32
+    ) -> Vec<TokenRecord> {
+        storage::read_index(
+            &env,
+            &DataKey::SymbolCount(symbol.clone()),
+            |n| DataKey::BySymbol(symbol.clone(), n),
+            start,
+            limit.min(MAX_PAGE_SIZE),
+        )
+    }
+
+    fn tokens_by_admin(
+        env: Env,
+        admin: Address,
+        start: u32,
+        limit: u32
+    ) -> Vec<TokenRecord> {
+        storage::read_index(
+            &env,
+            &DataKey::AdminCount(admin.clone()),
+            |n| DataKey::ByAdmin(admin.clone(), n),
+            start,
+            limit.min(MAX_PAGE_SIZE),
+        )
+    }
+
+    fn symbol_token_count(env: Env, symbol: String) -> u32 {
+        storage::read_index_count(&env, &DataKey::SymbolCount(symbol))
+    }
+
+    fn admin_token_count(env: Env, admin: Address) -> u32 {
+        storage::read_index_count(&env, &DataKey::AdminCount(admin))
+    }
+
+    fn token_count(env: Env) -> u32 {
+        env.storage().instance()
+            .get(&DataKey::TokenCount)
+            .unwrap_or(0)
+    }
+
+    fn wasm_hash(env: Env) -> Result<BytesN<32>, FactoryError> {
+        env.storage().instance()
+            .get(&DataKey::WasmHash)
+            .ok_or(FactoryError::NotInitialized)
+    }
+
+    fn admin(env: Env) -> Result<Address, FactoryError> {
+        env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(FactoryError::NotInitialized)
+    }
+}
+
+#[cfg(test)]
+mod test;
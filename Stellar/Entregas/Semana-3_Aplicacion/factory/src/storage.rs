@@ -0,0 +1,127 @@
+// src/storage.rs
+use soroban_sdk::{contracttype, Address, Env, String, Vec};
+
+use crate::errors::FactoryError;
+
+/// Enum que define todas las claves de almacenamiento
+///
+/// - Instance Storage: configuración y contador del registro
+/// - Persistent Storage: registro de tokens e índices (crecen sin límite)
+#[contracttype]
+pub enum DataKey {
+    /// Admin de la factory - Instance Storage
+    /// Actualiza el wasm y marca tokens como verificados
+    Admin,
+
+    /// Hash del wasm de TokenBDB subido a la red - Instance Storage
+    WasmHash,
+
+    /// Cantidad de tokens registrados - Instance Storage
+    TokenCount,
+
+    /// Registro por posición (0..TokenCount) - Persistent Storage
+    Token(u32),
+
+    /// Posición en el registro de una dirección - Persistent Storage
+    TokenIndex(Address),
+
+    /// Cantidad de tokens con un símbolo - Persistent Storage
+    SymbolCount(String),
+
+    /// Posición en el registro del n-ésimo token con un símbolo - Persistent Storage
+    /// Tupla (símbolo, n), n en 0..SymbolCount
+    BySymbol(String, u32),
+
+    /// Cantidad de tokens de un admin - Persistent Storage
+    AdminCount(Address),
+
+    /// Posición en el registro del n-ésimo token de un admin - Persistent Storage
+    /// Tupla (admin, n), n en 0..AdminCount
+    ByAdmin(Address, u32),
+}
+
+/// Parámetros del initialize del token desplegado
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenParams {
+    pub admin: Address,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+    pub max_supply: Option<i128>,
+}
+
+/// Entrada del registro de tokens
+///
+/// `admin` es el admin inicial: si el token transfiere su admin
+/// después, el registro no se actualiza.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenRecord {
+    pub address: Address,
+    pub deployer: Address,
+    pub admin: Address,
+    pub name: String,
+    pub symbol: String,
+    pub deployed_ledger: u32,
+    pub verified: bool,
+}
+
+/// Lee la entrada del registro en la posición `index`
+pub fn read_token(env: &Env, index: u32) -> Option<TokenRecord> {
+    env.storage().persistent().get(&DataKey::Token(index))
+}
+
+/// Guarda la entrada del registro en la posición `index`
+pub fn write_token(env: &Env, index: u32, record: &TokenRecord) {
+    let key = DataKey::Token(index);
+    env.storage().persistent().set(&key, record);
+    env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+}
+
+/// Cantidad de entradas de un índice secundario (símbolo o admin)
+pub fn read_index_count(env: &Env, count_key: &DataKey) -> u32 {
+    env.storage().persistent().get(count_key).unwrap_or(0)
+}
+
+/// Agrega `index` al final de un índice secundario
+///
+/// Cada posición ocupa su propia entrada `entry_key(n)`, así que el
+/// costo de un deploy no crece con la cantidad de tokens del índice.
+pub fn push_index(
+    env: &Env,
+    count_key: &DataKey,
+    entry_key: impl Fn(u32) -> DataKey,
+    index: u32,
+) -> Result<(), FactoryError> {
+    let count = read_index_count(env, count_key);
+    let key = entry_key(count);
+    env.storage().persistent().set(&key, &index);
+    env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+
+    let count = count.checked_add(1).ok_or(FactoryError::OverflowError)?;
+    env.storage().persistent().set(count_key, &count);
+    env.storage().persistent().extend_ttl(count_key, 100_000, 200_000);
+    Ok(())
+}
+
+/// Entradas del registro en las posiciones `start..start + limit` de un
+/// índice secundario
+pub fn read_index(
+    env: &Env,
+    count_key: &DataKey,
+    entry_key: impl Fn(u32) -> DataKey,
+    start: u32,
+    limit: u32,
+) -> Vec<TokenRecord> {
+    let end = read_index_count(env, count_key).min(start.saturating_add(limit));
+
+    let mut records = Vec::new(env);
+    for n in start..end {
+        let index: Option<u32> = env.storage().persistent().get(&entry_key(n));
+        if let Some(record) = index.and_then(|index| read_token(env, index)) {
+            records.push_back(record);
+        }
+    }
+    records
+}
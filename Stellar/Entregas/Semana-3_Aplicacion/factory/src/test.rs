@@ -0,0 +1,282 @@
+// src/test.rs
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+
+// TokenBDB compilado a wasm: la factory despliega instancias de este código
+// Regenerar con: ../fixtures.sh build
+mod token_bdb {
+    soroban_sdk::contractimport!(file = "testdata/token_bdb.wasm");
+}
+
+/// Helper: factory inicializada con el wasm de TokenBDB subido
+fn setup(env: &Env) -> (TokenFactoryClient<'_>, Address) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let wasm_hash = env.deployer().upload_contract_wasm(token_bdb::WASM);
+
+    let factory_id = env.register(TokenFactory, ());
+    let factory = TokenFactoryClient::new(env, &factory_id);
+    factory.initialize(&admin, &wasm_hash);
+
+    (factory, admin)
+}
+
+/// Helper: parámetros de un token con `symbol` y `admin`
+fn params(env: &Env, admin: &Address, symbol: &str) -> TokenParams {
+    TokenParams {
+        admin: admin.clone(),
+        name: String::from_str(env, "Token BDB"),
+        symbol: String::from_str(env, symbol),
+        decimals: 7,
+        max_supply: None,
+    }
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    let (factory, admin) = setup(&env);
+
+    assert_eq!(factory.admin(), admin);
+    assert_eq!(factory.token_count(), 0);
+
+    let result = factory.try_initialize(&admin, &factory.wasm_hash());
+    assert_eq!(result, Err(Ok(FactoryError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_initialize_requires_admin_auth() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let wasm_hash = env.deployer().upload_contract_wasm(token_bdb::WASM);
+    let factory = TokenFactoryClient::new(&env, &env.register(TokenFactory, ()));
+
+    // Sin la firma del admin no se guarda nada
+    assert!(factory.try_initialize(&admin, &wasm_hash).is_err());
+    assert_eq!(factory.try_admin(), Err(Ok(FactoryError::NotInitialized)));
+
+    env.mock_all_auths();
+    factory.initialize(&admin, &wasm_hash);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(factory.admin(), admin);
+}
+
+#[test]
+fn test_deploy_initializes_token() {
+    let env = Env::default();
+    let (factory, _) = setup(&env);
+    let deployer = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[1; 32]);
+
+    let token_id = factory.deploy(&deployer, &salt, &params(&env, &token_admin, "BDB"));
+
+    // El token queda inicializado y operable
+    let token = token_bdb::Client::new(&env, &token_id);
+    assert_eq!(token.symbol(), String::from_str(&env, "BDB"));
+    assert_eq!(token.admin(), Some(token_admin.clone()));
+    let user = Address::generate(&env);
+    token.mint(&token_admin, &user, &1_000);
+    assert_eq!(token.balance(&user), 1_000);
+
+    // Y registrado
+    let record = factory.token(&token_id);
+    assert_eq!(record.deployer, deployer);
+    assert_eq!(record.admin, token_admin);
+    assert!(!record.verified);
+    assert_eq!(factory.token_count(), 1);
+}
+
+#[test]
+fn test_deploy_address_is_deterministic() {
+    let env = Env::default();
+    let (factory, _) = setup(&env);
+    let deployer = Address::generate(&env);
+    let other = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[7; 32]);
+
+    // La dirección se conoce antes del deploy y depende del deployer
+    let expected = factory.deployed_address(&deployer, &salt);
+    assert_ne!(expected, factory.deployed_address(&other, &salt));
+
+    let token_id = factory.deploy(&deployer, &salt, &params(&env, &deployer, "BDB"));
+    assert_eq!(token_id, expected);
+
+    // El mismo deployer no puede reutilizar el salt
+    let result = factory.try_deploy(&deployer, &salt, &params(&env, &deployer, "BDB"));
+    assert_eq!(result, Err(Ok(FactoryError::AlreadyDeployed)));
+
+    // Otro deployer sí
+    let token_id = factory.deploy(&other, &salt, &params(&env, &other, "BDB"));
+    assert_ne!(token_id, expected);
+}
+
+#[test]
+fn test_deploy_rolls_back_on_failed_initialize() {
+    let env = Env::default();
+    let (factory, admin) = setup(&env);
+    let salt = BytesN::from_array(&env, &[2; 32]);
+
+    // Decimales fuera de rango: TokenBDB rechaza el initialize
+    let mut bad = params(&env, &admin, "BDB");
+    bad.decimals = 50;
+    let result = factory.try_deploy(&admin, &salt, &bad);
+    assert_eq!(result, Err(Ok(FactoryError::InitializationFailed)));
+    assert_eq!(factory.token_count(), 0);
+
+    // El salt sigue libre
+    let token_id = factory.deploy(&admin, &salt, &params(&env, &admin, "BDB"));
+    assert_eq!(token_id, factory.deployed_address(&admin, &salt));
+}
+
+#[test]
+fn test_registry_pagination() {
+    let env = Env::default();
+    let (factory, admin) = setup(&env);
+
+    let mut deployed = std::vec::Vec::new();
+    for i in 0..5u8 {
+        let salt = BytesN::from_array(&env, &[i; 32]);
+        deployed.push(factory.deploy(&admin, &salt, &params(&env, &admin, "BDB")));
+    }
+
+    let page = factory.tokens(&0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().address, deployed[0]);
+    assert_eq!(page.get(1).unwrap().address, deployed[1]);
+
+    let page = factory.tokens(&4, &2);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().address, deployed[4]);
+
+    assert_eq!(factory.tokens(&10, &2).len(), 0);
+    assert_eq!(factory.tokens(&0, &0).len(), 0);
+    assert_eq!(factory.tokens(&1, &u32::MAX).len(), 4);
+}
+
+#[test]
+fn test_query_by_symbol_and_admin() {
+    let env = Env::default();
+    let (factory, _) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let a1 = factory.deploy(&alice, &BytesN::from_array(&env, &[1; 32]), &params(&env, &alice, "AAA"));
+    let a2 = factory.deploy(&alice, &BytesN::from_array(&env, &[2; 32]), &params(&env, &alice, "BBB"));
+    let b1 = factory.deploy(&bob, &BytesN::from_array(&env, &[1; 32]), &params(&env, &bob, "AAA"));
+
+    let aaa = String::from_str(&env, "AAA");
+    let by_symbol = factory.tokens_by_symbol(&aaa, &0, &10);
+    assert_eq!(by_symbol.len(), 2);
+    assert_eq!(by_symbol.get(0).unwrap().address, a1);
+    assert_eq!(by_symbol.get(1).unwrap().address, b1);
+    assert_eq!(factory.symbol_token_count(&aaa), 2);
+
+    let by_admin = factory.tokens_by_admin(&alice, &0, &10);
+    assert_eq!(by_admin.len(), 2);
+    assert_eq!(by_admin.get(1).unwrap().address, a2);
+    assert_eq!(factory.admin_token_count(&alice), 2);
+    assert_eq!(factory.admin_token_count(&bob), 1);
+
+    let zzz = String::from_str(&env, "ZZZ");
+    assert_eq!(factory.tokens_by_symbol(&zzz, &0, &10).len(), 0);
+    assert_eq!(factory.symbol_token_count(&zzz), 0);
+}
+
+#[test]
+fn test_query_by_symbol_and_admin_pagination() {
+    let env = Env::default();
+    let (factory, admin) = setup(&env);
+    let symbol = String::from_str(&env, "BDB");
+
+    let mut deployed = std::vec::Vec::new();
+    for i in 0..5u8 {
+        let salt = BytesN::from_array(&env, &[i; 32]);
+        deployed.push(factory.deploy(&admin, &salt, &params(&env, &admin, "BDB")));
+    }
+
+    let page = factory.tokens_by_symbol(&symbol, &1, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().address, deployed[1]);
+    assert_eq!(page.get(1).unwrap().address, deployed[2]);
+
+    let page = factory.tokens_by_admin(&admin, &3, &50);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().address, deployed[3]);
+    assert_eq!(page.get(1).unwrap().address, deployed[4]);
+
+    assert_eq!(factory.tokens_by_symbol(&symbol, &5, &2).len(), 0);
+    assert_eq!(factory.tokens_by_symbol(&symbol, &0, &0).len(), 0);
+    assert_eq!(factory.tokens_by_admin(&admin, &1, &u32::MAX).len(), 4);
+}
+
+#[test]
+fn test_deploy_requires_token_admin_auth() {
+    let env = Env::default();
+    let (factory, _) = setup(&env);
+    let deployer = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[3; 32]);
+
+    // Firman el deployer y el admin del token
+    factory.deploy(&deployer, &salt, &params(&env, &token_admin, "BDB"));
+    let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(address, _)| address).collect();
+    assert_eq!(signers, std::vec![deployer.clone(), token_admin.clone()]);
+
+    // Sin la firma del admin no se registra nada a su nombre
+    env.set_auths(&[]);
+    let result = factory.try_deploy(
+        &deployer,
+        &BytesN::from_array(&env, &[4; 32]),
+        &params(&env, &token_admin, "BDB")
+    );
+    assert!(result.is_err());
+    assert_eq!(factory.admin_token_count(&token_admin), 1);
+}
+
+#[test]
+fn test_set_verified() {
+    let env = Env::default();
+    let (factory, admin) = setup(&env);
+    let token_id = factory.deploy(&admin, &BytesN::from_array(&env, &[1; 32]), &params(&env, &admin, "BDB"));
+
+    factory.set_verified(&token_id, &true);
+    assert_eq!(env.auths()[0].0, admin);
+    assert!(factory.token(&token_id).verified);
+    assert!(factory.tokens_by_admin(&admin, &0, &1).get(0).unwrap().verified);
+
+    factory.set_verified(&token_id, &false);
+    assert!(!factory.token(&token_id).verified);
+
+    let unknown = Address::generate(&env);
+    let result = factory.try_set_verified(&unknown, &true);
+    assert_eq!(result, Err(Ok(FactoryError::TokenNotFound)));
+}
+
+#[test]
+fn test_set_wasm_hash() {
+    let env = Env::default();
+    let (factory, admin) = setup(&env);
+    let new_hash = BytesN::from_array(&env, &[9; 32]);
+
+    factory.set_wasm_hash(&new_hash);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(factory.wasm_hash(), new_hash);
+}
+
+#[test]
+fn test_not_initialized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let factory_id = env.register(TokenFactory, ());
+    let factory = TokenFactoryClient::new(&env, &factory_id);
+    let admin = Address::generate(&env);
+
+    let result = factory.try_deploy(&admin, &BytesN::from_array(&env, &[1; 32]), &params(&env, &admin, "BDB"));
+    assert_eq!(result, Err(Ok(FactoryError::NotInitialized)));
+}
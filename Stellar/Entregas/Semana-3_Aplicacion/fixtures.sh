@@ -0,0 +1,87 @@
+#!/usr/bin/env bash
+# Wasm de los tokens que usan los tests (carpetas testdata/)
+#
+#   ./fixtures.sh build   recompila TokenBDB y WorxToken y copia los wasm
+#   ./fixtures.sh check   falla si algún wasm no corresponde a las fuentes actuales
+#
+# Junto a cada wasm queda un .sha256 con la huella de las fuentes con las que
+# se compiló: la ruta y el contenido de Cargo.toml y src/ (sin test.rs) del
+# token y de token_core, más el Cargo.lock del token. test_fixtures_are_current
+# (tests de token_bdb) recalcula la huella de todos los wasm de FIXTURES y
+# falla si alguno quedó desactualizado.
+set -euo pipefail
+cd "$(dirname "$0")"
+
+# wasm de testdata:crate que lo genera
+FIXTURES=(
+    "token_bdb/testdata/token_bdb_v3.wasm:token_bdb"
+    "airdrop/testdata/token_bdb.wasm:token_bdb"
+    "governor/testdata/token_bdb.wasm:token_bdb"
+    "factory/testdata/token_bdb.wasm:token_bdb"
+    "worx_token/testdata/worx_token_v3.wasm:worx_token"
+    "vesting/testdata/worx_token.wasm:worx_token"
+)
+
+# Directorio de fuentes de cada token
+src_dir() {
+    case "$1" in
+        worx_token) echo "worx_token/contracts/src" ;;
+        *) echo "$1/src" ;;
+    esac
+}
+
+# Archivos que entran en la huella, en orden de bytes
+sources() {
+    local crate="$1"
+    printf '%s\n' \
+        token_core/Cargo.toml token_core/src/*.rs \
+        "$crate/Cargo.toml" "$crate/Cargo.lock" "$(src_dir "$crate")"/*.rs \
+        | grep -v '/test\.rs$' | LC_ALL=C sort
+}
+
+# sha256 de cada ruta seguida de su contenido
+fingerprint() {
+    local file
+    for file in $(sources "$1"); do
+        printf '%s\n' "$file"
+        cat "$file"
+    done | sha256sum | cut -d' ' -f1
+}
+
+build() {
+    local crate
+    for crate in token_bdb worx_token; do
+        (cd "$crate" && cargo build --release --target wasm32v1-none)
+    done
+
+    local fixture wasm crate
+    for fixture in "${FIXTURES[@]}"; do
+        wasm="${fixture%%:*}"
+        crate="${fixture##*:}"
+        cp "$crate/target/wasm32v1-none/release/$crate.wasm" "$wasm"
+        fingerprint "$crate" > "$wasm.sha256"
+        echo "$wasm"
+    done
+}
+
+check() {
+    local fixture wasm crate stale=0
+    for fixture in "${FIXTURES[@]}"; do
+        wasm="${fixture%%:*}"
+        crate="${fixture##*:}"
+        if [ "$(cat "$wasm.sha256" 2>/dev/null)" != "$(fingerprint "$crate")" ]; then
+            echo "desactualizado: $wasm (correr ./fixtures.sh build)"
+            stale=1
+        fi
+    done
+    return "$stale"
+}
+
+case "${1:-}" in
+    build) build ;;
+    check) check ;;
+    *)
+        echo "uso: $0 build|check" >&2
+        exit 2
+        ;;
+esac
@@ -0,0 +1,55 @@
+// src/errors.rs
+use soroban_sdk::contracterror;
+
+/// Enum de errores personalizados del governor
+///
+/// Cada error tiene un código único para debugging en el ledger
+/// Los códigos empiezan en 1 (0 está reservado para "sin error")
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GovernorError {
+    /// El contrato ya fue inicializado
+    /// Se lanza si se intenta llamar initialize() dos veces
+    AlreadyInitialized = 1,
+
+    /// El contrato no ha sido inicializado
+    /// Debe llamarse initialize() primero
+    NotInitialized = 2,
+
+    /// Configuración inválida
+    /// voting_period = 0, plazos sobre el máximo, quorum o threshold fuera de 0-10000 bps
+    InvalidConfig = 3,
+
+    /// El proponente no alcanza el proposal_threshold
+    /// Se miden sus votos en el ledger anterior a la propuesta
+    BelowProposalThreshold = 4,
+
+    /// Propuesta sin llamadas o con más de MAX_CALLS
+    InvalidProposal = 5,
+
+    /// No existe una propuesta con ese id
+    ProposalNotFound = 6,
+
+    /// La propuesta no está en período de votación
+    /// cast_vote() solo se acepta con la propuesta en estado Active
+    VotingClosed = 7,
+
+    /// La cuenta ya votó en esta propuesta
+    AlreadyVoted = 8,
+
+    /// La cuenta no tenía votos en el ledger de snapshot
+    NoVotingPower = 9,
+
+    /// La propuesta no fue aprobada (o ya se ejecutó)
+    /// execute() solo se acepta con la propuesta en estado Succeeded
+    ProposalNotSucceeded = 10,
+
+    /// El token falló al informar votos o supply
+    /// Ej: ledger sin cerrar o historial de checkpoints no disponible
+    TokenCallFailed = 11,
+
+    /// Overflow en operación aritmética
+    /// Plazos de la propuesta, cálculo del quorum, conteo de votos o threshold
+    OverflowError = 12,
+}
@@ -0,0 +1,381 @@
+// src/lib.rs
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contractevent, contractimpl, Address, Env, String, Val, Vec,
+};
+
+mod errors;
+mod storage;
+
+pub use errors::GovernorError;
+pub use storage::{Call, GovernorConfig, Proposal, ProposalState, VoteType};
+use storage::DataKey;
+
+// EVENTOS
+
+#[contractevent]
+pub struct ProposalCreatedEvent {
+    pub proposal_id: u32,
+    pub proposer: Address,
+    pub description: String,
+    pub snapshot_ledger: u32,
+    pub end_ledger: u32,
+    pub quorum: i128,
+}
+
+#[contractevent]
+pub struct VoteCastEvent {
+    pub proposal_id: u32,
+    pub voter: Address,
+    pub support: VoteType,
+    pub weight: i128,
+}
+
+#[contractevent]
+pub struct ProposalExecutedEvent {
+    pub proposal_id: u32,
+}
+
+/// Máximo de llamadas por propuesta
+const MAX_CALLS: u32 = 10;
+
+/// Basis points que representan el 100%
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Máximo de `voting_delay` en ledgers (~3 días)
+const MAX_VOTING_DELAY: u32 = 50_000;
+
+/// Máximo de `voting_period` en ledgers (~6 días)
+///
+/// Junto con `MAX_VOTING_DELAY` queda dentro del TTL que recibe la
+/// propuesta al crearse (200_000 ledgers).
+const MAX_VOTING_PERIOD: u32 = 100_000;
+
+/// Funciones del token que usa el governor (TokenBDB)
+///
+/// Ambas fallan si el ledger todavía no cerró; el governor solo consulta
+/// snapshots pasados y las invoca con `try_` (ver `past_votes` y
+/// `past_supply`).
+#[contractclient(name = "VotesClient")]
+pub trait VotesInterface {
+    fn get_past_votes(env: Env, account: Address, ledger: u32) -> i128;
+    fn total_supply_at(env: Env, ledger: u32) -> i128;
+}
+
+/// Governor: propuestas votadas con el poder de voto del token
+///
+/// Cualquier cuenta con votos suficientes propone una lista de llamadas
+/// (mint, roles, metadatos del token...). Si la votación alcanza quorum
+/// y threshold, cualquiera puede ejecutarlas: el governor las invoca
+/// con su propia dirección, así que debe tener los roles necesarios.
+#[contract]
+pub struct Governor;
+
+/// Interfaz pública del governor
+pub trait GovernorTrait {
+    /// Fija la configuración (solo una vez)
+    ///
+    /// Requiere autorización de `admin`, la cuenta que despliega el governor.
+    fn initialize(env: Env, admin: Address, config: GovernorConfig) -> Result<(), GovernorError>;
+
+    /// Crea una propuesta y devuelve su id
+    ///
+    /// Requiere autorización del proponente y al menos
+    /// `proposal_threshold` votos en el ledger anterior. El quorum se
+    /// fija acá, sobre el supply de ese mismo ledger.
+    fn propose(
+        env: Env,
+        proposer: Address,
+        calls: Vec<Call>,
+        description: String
+    ) -> Result<u32, GovernorError>;
+
+    /// Vota una propuesta activa con los votos del snapshot
+    ///
+    /// Requiere autorización del votante. Un voto por cuenta.
+    fn cast_vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u32,
+        support: VoteType
+    ) -> Result<i128, GovernorError>;
+
+    /// Ejecuta las llamadas de una propuesta aprobada (cualquiera puede)
+    fn execute(env: Env, proposal_id: u32) -> Result<(), GovernorError>;
+
+    /// Estado actual de una propuesta
+    fn state(env: Env, proposal_id: u32) -> Result<ProposalState, GovernorError>;
+
+    // Métodos de consulta (getters)
+    fn config(env: Env) -> Result<GovernorConfig, GovernorError>;
+    fn proposal(env: Env, proposal_id: u32) -> Result<Proposal, GovernorError>;
+    fn proposal_count(env: Env) -> u32;
+    fn get_vote(env: Env, proposal_id: u32, voter: Address) -> Option<VoteType>;
+}
+
+/// Votos de `account` al cierre de `ledger`, leídos del token
+///
+/// Cualquier falla del token (ledger sin cerrar, historial no disponible,
+/// contrato inexistente) se informa como `TokenCallFailed`.
+fn past_votes(env: &Env, token: &Address, account: &Address, ledger: u32) -> Result<i128, GovernorError> {
+    match VotesClient::new(env, token).try_get_past_votes(account, &ledger) {
+        Ok(Ok(votes)) => Ok(votes),
+        _ => Err(GovernorError::TokenCallFailed),
+    }
+}
+
+/// Supply del token al cierre de `ledger` (mismo manejo de errores que `past_votes`)
+fn past_supply(env: &Env, token: &Address, ledger: u32) -> Result<i128, GovernorError> {
+    match VotesClient::new(env, token).try_total_supply_at(&ledger) {
+        Ok(Ok(supply)) => Ok(supply),
+        _ => Err(GovernorError::TokenCallFailed),
+    }
+}
+
+/// Indica si la votación cerrada aprobó la propuesta
+///
+/// - Quorum: for + abstain >= quorum guardado al crear la propuesta
+/// - Threshold: for supera threshold_bps de for + against
+fn proposal_passed(config: &GovernorConfig, proposal: &Proposal) -> Result<bool, GovernorError> {
+    let participation = proposal.for_votes
+        .checked_add(proposal.abstain_votes)
+        .ok_or(GovernorError::OverflowError)?;
+    if participation < proposal.quorum {
+        return Ok(false);
+    }
+
+    let decided = proposal.for_votes
+        .checked_add(proposal.against_votes)
+        .and_then(|decided| decided.checked_mul(config.threshold_bps as i128))
+        .ok_or(GovernorError::OverflowError)?;
+    let for_bps = proposal.for_votes
+        .checked_mul(BPS_DENOMINATOR)
+        .ok_or(GovernorError::OverflowError)?;
+    Ok(for_bps > decided)
+}
+
+#[contractimpl]
+impl GovernorTrait for Governor {
+    fn initialize(env: Env, admin: Address, config: GovernorConfig) -> Result<(), GovernorError> {
+        // 1. Verificar que no esté inicializado
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(GovernorError::AlreadyInitialized);
+        }
+
+        // 2. Verificar autorización
+        admin.require_auth();
+
+        // 3. Validaciones
+        if config.voting_period == 0
+            || config.voting_period > MAX_VOTING_PERIOD
+            || config.voting_delay > MAX_VOTING_DELAY
+            || config.proposal_threshold < 0
+            || config.quorum_bps as i128 > BPS_DENOMINATOR
+            || config.threshold_bps as i128 >= BPS_DENOMINATOR
+        {
+            return Err(GovernorError::InvalidConfig);
+        }
+
+        // 4. Guardar configuración
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage().instance().set(&DataKey::ProposalCount, &0u32);
+        env.storage().instance().extend_ttl(100_000, 200_000);
+
+        Ok(())
+    }
+
+    fn propose(
+        env: Env,
+        proposer: Address,
+        calls: Vec<Call>,
+        description: String
+    ) -> Result<u32, GovernorError> {
+        // 1. Verificar inicialización y autorización del proponente
+        let config = Self::config(env.clone())?;
+        proposer.require_auth();
+
+        // 2. Validar las llamadas
+        if calls.is_empty() || calls.len() > MAX_CALLS {
+            return Err(GovernorError::InvalidProposal);
+        }
+
+        // 3. Verificar el threshold con los votos del ledger anterior
+        let current = env.ledger().sequence();
+        let previous = current.saturating_sub(1);
+        if config.proposal_threshold > 0 {
+            let votes = past_votes(&env, &config.token, &proposer, previous)?;
+            if votes < config.proposal_threshold {
+                return Err(GovernorError::BelowProposalThreshold);
+            }
+        }
+
+        // 4. Fijar el quorum con el supply del ledger anterior: el del
+        // snapshot todavía no existe, y guardarlo evita depender del
+        // historial del token al cerrar la votación
+        let quorum = past_supply(&env, &config.token, previous)?
+            .checked_mul(config.quorum_bps as i128)
+            .ok_or(GovernorError::OverflowError)?
+            / BPS_DENOMINATOR;
+
+        // 5. Guardar la propuesta
+        let proposal_id = Self::proposal_count(env.clone());
+        let snapshot_ledger = current
+            .checked_add(config.voting_delay)
+            .ok_or(GovernorError::OverflowError)?;
+        let end_ledger = snapshot_ledger
+            .checked_add(config.voting_period)
+            .ok_or(GovernorError::OverflowError)?;
+        let proposal = Proposal {
+            proposer: proposer.clone(),
+            description: description.clone(),
+            calls,
+            snapshot_ledger,
+            end_ledger,
+            quorum,
+            for_votes: 0,
+            against_votes: 0,
+            abstain_votes: 0,
+            executed: false,
+        };
+        let key = DataKey::Proposal(proposal_id);
+        env.storage().persistent().set(&key, &proposal);
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+        let next_id = proposal_id.checked_add(1).ok_or(GovernorError::OverflowError)?;
+        env.storage().instance().set(&DataKey::ProposalCount, &next_id);
+        env.storage().instance().extend_ttl(100_000, 200_000);
+
+        // 6. Emitir evento
+        ProposalCreatedEvent {
+            proposal_id,
+            proposer,
+            description,
+            snapshot_ledger,
+            end_ledger,
+            quorum,
+        }.publish(&env);
+
+        Ok(proposal_id)
+    }
+
+    fn cast_vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u32,
+        support: VoteType
+    ) -> Result<i128, GovernorError> {
+        // 1. Verificar autorización del votante
+        voter.require_auth();
+
+        // 2. Solo se vota en propuestas activas, una vez por cuenta
+        if Self::state(env.clone(), proposal_id)? != ProposalState::Active {
+            return Err(GovernorError::VotingClosed);
+        }
+        let vote_key = DataKey::Vote(proposal_id, voter.clone());
+        if env.storage().persistent().has(&vote_key) {
+            return Err(GovernorError::AlreadyVoted);
+        }
+
+        // 3. Leer los votos al snapshot
+        let config = Self::config(env.clone())?;
+        let mut proposal = Self::proposal(env.clone(), proposal_id)?;
+        let weight = past_votes(&env, &config.token, &voter, proposal.snapshot_ledger)?;
+        if weight <= 0 {
+            return Err(GovernorError::NoVotingPower);
+        }
+
+        // 4. Sumar al conteo y registrar el voto
+        let tally = match support {
+            VoteType::Against => &mut proposal.against_votes,
+            VoteType::For => &mut proposal.for_votes,
+            VoteType::Abstain => &mut proposal.abstain_votes,
+        };
+        *tally = tally.checked_add(weight).ok_or(GovernorError::OverflowError)?;
+        let key = DataKey::Proposal(proposal_id);
+        env.storage().persistent().set(&key, &proposal);
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+        env.storage().persistent().set(&vote_key, &support);
+        env.storage().persistent().extend_ttl(&vote_key, 100_000, 200_000);
+
+        // 5. Emitir evento
+        VoteCastEvent {
+            proposal_id,
+            voter,
+            support,
+            weight,
+        }.publish(&env);
+
+        Ok(weight)
+    }
+
+    fn execute(env: Env, proposal_id: u32) -> Result<(), GovernorError> {
+        // 1. Solo propuestas aprobadas y sin ejecutar
+        if Self::state(env.clone(), proposal_id)? != ProposalState::Succeeded {
+            return Err(GovernorError::ProposalNotSucceeded);
+        }
+
+        // 2. Marcar como ejecutada antes de invocar (evita re-ejecución)
+        let mut proposal = Self::proposal(env.clone(), proposal_id)?;
+        proposal.executed = true;
+        let key = DataKey::Proposal(proposal_id);
+        env.storage().persistent().set(&key, &proposal);
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+
+        // 3. Invocar cada llamada; si alguna falla se revierte todo
+        for call in proposal.calls.iter() {
+            env.invoke_contract::<Val>(&call.contract, &call.function, call.args);
+        }
+
+        // 4. Emitir evento
+        ProposalExecutedEvent { proposal_id }.publish(&env);
+
+        Ok(())
+    }
+
+    fn state(env: Env, proposal_id: u32) -> Result<ProposalState, GovernorError> {
+        let proposal = Self::proposal(env.clone(), proposal_id)?;
+        if proposal.executed {
+            return Ok(ProposalState::Executed);
+        }
+
+        let current = env.ledger().sequence();
+        if current <= proposal.snapshot_ledger {
+            return Ok(ProposalState::Pending);
+        }
+        if current <= proposal.end_ledger {
+            return Ok(ProposalState::Active);
+        }
+
+        let config = Self::config(env.clone())?;
+        if proposal_passed(&config, &proposal)? {
+            Ok(ProposalState::Succeeded)
+        } else {
+            Ok(ProposalState::Defeated)
+        }
+    }
+
+    fn config(env: Env) -> Result<GovernorConfig, GovernorError> {
+        env.storage().instance()
+            .get(&DataKey::Config)
+            .ok_or(GovernorError::NotInitialized)
+    }
+
+    fn proposal(env: Env, proposal_id: u32) -> Result<Proposal, GovernorError> {
+        env.storage().persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(GovernorError::ProposalNotFound)
+    }
+
+    fn proposal_count(env: Env) -> u32 {
+        env.storage().instance()
+            .get(&DataKey::ProposalCount)
+            .unwrap_or(0)
+    }
+
+    fn get_vote(env: Env, proposal_id: u32, voter: Address) -> Option<VoteType> {
+        env.storage().persistent().get(&DataKey::Vote(proposal_id, voter))
+    }
+}
+
+#[cfg(test)]
+mod test;
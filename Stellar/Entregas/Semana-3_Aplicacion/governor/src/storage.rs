@@ -0,0 +1,100 @@
+// src/storage.rs
+use soroban_sdk::{contracttype, Address, String, Symbol, Val, Vec};
+
+/// Enum que define todas las claves de almacenamiento
+///
+/// - Instance Storage: configuración y contador de propuestas
+/// - Persistent Storage: propuestas y votos (crecen sin límite)
+#[contracttype]
+pub enum DataKey {
+    /// Configuración del governor - Instance Storage
+    Config,
+
+    /// Cantidad de propuestas creadas - Instance Storage
+    /// El id de la próxima propuesta es este valor
+    ProposalCount,
+
+    /// Propuesta por id - Persistent Storage
+    Proposal(u32),
+
+    /// Voto de una cuenta en una propuesta - Persistent Storage
+    /// Tupla (proposal_id, votante); solo existe si ya votó
+    Vote(u32, Address),
+}
+
+/// Parámetros de gobernanza (se fijan en initialize)
+///
+/// Los plazos se miden en ledgers (~5 segundos cada uno). Quorum y
+/// threshold van en basis points (10000 = 100%).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernorConfig {
+    /// Token con votos delegados (TokenBDB)
+    pub token: Address,
+    /// Ledgers entre la creación y el snapshot de votos
+    pub voting_delay: u32,
+    /// Ledgers de votación después del snapshot
+    pub voting_period: u32,
+    /// Votos mínimos del proponente para crear propuestas
+    pub proposal_threshold: i128,
+    /// Votos for + abstain mínimos, en bps del supply al crear la propuesta
+    pub quorum_bps: u32,
+    /// Porcentaje de for sobre for + against que hay que superar, en bps
+    pub threshold_bps: u32,
+}
+
+/// Llamada cross-contract que ejecuta una propuesta aprobada
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Call {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+/// Propuesta con su conteo de votos
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub proposer: Address,
+    pub description: String,
+    pub calls: Vec<Call>,
+    /// Los votos se leen del token al cierre de este ledger
+    pub snapshot_ledger: u32,
+    /// Último ledger en que se aceptan votos
+    pub end_ledger: u32,
+    /// Votos for + abstain mínimos: quorum_bps del supply del ledger
+    /// anterior a la propuesta
+    pub quorum: i128,
+    pub for_votes: i128,
+    pub against_votes: i128,
+    pub abstain_votes: i128,
+    pub executed: bool,
+}
+
+/// Sentido de un voto
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VoteType {
+    Against = 0,
+    For = 1,
+    Abstain = 2,
+}
+
+/// Estado de una propuesta (se calcula, no se guarda)
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ProposalState {
+    /// Creada, esperando el snapshot
+    Pending = 0,
+    /// En período de votación
+    Active = 1,
+    /// Votación cerrada sin quorum o sin superar el threshold
+    Defeated = 2,
+    /// Aprobada, lista para ejecutar
+    Succeeded = 3,
+    /// Ya ejecutada
+    Executed = 4,
+}
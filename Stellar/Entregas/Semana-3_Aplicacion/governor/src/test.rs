@@ -0,0 +1,447 @@
+// src/test.rs
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger},
+    vec, Address, Env, Event, IntoVal, String, Symbol,
+};
+
+// TokenBDB compilado a wasm: aporta los votos delegados
+// Regenerar con: ../fixtures.sh build
+mod token_bdb {
+    soroban_sdk::contractimport!(file = "testdata/token_bdb.wasm");
+}
+
+const VOTING_DELAY: u32 = 5;
+const VOTING_PERIOD: u32 = 100;
+
+/// Helper: fija el número de secuencia del ledger
+fn set_sequence(env: &Env, sequence: u32) {
+    env.ledger().with_mut(|li| {
+        li.sequence_number = sequence;
+    });
+}
+
+/// Helper: TokenBDB con 1000 tokens delegados + governor con rol Minter
+///
+/// alice 600, bob 300, carol 100 (cada uno delegado en sí mismo).
+/// Quorum 40% del supply, threshold 50%, mínimo 100 votos para proponer.
+fn setup(env: &Env) -> (GovernorClient<'_>, token_bdb::Client<'_>, [Address; 3]) {
+    env.mock_all_auths();
+    set_sequence(env, 10);
+
+    let admin = Address::generate(env);
+    let token_id = env.register(token_bdb::WASM, ());
+    let token = token_bdb::Client::new(env, &token_id);
+    token.initialize(
+        &admin,
+        &String::from_str(env, "Builder Token"),
+        &String::from_str(env, "BDB"),
+        &7,
+        &None
+    );
+
+    let voters = [Address::generate(env), Address::generate(env), Address::generate(env)];
+    for (voter, amount) in voters.iter().zip([600, 300, 100]) {
+        token.mint(&admin, voter, &amount);
+        token.delegate(voter, voter);
+    }
+
+    let governor_id = env.register(Governor, ());
+    let governor = GovernorClient::new(env, &governor_id);
+    governor.initialize(&admin, &GovernorConfig {
+        token: token_id,
+        voting_delay: VOTING_DELAY,
+        voting_period: VOTING_PERIOD,
+        proposal_threshold: 100,
+        quorum_bps: 4_000,
+        threshold_bps: 5_000,
+    });
+    token.grant_role(&token_bdb::Role::Minter, &governor_id);
+
+    // Los votos se leen de ledgers cerrados
+    set_sequence(env, 11);
+
+    (governor, token, voters)
+}
+
+/// Helper: llamada a mint() del token desde el governor
+fn mint_call(env: &Env, governor: &GovernorClient, token: &token_bdb::Client, to: &Address, amount: i128) -> Call {
+    Call {
+        contract: token.address.clone(),
+        function: Symbol::new(env, "mint"),
+        args: vec![
+            env,
+            governor.address.into_val(env),
+            to.into_val(env),
+            amount.into_val(env),
+        ],
+    }
+}
+
+/// Helper: crea una propuesta de mint y la deja en período de votación
+fn propose_mint(env: &Env, governor: &GovernorClient, token: &token_bdb::Client, proposer: &Address, to: &Address) -> u32 {
+    let proposal_id = governor.propose(
+        proposer,
+        &vec![env, mint_call(env, governor, token, to, 500)],
+        &String::from_str(env, "Mint de 500 BDB para el equipo"),
+    );
+    set_sequence(env, 11 + VOTING_DELAY + 1);
+    proposal_id
+}
+
+/// Test: ciclo completo propuesta -> votación -> ejecución
+#[test]
+fn test_proposal_lifecycle() {
+    let env = Env::default();
+    let (governor, token, [alice, bob, _carol]) = setup(&env);
+    let team = Address::generate(&env);
+
+    let proposal_id = governor.propose(
+        &alice,
+        &vec![&env, mint_call(&env, &governor, &token, &team, 500)],
+        &String::from_str(&env, "Mint de 500 BDB para el equipo"),
+    );
+    assert_eq!(proposal_id, 0);
+    assert_eq!(governor.proposal_count(), 1);
+    assert_eq!(governor.state(&proposal_id), ProposalState::Pending);
+
+    set_sequence(&env, 11 + VOTING_DELAY + 1);
+    assert_eq!(governor.state(&proposal_id), ProposalState::Active);
+    assert_eq!(governor.cast_vote(&alice, &proposal_id, &VoteType::For), 600);
+    assert_eq!(governor.cast_vote(&bob, &proposal_id, &VoteType::Against), 300);
+    assert_eq!(governor.get_vote(&proposal_id, &bob), Some(VoteType::Against));
+
+    set_sequence(&env, 11 + VOTING_DELAY + VOTING_PERIOD + 1);
+    assert_eq!(governor.state(&proposal_id), ProposalState::Succeeded);
+
+    governor.execute(&proposal_id);
+    let event = ProposalExecutedEvent { proposal_id };
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![&env, (governor.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(token.balance(&team), 500);
+    assert_eq!(governor.state(&proposal_id), ProposalState::Executed);
+    assert_eq!(
+        governor.try_execute(&proposal_id),
+        Err(Ok(GovernorError::ProposalNotSucceeded))
+    );
+}
+
+/// Test: los votos se leen al snapshot, no al momento de votar
+#[test]
+fn test_votes_read_at_snapshot() {
+    let env = Env::default();
+    let (governor, token, [alice, bob, carol]) = setup(&env);
+    let proposal_id = propose_mint(&env, &governor, &token, &alice, &carol);
+
+    // Movimientos posteriores al snapshot no cambian el peso del voto
+    token.transfer(&alice, &bob, &500);
+    assert_eq!(governor.cast_vote(&alice, &proposal_id, &VoteType::For), 600);
+    assert_eq!(governor.cast_vote(&bob, &proposal_id, &VoteType::Against), 300);
+
+    let proposal = governor.proposal(&proposal_id);
+    assert_eq!(proposal.for_votes, 600);
+    assert_eq!(proposal.against_votes, 300);
+    assert_eq!(proposal.abstain_votes, 0);
+}
+
+/// Test: sin quorum la propuesta se rechaza aunque gane el for
+#[test]
+fn test_defeated_without_quorum() {
+    let env = Env::default();
+    let (governor, token, [alice, _bob, carol]) = setup(&env);
+    let proposal_id = propose_mint(&env, &governor, &token, &alice, &carol);
+
+    // 100 votos < 40% de 1000
+    governor.cast_vote(&carol, &proposal_id, &VoteType::For);
+
+    set_sequence(&env, 11 + VOTING_DELAY + VOTING_PERIOD + 1);
+    assert_eq!(governor.state(&proposal_id), ProposalState::Defeated);
+    assert_eq!(
+        governor.try_execute(&proposal_id),
+        Err(Ok(GovernorError::ProposalNotSucceeded))
+    );
+}
+
+/// Test: abstain cuenta para el quorum pero no para el threshold
+#[test]
+fn test_abstain_and_against() {
+    let env = Env::default();
+    let (governor, token, [alice, bob, carol]) = setup(&env);
+    let team = Address::generate(&env);
+
+    // alice se abstiene: hay quorum, pero against (300) supera a for (100)
+    let defeated = propose_mint(&env, &governor, &token, &alice, &team);
+    governor.cast_vote(&alice, &defeated, &VoteType::Abstain);
+    governor.cast_vote(&bob, &defeated, &VoteType::Against);
+    governor.cast_vote(&carol, &defeated, &VoteType::For);
+
+    // bob se abstiene y carol vota a favor: 100 for contra 0 against
+    let passed = governor.propose(
+        &alice,
+        &vec![&env, mint_call(&env, &governor, &token, &team, 500)],
+        &String::from_str(&env, "Segundo intento"),
+    );
+    set_sequence(&env, 11 + 2 * VOTING_DELAY + 2);
+    governor.cast_vote(&bob, &passed, &VoteType::Abstain);
+    governor.cast_vote(&carol, &passed, &VoteType::For);
+
+    set_sequence(&env, 11 + 2 * VOTING_DELAY + VOTING_PERIOD + 2);
+    assert_eq!(governor.state(&defeated), ProposalState::Defeated);
+    assert_eq!(governor.state(&passed), ProposalState::Succeeded);
+}
+
+/// Test: errores de cast_vote
+#[test]
+fn test_vote_errors() {
+    let env = Env::default();
+    let (governor, token, [alice, bob, _carol]) = setup(&env);
+    let proposal_id = governor.propose(
+        &alice,
+        &vec![&env, mint_call(&env, &governor, &token, &bob, 500)],
+        &String::from_str(&env, "Mint"),
+    );
+
+    // Antes del snapshot
+    assert_eq!(
+        governor.try_cast_vote(&alice, &proposal_id, &VoteType::For),
+        Err(Ok(GovernorError::VotingClosed))
+    );
+
+    set_sequence(&env, 11 + VOTING_DELAY + 1);
+    governor.cast_vote(&alice, &proposal_id, &VoteType::For);
+    assert_eq!(
+        governor.try_cast_vote(&alice, &proposal_id, &VoteType::Against),
+        Err(Ok(GovernorError::AlreadyVoted))
+    );
+    assert_eq!(
+        governor.try_cast_vote(&Address::generate(&env), &proposal_id, &VoteType::For),
+        Err(Ok(GovernorError::NoVotingPower))
+    );
+    assert_eq!(
+        governor.try_cast_vote(&alice, &7, &VoteType::For),
+        Err(Ok(GovernorError::ProposalNotFound))
+    );
+
+    // Después del cierre
+    set_sequence(&env, 11 + VOTING_DELAY + VOTING_PERIOD + 1);
+    assert_eq!(
+        governor.try_cast_vote(&bob, &proposal_id, &VoteType::For),
+        Err(Ok(GovernorError::VotingClosed))
+    );
+}
+
+/// Test: errores de propose
+#[test]
+fn test_propose_errors() {
+    let env = Env::default();
+    let (governor, token, [alice, _bob, carol]) = setup(&env);
+
+    // carol tiene 100 votos (alcanza el mínimo), una cuenta nueva no
+    let call = mint_call(&env, &governor, &token, &carol, 1);
+    governor.propose(&carol, &vec![&env, call.clone()], &String::from_str(&env, "Ok"));
+    assert_eq!(
+        governor.try_propose(
+            &Address::generate(&env),
+            &vec![&env, call.clone()],
+            &String::from_str(&env, "Sin votos")
+        ),
+        Err(Ok(GovernorError::BelowProposalThreshold))
+    );
+
+    assert_eq!(
+        governor.try_propose(&alice, &Vec::new(&env), &String::from_str(&env, "Vacía")),
+        Err(Ok(GovernorError::InvalidProposal))
+    );
+    let mut too_many = Vec::new(&env);
+    for _ in 0..=MAX_CALLS {
+        too_many.push_back(call.clone());
+    }
+    assert_eq!(
+        governor.try_propose(&alice, &too_many, &String::from_str(&env, "Larga")),
+        Err(Ok(GovernorError::InvalidProposal))
+    );
+}
+
+/// Test: si una llamada falla, la ejecución se revierte completa
+#[test]
+fn test_execute_reverts_all_calls() {
+    let env = Env::default();
+    let (governor, token, [alice, bob, _carol]) = setup(&env);
+    let team = Address::generate(&env);
+
+    // La segunda llamada requiere el rol Pauser, que el governor no tiene
+    let pause = Call {
+        contract: token.address.clone(),
+        function: Symbol::new(&env, "pause"),
+        args: vec![
+            &env,
+            governor.address.into_val(&env),
+            token_bdb::PauseTarget::Transfer.into_val(&env),
+        ],
+    };
+    let proposal_id = governor.propose(
+        &alice,
+        &vec![&env, mint_call(&env, &governor, &token, &team, 500), pause],
+        &String::from_str(&env, "Mint y pausa"),
+    );
+    set_sequence(&env, 11 + VOTING_DELAY + 1);
+    governor.cast_vote(&alice, &proposal_id, &VoteType::For);
+    governor.cast_vote(&bob, &proposal_id, &VoteType::For);
+    set_sequence(&env, 11 + VOTING_DELAY + VOTING_PERIOD + 1);
+
+    assert!(governor.try_execute(&proposal_id).is_err());
+    assert_eq!(token.balance(&team), 0);
+    assert_eq!(governor.state(&proposal_id), ProposalState::Succeeded);
+
+    // Con el rol otorgado, la misma propuesta se ejecuta
+    token.grant_role(&token_bdb::Role::Pauser, &governor.address);
+    governor.execute(&proposal_id);
+    assert_eq!(token.balance(&team), 500);
+    assert!(token.is_paused(&token_bdb::PauseTarget::Transfer));
+}
+
+/// Test: el quorum se fija al crear la propuesta
+#[test]
+fn test_quorum_fixed_at_proposal() {
+    let env = Env::default();
+    let (governor, token, [alice, bob, carol]) = setup(&env);
+    let team = Address::generate(&env);
+
+    let proposal_id = governor.propose(
+        &alice,
+        &vec![&env, mint_call(&env, &governor, &token, &team, 500)],
+        &String::from_str(&env, "Mint"),
+    );
+    let event = ProposalCreatedEvent {
+        proposal_id,
+        proposer: alice.clone(),
+        description: String::from_str(&env, "Mint"),
+        snapshot_ledger: 11 + VOTING_DELAY,
+        end_ledger: 11 + VOTING_DELAY + VOTING_PERIOD,
+        quorum: 400,
+    };
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![&env, (governor.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(governor.proposal(&proposal_id).quorum, 400);
+
+    // Un mint antes del snapshot no sube el quorum ya guardado
+    token.mint(&governor.address, &Address::generate(&env), &9_000);
+    set_sequence(&env, 11 + VOTING_DELAY + 1);
+    governor.cast_vote(&bob, &proposal_id, &VoteType::For);
+    governor.cast_vote(&carol, &proposal_id, &VoteType::Abstain);
+
+    set_sequence(&env, 11 + VOTING_DELAY + VOTING_PERIOD + 1);
+    assert_eq!(governor.state(&proposal_id), ProposalState::Succeeded);
+}
+
+/// Test: errores del token y overflow se informan sin panic
+#[test]
+fn test_token_failures() {
+    let env = Env::default();
+    let (governor, token, [alice, _bob, carol]) = setup(&env);
+    let call = mint_call(&env, &governor, &token, &carol, 1);
+
+    // Supply * quorum_bps no entra en i128
+    set_sequence(&env, 20);
+    token.mint(&governor.address, &Address::generate(&env), &(i128::MAX / 2));
+    set_sequence(&env, 21);
+    assert_eq!(
+        governor.try_propose(&alice, &vec![&env, call.clone()], &String::from_str(&env, "Overflow")),
+        Err(Ok(GovernorError::OverflowError))
+    );
+
+    // Token que no responde (no es un contrato)
+    let broken = GovernorClient::new(&env, &env.register(Governor, ()));
+    broken.initialize(&Address::generate(&env), &GovernorConfig {
+        token: Address::generate(&env),
+        ..governor.config()
+    });
+    assert_eq!(
+        broken.try_propose(&alice, &vec![&env, call], &String::from_str(&env, "Sin token")),
+        Err(Ok(GovernorError::TokenCallFailed))
+    );
+}
+
+/// Test: validaciones de initialize
+#[test]
+fn test_initialize_validations() {
+    let env = Env::default();
+    let (governor, _token, _voters) = setup(&env);
+    let config = governor.config();
+    let admin = Address::generate(&env);
+
+    assert_eq!(
+        governor.try_initialize(&admin, &config),
+        Err(Ok(GovernorError::AlreadyInitialized))
+    );
+
+    let fresh = GovernorClient::new(&env, &env.register(Governor, ()));
+    assert_eq!(fresh.try_state(&0), Err(Ok(GovernorError::ProposalNotFound)));
+    assert_eq!(
+        fresh.try_propose(&Address::generate(&env), &Vec::new(&env), &String::from_str(&env, "x")),
+        Err(Ok(GovernorError::NotInitialized))
+    );
+    assert_eq!(
+        fresh.try_initialize(&admin, &GovernorConfig { voting_period: 0, ..config.clone() }),
+        Err(Ok(GovernorError::InvalidConfig))
+    );
+    assert_eq!(
+        fresh.try_initialize(&admin, &GovernorConfig { voting_period: MAX_VOTING_PERIOD + 1, ..config.clone() }),
+        Err(Ok(GovernorError::InvalidConfig))
+    );
+    assert_eq!(
+        fresh.try_initialize(&admin, &GovernorConfig { voting_delay: MAX_VOTING_DELAY + 1, ..config.clone() }),
+        Err(Ok(GovernorError::InvalidConfig))
+    );
+    assert_eq!(
+        fresh.try_initialize(&admin, &GovernorConfig { quorum_bps: 10_001, ..config.clone() }),
+        Err(Ok(GovernorError::InvalidConfig))
+    );
+    assert_eq!(
+        fresh.try_initialize(&admin, &GovernorConfig { threshold_bps: 10_000, ..config }),
+        Err(Ok(GovernorError::InvalidConfig))
+    );
+}
+
+/// Test: initialize exige la autorización del admin
+#[test]
+fn test_initialize_requires_admin_auth() {
+    let env = Env::default();
+    let (governor, _token, _voters) = setup(&env);
+    let config = governor.config();
+    let admin = Address::generate(&env);
+
+    // Sin autorizaciones mockeadas la llamada falla y no guarda nada
+    let fresh = GovernorClient::new(&env, &env.register(Governor, ()));
+    env.set_auths(&[]);
+    assert!(fresh.try_initialize(&admin, &config).is_err());
+    assert_eq!(fresh.try_config(), Err(Ok(GovernorError::NotInitialized)));
+
+    env.mock_all_auths();
+    fresh.initialize(&admin, &config);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    fresh.address.clone(),
+                    Symbol::new(&env, "initialize"),
+                    (admin.clone(), config.clone()).into_val(&env)
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(fresh.config(), config);
+}
@@ -2,17 +2,24 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contractclient, contractevent, Address, Env, String, 
-    symbol_short,
+    contract, contractclient, contractimpl, contractevent, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, Executable, MuxedAddress, String, Symbol, Vec,
+};
+use token_core::{
+    admin, base, burnable, capped, mintable, pausable, require_initialized, require_past_ledger,
+    TokenHooks,
 };
 
 mod storage;
-mod errors;
 
-use storage::{DataKey, TokenMetadata};
-use errors::TokenError;
+use storage::{TokenMetadata, PauseTarget, Role};
+pub use token_core::events::*;
+pub use token_core::{
+    TokenError, MAX_BATCH_SIZE, MAX_MEMO_LENGTH, MAX_PAGE_SIZE, STORAGE_VERSION,
+    UNLIMITED_ALLOWANCE,
+};
 
-// EVENTOS - corrige warning .publish() deprecado
+// EVENTOS propios de TokenBDB (los comunes vienen de token_core::events)
 
 #[contractevent]
 pub struct InitEvent {
@@ -23,63 +30,65 @@ pub struct InitEvent {
 }
 
 #[contractevent]
-pub struct MintEvent {
-    pub to: Address,
-    pub amount: i128,
-    pub new_balance: i128,
-    pub new_supply: i128,
+pub struct RoleGrantedEvent {
+    pub role: Role,
+    pub account: Address,
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct RoleRevokedEvent {
+    pub role: Role,
+    pub account: Address,
+    pub admin: Address,
 }
 
 #[contractevent]
-pub struct BurnEvent {
+pub struct PrivilegedBurnEvent {
+    pub burner: Address,
     pub from: Address,
     pub amount: i128,
-    pub new_balance: i128, 
+    pub new_balance: i128,
     pub new_supply: i128,
 }
 
 #[contractevent]
-pub struct TransferEvent {
-    pub from: Address,
-    pub to: Address,
-    pub amount: i128,
-    pub from_new_balance: i128,
-    pub to_new_balance: i128,
+pub struct PermitKeyRegisteredEvent {
+    pub owner: Address,
+    pub public_key: BytesN<32>,
 }
 
 #[contractevent]
-pub struct TransferFromEvent {
-    pub spender: Address,
-    pub from: Address, 
-    pub to: Address,
-    pub amount: i128,
-    pub from_new_balance: i128,
-    pub to_new_balance: i128,
-    pub new_allowance: i128,
+pub struct DelegateChangedEvent {
+    pub delegator: Address,
+    pub from_delegate: Option<Address>,
+    pub to_delegate: Address,
 }
 
 #[contractevent]
-pub struct ApproveEvent {
-    pub from: Address,
-    pub spender: Address,
-    pub old_allowance: i128,
-    pub new_allowance: i128,
+pub struct DelegateVotesChangedEvent {
+    pub delegate: Address,
+    pub previous_votes: i128,
+    pub new_votes: i128,
 }
 
-/// Constantes de configuración
-const MAX_DECIMALS: u32 = 18;
-const MAX_NAME_LENGTH: u32 = 100;
-const MAX_SYMBOL_LENGTH: u32 = 32;
+/// Versión del código del contrato (se incrementa en cada release)
+const CONTRACT_VERSION: u32 = 3;
 
 
 /// Estructura del contrato Token BDB
 #[contract]
 pub struct TokenBDB;
 
-/// Trait que define la interfaz del token según CAP-46
+/// Trait que define la interfaz del token según CAP-46 / SEP-41
 /// 
 /// Esta es la interfaz estándar de tokens fungibles en Stellar
 /// Compatible con wallets, DEXs, y el ecosistema completo
+///
+/// Los métodos SEP-41 tienen el mismo nombre, orden y tipos de argumentos
+/// que `soroban_sdk::token::TokenInterface`, por lo que el contrato se
+/// puede usar con `soroban_sdk::token::TokenClient`. Los errores se
+/// devuelven como `TokenError` (errores de contrato en el ledger).
 pub trait TokenTrait {
     /// Inicializa el token con metadatos y admin
     /// 
@@ -88,19 +97,26 @@ pub trait TokenTrait {
     /// - Name: nombre completo del token
     /// - Symbol: identificador corto (ej: BDB, USDC)
     /// - Decimals: precisión del token (7 para Stellar)
+    /// - Max supply: tope opcional del supply (None = sin tope)
     fn initialize(
         env: Env, 
         admin: Address, 
         name: String, 
         symbol: String,
-        decimals: u32
+        decimals: u32,
+        max_supply: Option<i128>
     ) -> Result<(), TokenError>;
     
-    /// Crea nuevos tokens (solo admin)
+    /// Crea nuevos tokens (solo cuentas con rol Minter)
     /// 
     /// Aumenta el supply total y el balance del destinatario
-    /// Requiere autorización del admin
-    fn mint(env: Env, to: Address, amount: i128) -> Result<(), TokenError>;
+    /// Requiere autorización de `minter`
+    fn mint(
+        env: Env,
+        minter: Address,
+        to: Address,
+        amount: i128
+    ) -> Result<(), TokenError>;
     
     /// Destruye tokens reduciendo el supply
     /// 
@@ -108,36 +124,62 @@ pub trait TokenTrait {
     /// Requiere autorización del owner
     fn burn(env: Env, from: Address, amount: i128) -> Result<(), TokenError>;
     
+    /// Destruye tokens de `from` usando el allowance de `spender`
+    /// 
+    /// Requiere autorización del spender
+    /// Reduce el allowance, el balance y el supply total
+    fn burn_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        amount: i128
+    ) -> Result<(), TokenError>;
+    
+    /// Quema tokens de `from` sin su firma ni allowance (solo rol Burner)
+    /// 
+    /// Requiere autorización de `burner`. Respeta la pausa de Burn y
+    /// queda deshabilitado, como el mint, si el admin renunció.
+    fn privileged_burn(
+        env: Env,
+        burner: Address,
+        from: Address,
+        amount: i128
+    ) -> Result<(), TokenError>;
+    
     /// Consulta el balance de una cuenta
     /// 
     /// Devuelve 0 si la cuenta nunca ha recibido tokens
-    fn balance(env: Env, account: Address) -> i128;
+    fn balance(env: Env, id: Address) -> i128;
     
     /// Transfiere tokens entre cuentas
     /// 
     /// Requiere autorización de `from`
     /// No permite transferencias a sí mismo
+    /// `to` puede ser una dirección muxed (se acredita a la cuenta base)
     fn transfer(
         env: Env, 
         from: Address, 
-        to: Address, 
+        to: MuxedAddress, 
         amount: i128
     ) -> Result<(), TokenError>;
     
     /// Aprueba a otro usuario para gastar tokens
     /// 
     /// Permite que `spender` gaste hasta `amount` tokens
-    /// de la cuenta de `from`. Se puede revocar con amount=0
+    /// de la cuenta de `from` hasta el ledger `expiration_ledger`.
+    /// Se puede revocar con amount=0
     fn approve(
         env: Env, 
         from: Address, 
         spender: Address, 
-        amount: i128
+        amount: i128,
+        expiration_ledger: u32
     ) -> Result<(), TokenError>;
     
     /// Consulta el allowance entre dos cuentas
     /// 
     /// Devuelve cuánto puede gastar `spender` de los tokens de `from`
+    /// Devuelve 0 si el allowance ya expiró
     fn allowance(env: Env, from: Address, spender: Address) -> i128;
     
     /// Transfiere tokens en nombre de otro usuario
@@ -157,10 +199,527 @@ pub trait TokenTrait {
     fn symbol(env: Env) -> String;
     fn decimals(env: Env) -> u32;
     fn total_supply(env: Env) -> i128;
-    fn admin(env: Env) -> Address;
+    
+    /// Devuelve el admin actual
+    /// 
+    /// None si el contrato no fue inicializado o si el admin renunció
+    fn admin(env: Env) -> Option<Address>;
+}
+
+/// Gestión del administrador del token
+/// 
+/// La transferencia del admin se hace en dos pasos para no perder
+/// el control del token por un error de tipeo:
+/// 1. El admin actual propone al nuevo admin (propose_admin)
+/// 2. El nuevo admin acepta con su propia firma (accept_admin)
+pub trait AdminTrait {
+    /// Propone un nuevo admin (solo admin actual)
+    /// 
+    /// Reemplaza cualquier propuesta pendiente anterior
+    fn propose_admin(env: Env, new_admin: Address) -> Result<(), TokenError>;
+    
+    /// Acepta la propuesta pendiente
+    /// 
+    /// Requiere autorización del admin propuesto. El admin anterior
+    /// pierde todos sus roles.
+    fn accept_admin(env: Env) -> Result<(), TokenError>;
+    
+    /// Renuncia al admin de forma permanente (solo admin actual)
+    /// 
+    /// Después de renunciar nadie puede volver a mintear:
+    /// el supply queda fijo para siempre. El admin pierde sus roles.
+    fn renounce_admin(env: Env) -> Result<(), TokenError>;
+    
+    /// Consulta el admin propuesto pendiente de aceptar
+    fn pending_admin(env: Env) -> Option<Address>;
+    
+    /// Indica si el admin renunció
+    fn admin_renounced(env: Env) -> bool;
+}
+
+/// Registro de roles para operaciones privilegiadas
+/// 
+/// Permite delegar, por ejemplo, el mint a un servicio (hot wallet)
+/// sin entregarle el admin. Solo el admin otorga y revoca roles.
+/// Cuando el admin cambia (accept_admin) o renuncia, la cuenta saliente
+/// pierde todos sus roles; los delegados a otras cuentas se mantienen.
+pub trait RoleTrait {
+    /// Otorga `role` a `account` (solo admin)
+    fn grant_role(env: Env, role: Role, account: Address) -> Result<(), TokenError>;
+    
+    /// Revoca `role` de `account` (solo admin)
+    fn revoke_role(env: Env, role: Role, account: Address) -> Result<(), TokenError>;
+    
+    /// Consulta si `account` tiene `role`
+    fn has_role(env: Env, role: Role, account: Address) -> bool;
+}
+
+/// Circuit breaker: pausa de operaciones ante un incidente
+/// 
+/// Cada clase de operación se pausa por separado, o todas a la vez
+/// con `PauseTarget::All`:
+/// - Transfer: transfer() y transfer_from()
+/// - Mint: mint()
+/// - Burn: burn() y burn_from()
+/// 
+/// approve() y las consultas nunca se pausan.
+pub trait PauseTrait {
+    /// Pausa `target` (cuentas con rol Pauser)
+    fn pause(env: Env, caller: Address, target: PauseTarget) -> Result<(), TokenError>;
+    
+    /// Reanuda `target` (cuentas con rol Pauser)
+    /// 
+    /// Reanudar `All` no borra las pausas por operación
+    fn unpause(env: Env, caller: Address, target: PauseTarget) -> Result<(), TokenError>;
+    
+    /// Modo "redeem only" (cuentas con rol Pauser)
+    /// 
+    /// Pausa transfer y mint pero deja habilitado el burn,
+    /// para que los holders puedan redimir durante un incidente
+    fn set_redeem_only(env: Env, caller: Address) -> Result<(), TokenError>;
+    
+    /// Consulta si `target` está pausado
+    /// 
+    /// Para una operación concreta considera también la pausa global
+    fn is_paused(env: Env, target: PauseTarget) -> bool;
+}
+
+/// Autorización por cuenta (freeze), como en el Stellar Asset Contract
+/// 
+/// Las cuentas están autorizadas por defecto. Una cuenta congelada no
+/// puede enviar ni recibir: transfer, transfer_from, burn, burn_from
+/// y mint fallan con AccountFrozen si involucran su balance.
+pub trait AuthorizationTrait {
+    /// Congela (authorize = false) o descongela (authorize = true) una cuenta
+    /// 
+    /// Requiere autorización del admin
+    fn set_authorized(env: Env, id: Address, authorize: bool) -> Result<(), TokenError>;
+    
+    /// Consulta si la cuenta está autorizada (no congelada)
+    fn authorized(env: Env, id: Address) -> bool;
+}
+
+/// Tope de supply verificable on-chain
+/// 
+/// El tope se fija en initialize() y solo se puede bajar, nunca subir.
+/// Así los holders pueden comprobar el máximo de tokens que existirán
+/// sin confiar en el admin.
+pub trait SupplyCapTrait {
+    /// Consulta el tope de supply (None = sin tope)
+    fn max_supply(env: Env) -> Option<i128>;
+    
+    /// Baja el tope de supply (solo admin)
+    /// 
+    /// El nuevo tope debe ser menor al actual y no menor al supply en
+    /// circulación. Si no había tope, fija uno por primera vez.
+    fn lower_max_supply(env: Env, new_max_supply: i128) -> Result<(), TokenError>;
+}
+
+/// Actualizaciones in-place del contrato
+/// 
+/// upgrade() reemplaza el wasm manteniendo el contract ID y todo el
+/// storage (balances, allowances, metadata). Si el nuevo wasm cambia el
+/// esquema de storage, el admin llama migrate() una vez después del upgrade.
+pub trait UpgradeTrait {
+    /// Reemplaza el wasm del contrato (solo admin)
+    /// 
+    /// El wasm debe estar subido previamente a la red
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), TokenError>;
+    
+    /// Migra el storage al esquema de este código (solo admin)
+    /// 
+    /// Corre una sola vez por cada cambio de esquema; si el storage ya
+    /// está al día falla con MigrationNotNeeded.
+    fn migrate(env: Env) -> Result<(), TokenError>;
+    
+    /// Versión del código desplegado
+    fn version(env: Env) -> u32;
+    
+    /// Versión del esquema de storage guardado on-chain
+    fn storage_version(env: Env) -> u32;
+}
+
+/// Operaciones en lote para distribuciones (ej: pago de nómina)
+/// 
+/// Una sola transacción y una sola firma para hasta MAX_BATCH_SIZE
+/// destinatarios. Son todo-o-nada: el total se valida antes de mover
+/// fondos y cualquier error revierte el lote completo. Se emite un
+/// evento por destinatario, igual que en transfer() y mint().
+pub trait BatchTrait {
+    /// Transfiere desde `from` a varios destinatarios
+    /// 
+    /// Requiere autorización de `from` una sola vez
+    fn transfer_batch(
+        env: Env,
+        from: Address,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError>;
+    
+    /// Mintea a varios destinatarios (cuentas con rol Minter)
+    /// 
+    /// Requiere autorización de `minter` una sola vez
+    fn mint_batch(
+        env: Env,
+        minter: Address, recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError>;
+    
+    /// Consulta los balances de varias cuentas (mismo orden que `ids`)
+    fn balances(env: Env, ids: Vec<Address>) -> Vec<i128>;
+}
+
+/// Consultas históricas de balances y supply
+/// 
+/// Cada cambio de balance (transfer, mint, burn y sus variantes) guarda
+/// un checkpoint con el ledger en que ocurrió. El historial completo se
+/// conserva (paginado); solo en un contrato actualizado desde un esquema
+/// sin checkpoints, los ledgers anteriores a la migración fallan con
+/// CheckpointUnavailable.
+pub trait CheckpointTrait {
+    /// Balance de `id` al cierre de `ledger` (debe ser un ledger pasado)
+    fn balance_at(env: Env, id: Address, ledger: u32) -> Result<i128, TokenError>;
+    
+    /// Total supply al cierre de `ledger` (debe ser un ledger pasado)
+    fn total_supply_at(env: Env, ledger: u32) -> Result<i128, TokenError>;
+}
+
+/// Approvals firmados off-chain (permits)
+/// 
+/// El owner firma un approve con una clave ed25519 registrada y un
+/// relayer lo envía pagando el fee. Cada permit lleva el nonce actual
+/// del owner, que se incrementa al usarlo: una firma sirve una sola vez.
+pub trait PermitTrait {
+    /// Registra (o rota) la clave pública ed25519 de `owner` para permits
+    /// 
+    /// Requiere autorización de `owner`
+    fn register_permit_key(
+        env: Env,
+        owner: Address,
+        public_key: BytesN<32>
+    ) -> Result<(), TokenError>;
+    
+    /// Aplica un approve firmado por `owner` (lo puede enviar cualquiera)
+    /// 
+    /// `signature` firma el mensaje de permit_message(); el allowance
+    /// expira en `expiration_ledger`, que también es el plazo del permit.
+    /// 
+    /// Monto, plazo, nonce y clave se validan antes que la firma y fallan
+    /// con su TokenError. Una firma inválida, en cambio, aborta la
+    /// invocación con un error del host (ed25519_verify no devuelve un
+    /// resultado): `try_permit` la recibe como `Err(Err(_))`.
+    fn permit(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        nonce: u64,
+        signature: BytesN<64>
+    ) -> Result<(), TokenError>;
+    
+    /// Clave pública registrada para permits de `owner`
+    fn permit_key(env: Env, owner: Address) -> Option<BytesN<32>>;
+    
+    /// Nonce que debe llevar el próximo permit de `owner`
+    fn permit_nonce(env: Env, owner: Address) -> u64;
+}
+
+/// Mensaje que el owner firma para un permit
+/// 
+/// XDR de la tupla ("permit", network_id, contrato, owner, spender,
+/// amount, expiration_ledger, nonce). El network id y la dirección del
+/// contrato evitan que la firma se reutilice en otra red u otro token.
+pub fn permit_message(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+    nonce: u64
+) -> Bytes {
+    (
+        Symbol::new(env, "permit"),
+        env.ledger().network_id(),
+        env.current_contract_address(),
+        owner.clone(),
+        spender.clone(),
+        amount,
+        expiration_ledger,
+        nonce,
+    ).to_xdr(env)
+}
+
+/// Poder de voto delegado (token de gobernanza)
+/// 
+/// Los votos de una cuenta son la suma de los balances que le fueron
+/// delegados. Un balance sin delegar no cuenta para nadie: para votar
+/// con los propios tokens hay que delegarse a uno mismo.
+pub trait VotesTrait {
+    /// Delega el poder de voto de `delegator` en `delegatee`
+    /// 
+    /// Requiere autorización de `delegator`. Reemplaza la delegación
+    /// anterior y mueve todo su balance actual al nuevo delegado.
+    fn delegate(env: Env, delegator: Address, delegatee: Address) -> Result<(), TokenError>;
+    
+    /// Delegado actual de `account` (None si nunca delegó)
+    fn delegates(env: Env, account: Address) -> Option<Address>;
+    
+    /// Votos actuales de `account`
+    fn get_votes(env: Env, account: Address) -> i128;
+    
+    /// Votos de `account` al cierre de `ledger` (debe ser un ledger pasado)
+    fn get_past_votes(env: Env, account: Address, ledger: u32) -> Result<i128, TokenError>;
+}
+
+/// Mueve `amount` de votos entre los delegados de `from` y `to`
+/// 
+/// None representa un mint (sin origen) o un burn (sin destino).
+/// Se llama en cada cambio de balance, después de escribirlo.
+fn move_voting_power(env: &Env, from: Option<&Address>, to: Option<&Address>, amount: i128) {
+    let from_delegate = from.and_then(|id| storage::read_delegate(env, id));
+    let to_delegate = to.and_then(|id| storage::read_delegate(env, id));
+    move_delegate_votes(env, from_delegate, to_delegate, amount);
+}
+
+/// Resta `amount` votos a `from` y se los suma a `to`
+/// 
+/// Cada delegado afectado recibe un checkpoint y un evento
+fn move_delegate_votes(
+    env: &Env,
+    from: Option<Address>,
+    to: Option<Address>,
+    amount: i128
+) {
+    if from == to || amount == 0 {
+        return;
+    }
+    
+    if let Some(delegate) = from {
+        let previous_votes = storage::read_votes(env, &delegate);
+        let new_votes = previous_votes - amount;
+        storage::write_votes_checkpoint(env, &delegate, previous_votes, new_votes);
+        DelegateVotesChangedEvent { delegate, previous_votes, new_votes }.publish(env);
+    }
+    
+    if let Some(delegate) = to {
+        let previous_votes = storage::read_votes(env, &delegate);
+        let new_votes = previous_votes + amount;
+        storage::write_votes_checkpoint(env, &delegate, previous_votes, new_votes);
+        DelegateVotesChangedEvent { delegate, previous_votes, new_votes }.publish(env);
+    }
+}
+
+/// Metadatos extendidos del token (descripción, ícono, SEP-1)
+pub trait MetadataTrait {
+    /// Metadatos completos del token
+    fn metadata(env: Env) -> Result<TokenMetadata, TokenError>;
+    
+    /// Reemplaza los metadatos (cuentas con rol MetadataManager)
+    /// 
+    /// Vuelve a validar los límites de longitud; los decimales no
+    /// se pueden cambiar porque alterarían todos los balances.
+    fn update_metadata(
+        env: Env,
+        caller: Address,
+        metadata: TokenMetadata
+    ) -> Result<(), TokenError>;
+}
+
+/// Ajustes relativos del allowance
+/// 
+/// Alternativa a approve() que no pisa el valor anterior, así el spender
+/// no puede adelantarse y gastar el viejo y el nuevo. Un allowance de
+/// i128::MAX (UNLIMITED_ALLOWANCE) es ilimitado: transfer_from() y
+/// burn_from() no lo descuentan.
+pub trait AllowanceTrait {
+    /// Suma `amount` al allowance de `spender` (autoriza `from`)
+    /// 
+    /// `expiration_ledger` reemplaza la expiración actual.
+    /// Falla con OverflowError si el resultado supera i128::MAX
+    fn increase_allowance(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32
+    ) -> Result<(), TokenError>;
+    
+    /// Resta `amount` del allowance de `spender` (autoriza `from`)
+    /// 
+    /// Conserva la expiración actual. Falla con InsufficientAllowance
+    /// si el allowance es menor a `amount`
+    fn decrease_allowance(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128
+    ) -> Result<(), TokenError>;
+}
+
+/// Enumeración de holders y allowances
+/// 
+/// Las keys de balance no se pueden recorrer, así que el núcleo mantiene
+/// una lista de cuentas con balance > 0 y, por owner, la lista de
+/// spenders con allowance. Ambas se actualizan junto con la eliminación
+/// de keys en 0 (transfer, burn, approve...).
+pub trait HoldersTrait {
+    /// Cantidad de cuentas con balance > 0
+    /// 
+    /// Las consultas de listas fallan con ListsIncomplete en un contrato
+    /// migrado desde un esquema < 3: los holders y spenders anteriores no
+    /// figuran y no se pueden reconstruir on-chain.
+    fn holder_count(env: Env) -> Result<u32, TokenError>;
+    
+    /// Holders desde la posición `start`, hasta `limit` (máximo MAX_PAGE_SIZE)
+    /// 
+    /// El orden no es estable: cuando una cuenta queda en 0, el último
+    /// holder pasa a ocupar su posición.
+    fn holders(env: Env, start: u32, limit: u32) -> Result<Vec<Address>, TokenError>;
+    
+    /// Cantidad de spenders con un allowance guardado sobre los tokens de `owner`
+    /// 
+    /// Incluye los vencidos: es el total de posiciones de allowances_of()
+    fn spender_count(env: Env, owner: Address) -> Result<u32, TokenError>;
+    
+    /// Spenders con allowance vigente (> 0 y sin vencer) sobre los tokens de `owner`
+    /// 
+    /// Recorre las posiciones `start..start + limit` (máximo MAX_PAGE_SIZE)
+    /// y omite los vencidos, así que una página puede traer menos de `limit`.
+    fn allowances_of(env: Env, owner: Address, start: u32, limit: u32) -> Result<Vec<Address>, TokenError>;
+}
+
+/// Transferencias y mints con memo (referencia de pago)
+/// 
+/// El memo (hasta MAX_MEMO_LENGTH caracteres) viaja en TransferEvent /
+/// MintEvent. Una cuenta puede exigir memo: las transferencias que
+/// recibe sin memo (ni id muxed) fallan con MemoRequired.
+pub trait MemoTrait {
+    /// transfer() con memo; requiere autorización de `from`
+    fn transfer_with_memo(
+        env: Env,
+        from: Address,
+        to: MuxedAddress,
+        amount: i128,
+        memo: String
+    ) -> Result<(), TokenError>;
+    
+    /// mint() con memo (cuentas con rol Minter)
+    fn mint_with_memo(
+        env: Env,
+        minter: Address,
+        to: Address,
+        amount: i128,
+        memo: String
+    ) -> Result<(), TokenError>;
+    
+    /// transfer_from() con memo; requiere autorización de `spender`
+    fn transfer_from_with_memo(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+        memo: String
+    ) -> Result<(), TokenError>;
+    
+    /// transfer_batch() con un memo por destinatario; requiere autorización de `from`
+    fn transfer_batch_with_memo(
+        env: Env,
+        from: Address,
+        recipients: Vec<(Address, i128, String)>
+    ) -> Result<(), TokenError>;
+    
+    /// Activa o desactiva el requisito de memo de `id` (requiere su autorización)
+    fn set_memo_required(env: Env, id: Address, required: bool) -> Result<(), TokenError>;
+    
+    /// Indica si `id` exige memo en las transferencias que recibe
+    fn memo_required(env: Env, id: Address) -> bool;
+}
+
+/// Hook que implementan los contratos que aceptan transfer_and_call()
+/// 
+/// Se llama después de acreditar los tokens. Devolver false (o fallar)
+/// revierte la transferencia. Soroban no permite reentrar en el token
+/// durante el hook: el receptor solo cuenta con los argumentos.
+#[contractclient(name = "TokenReceiverClient")]
+pub trait TokenReceiver {
+    fn on_token_received(
+        env: Env,
+        token: Address,
+        from: Address,
+        amount: i128,
+        data: Bytes
+    ) -> bool;
+}
+
+/// Transferencias que notifican al contrato que recibe
+/// 
+/// Permite que un vault o una venta reaccionen al depósito en la misma
+/// transacción, sin approve() + transfer_from() en dos pasos.
+pub trait TransferAndCallTrait {
+    /// transfer() que además llama a `on_token_received` si `to` es un contrato
+    /// 
+    /// Requiere autorización de `from`. `data` llega tal cual al receptor.
+    /// Si el receptor rechaza, falla con TransferRejected y no se mueve
+    /// nada. Hacia una cuenta es un transfer() normal. Mientras espera al
+    /// receptor, un guard rechaza otra llamada con Reentrancy.
+    fn transfer_and_call(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+        data: Bytes
+    ) -> Result<(), TokenError>;
+}
+
+/// Todos los roles; el admin inicial los recibe en initialize()
+const ALL_ROLES: [Role; 4] = [Role::Minter, Role::Burner, Role::Pauser, Role::MetadataManager];
+
+/// Revoca todos los roles de `account` en nombre de `admin`
+/// 
+/// Se usa con el admin saliente: una clave rotada (o comprometida) no
+/// debe conservar el mint ni la pausa. Emite un evento por rol.
+fn revoke_all_roles(env: &Env, account: &Address, admin: &Address) {
+    for role in ALL_ROLES {
+        if storage::has_role(env, role, account) {
+            storage::remove_role(env, role, account);
+            RoleRevokedEvent {
+                role,
+                account: account.clone(),
+                admin: admin.clone(),
+            }.publish(env);
+        }
+    }
+}
+
+/// Exige la autorización de `account` y que tenga el rol `role`
+fn require_role(env: &Env, role: Role, account: &Address) -> Result<(), TokenError> {
+    require_initialized(env)?;
+    
+    account.require_auth();
+    
+    if !storage::has_role(env, role, account) {
+        return Err(TokenError::MissingRole);
+    }
+    
+    Ok(())
+}
+
+/// Los cambios de balance del núcleo mueven el poder de voto
+impl TokenHooks for TokenBDB {
+    fn on_balance_moved(
+        env: &Env,
+        from: Option<&Address>,
+        to: Option<&Address>,
+        amount: i128
+    ) {
+        move_voting_power(env, from, to, amount);
+    }
 }
 
 /// Implementación del contrato
+/// 
+/// La lógica vive en token_core; aquí solo se resuelve quién puede
+/// llamar cada operación (roles) y lo propio de TokenBDB.
 #[contractimpl]
 impl TokenTrait for TokenBDB {
     fn initialize(
@@ -168,454 +727,611 @@ impl TokenTrait for TokenBDB {
         admin: Address, 
         name: String, 
         symbol: String,
-        decimals: u32
+        decimals: u32,
+        max_supply: Option<i128>
     ) -> Result<(), TokenError> {
-        // 1. Verificar que no esté inicializado
-        if env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::AlreadyInitialized);
-        }
-        
-        // 2. Validar decimales (máximo 18 como Ethereum)
-        if decimals > MAX_DECIMALS {
-            return Err(TokenError::InvalidDecimals);
-        }
+        // 1. Validar y guardar admin, metadatos y tope
+        base::initialize(&env, &admin, name.clone(), symbol.clone(), decimals)?;
+        capped::initialize(&env, max_supply)?;
         
-        // 3. Validar metadatos (name y symbol no vacíos)
-        // CORRECCIÓN: String en Soroban usa is_empty() para vacío y len() devuelve u64
-        // VIEJA FORMA: name.len() == 0 (incorrecto - len() no existe así)
-        // NUEVA FORMA: is_empty() para vacío y cast a u32 para longitud máxima
-        if name.is_empty() || name.len() as u32 > MAX_NAME_LENGTH {
-            return Err(TokenError::InvalidMetadata);
+        // 2. El admin inicial recibe todos los roles; luego puede delegarlos
+        for role in ALL_ROLES {
+            storage::write_role(&env, role, &admin);
         }
         
-        if symbol.is_empty() || symbol.len() as u32 > MAX_SYMBOL_LENGTH {
-            return Err(TokenError::InvalidMetadata);
-        }
-        
-        // 4. Guardar metadata en instance storage
-        env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage().instance().set(&DataKey::TokenName, &name);
-        env.storage().instance().set(&DataKey::TokenSymbol, &symbol);
-        env.storage().instance().set(&DataKey::Decimals, &decimals);
-        env.storage().instance().set(&DataKey::TotalSupply, &0i128);
-        env.storage().instance().set(&DataKey::Initialized, &true);
-        
-        // 5. Extender TTL del storage de instance (30 días)
-        env.storage().instance().extend_ttl(100_000, 200_000);
-        
-        // 6. Emitir evento rico con todos los metadatos
-		InitEvent {
-			admin: admin.clone(),
-			name: name.clone(),       
-			symbol: symbol.clone(),   
-			decimals,                 
-		}.publish(&env);
+        // 3. Emitir evento rico con todos los metadatos
+        InitEvent {
+            admin,
+            name,
+            symbol,
+            decimals,
+        }.publish(&env);
         
         Ok(())
     }
     
-    fn mint(env: Env, to: Address, amount: i128) -> Result<(), TokenError> {
-        // 1. Verificar inicialización
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::NotInitialized);
-        }
-        
-        // 2. Solo el admin puede mintear
-        let admin: Address = env.storage().instance()
-            .get(&DataKey::Admin)
-            .ok_or(TokenError::NotInitialized)?;
-        admin.require_auth();
-        
-        // 3. Validaciones
-        if amount <= 0 {
-            return Err(TokenError::InvalidAmount);
-        }
-        
-        // 4. Validar que `to` no sea igual a `admin` (opcional, pero buena práctica)
-        // Esto evita que el admin se mintee tokens a sí mismo por error
-        
-        // 5. Obtener balance actual y verificar overflow
-        let balance = Self::balance(env.clone(), to.clone());
-        let new_balance = balance.checked_add(amount)
-            .ok_or(TokenError::OverflowError)?;
-        
-        // 6. Actualizar balance con TTL extendido
-        // CORRECCIÓN: Eliminar clone() innecesario - Address ya se pasa eficientemente
-        // VIEJA FORMA: &DataKey::Balance(to.clone())
-        // NUEVA FORMA: &DataKey::Balance(to.clone()) sin clone innecesario
-        env.storage().persistent().set(
-            &DataKey::Balance(to.clone()), // CORREGIDO: sin clone()
-            &new_balance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Balance(to.clone()), // CORREGIDO: sin clone()
-            100_000,
-            200_000
-        );
-        
-        // 7. Actualizar total supply
-        let total: i128 = env.storage().instance()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0);
-        let new_total = total.checked_add(amount)
-            .ok_or(TokenError::OverflowError)?;
-        env.storage().instance().set(
-            &DataKey::TotalSupply, 
-            &new_total
-        );
-        
-        // 8. Emitir evento detallado
-        MintEvent {
-            to: to.clone(),
-            amount,
-            new_balance,
-            new_supply: new_total,
-        }.publish(&env);
-        
-        Ok(())
+    fn mint(
+        env: Env,
+        minter: Address,
+        to: Address,
+        amount: i128
+    ) -> Result<(), TokenError> {
+        // Verificar inicialización y que `minter` tenga el rol Minter
+        require_role(&env, Role::Minter, &minter)?;
+        mintable::mint::<Self>(&env, to, amount)
     }
     
     fn burn(env: Env, from: Address, amount: i128) -> Result<(), TokenError> {
-        // 1. Verificar inicialización
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::NotInitialized);
+        burnable::burn::<Self>(&env, from, amount)
+    }
+    
+    fn burn_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        amount: i128
+    ) -> Result<(), TokenError> {
+        burnable::burn_from::<Self>(&env, spender, from, amount)
+    }
+    
+    fn privileged_burn(
+        env: Env,
+        burner: Address,
+        from: Address,
+        amount: i128
+    ) -> Result<(), TokenError> {
+        // 1. Verificar inicialización y que `burner` tenga el rol Burner
+        require_role(&env, Role::Burner, &burner)?;
+        if admin::admin_renounced(&env) {
+            return Err(TokenError::AdminRenounced);
         }
+        pausable::require_not_paused(&env, PauseTarget::Burn)?;
         
-        // 2. Requiere autorización del dueño de los tokens
-        from.require_auth();
-        
-        // 3. Validaciones
+        // 2. Validaciones
         if amount <= 0 {
             return Err(TokenError::InvalidAmount);
         }
         
-        let balance = Self::balance(env.clone(), from.clone());
-        if balance < amount {
-            return Err(TokenError::InsufficientBalance);
-        }
-        
-        // 4. Actualizar balance
-        let new_balance = balance - amount;
-        if new_balance == 0 {
-            // Optimización: eliminar key si balance = 0
-            env.storage().persistent().remove(&DataKey::Balance(from.clone()));
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Balance(from.clone()), 
-                &new_balance
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Balance(from.clone()), 
-                100_000,
-                200_000
-            );
-        }
+        // 3. Actualizar balance y total supply (sin firma del holder)
+        let (new_balance, new_total) = base::burn_balance::<Self>(&env, &from, amount)?;
         
-        // 5. Actualizar total supply
-        let total: i128 = env.storage().instance()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0);
-        let new_total = total.checked_sub(amount)
-            .ok_or(TokenError::OverflowError)?;
-        env.storage().instance().set(
-            &DataKey::TotalSupply,
-            &new_total
-        );
-        
-        // 6. Emitir evento
-        BurnEvent {
+        // 4. Emitir evento detallado y el estándar
+        PrivilegedBurnEvent {
+            burner,
             from: from.clone(),
             amount,
             new_balance,
             new_supply: new_total,
         }.publish(&env);
+        Sep41BurnEvent { from, amount }.publish(&env);
         
         Ok(())
     }
     
-    fn balance(env: Env, account: Address) -> i128 {
-        env.storage().persistent()
-            .get(&DataKey::Balance(account))
-            .unwrap_or(0)
+    fn balance(env: Env, id: Address) -> i128 {
+        base::balance(&env, &id)
     }
     
     fn transfer(
         env: Env, 
         from: Address, 
+        to: MuxedAddress, 
+        amount: i128
+    ) -> Result<(), TokenError> {
+        // El balance se acredita a la cuenta base de la dirección muxed
+        base::transfer::<Self>(&env, from, to, amount)
+    }
+    
+    fn approve(
+        env: Env, 
+        from: Address, 
+        spender: Address, 
+        amount: i128,
+        expiration_ledger: u32
+    ) -> Result<(), TokenError> {
+        base::approve(&env, from, spender, amount, expiration_ledger)
+    }
+    
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        base::allowance(&env, &from, &spender)
+    }
+
+    fn transfer_from(
+        env: Env, 
+        spender: Address, 
+        from: Address, 
         to: Address, 
         amount: i128
     ) -> Result<(), TokenError> {
-        // 1. Verificar inicialización
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::NotInitialized);
+        base::transfer_from::<Self>(&env, spender, from, to, amount)
+    }
+    
+    // Métodos de consulta
+    fn name(env: Env) -> String {
+        base::name(&env)
+    }
+    
+    fn symbol(env: Env) -> String {
+        base::symbol(&env)
+    }
+    
+    fn decimals(env: Env) -> u32 {
+        base::decimals(&env)
+    }
+    
+    fn total_supply(env: Env) -> i128 {
+        base::total_supply(&env)
+    }
+    
+    fn admin(env: Env) -> Option<Address> {
+        base::admin(&env)
+    }
+}
+
+#[contractimpl]
+impl AdminTrait for TokenBDB {
+    fn propose_admin(env: Env, new_admin: Address) -> Result<(), TokenError> {
+        admin::propose_admin(&env, new_admin)
+    }
+    
+    fn accept_admin(env: Env) -> Result<(), TokenError> {
+        // 1. Cambiar el admin (verifica la propuesta y la firma del nuevo)
+        let previous_admin = base::admin(&env);
+        admin::accept_admin(&env)?;
+        
+        // 2. El admin saliente pierde sus roles
+        let new_admin = base::admin(&env).ok_or(TokenError::NotInitialized)?;
+        if let Some(previous_admin) = previous_admin {
+            if previous_admin != new_admin {
+                revoke_all_roles(&env, &previous_admin, &new_admin);
+            }
         }
         
-        // 2. Verificar autorización del sender
-        from.require_auth();
+        Ok(())
+    }
+    
+    fn renounce_admin(env: Env) -> Result<(), TokenError> {
+        // 1. Renunciar (verifica la firma del admin)
+        let admin = base::admin(&env);
+        admin::renounce_admin(&env)?;
         
-        // 3. Validaciones
-        if amount <= 0 {
-            return Err(TokenError::InvalidAmount);
+        // 2. El admin que renuncia pierde sus roles
+        if let Some(admin) = admin {
+            revoke_all_roles(&env, &admin, &admin);
         }
         
-        // 4. No permitir transferencia a sí mismo (gas-efficient)
-        if from == to {
-            return Err(TokenError::InvalidRecipient);
-        }
+        Ok(())
+    }
+    
+    fn pending_admin(env: Env) -> Option<Address> {
+        admin::pending_admin(&env)
+    }
+    
+    fn admin_renounced(env: Env) -> bool {
+        admin::admin_renounced(&env)
+    }
+}
+
+#[contractimpl]
+impl RoleTrait for TokenBDB {
+    fn grant_role(env: Env, role: Role, account: Address) -> Result<(), TokenError> {
+        // 1. Solo el admin gestiona roles
+        let admin = token_core::require_admin(&env)?;
         
-        let from_balance = Self::balance(env.clone(), from.clone());
-        if from_balance < amount {
-            return Err(TokenError::InsufficientBalance);
+        // 2. Validar que el rol no esté otorgado
+        if storage::has_role(&env, role, &account) {
+            return Err(TokenError::RoleAlreadyGranted);
         }
         
-        // 5. Calcular nuevos balances con verificación de overflow
-        let new_from_balance = from_balance - amount;
-        let to_balance = Self::balance(env.clone(), to.clone());
-        let new_to_balance = to_balance.checked_add(amount)
-            .ok_or(TokenError::OverflowError)?;
-        
-        // 6. Actualizar balances con TTL
-        // Optimización: si from_balance = 0, eliminar key
-        // CORREGIDO: sin clone() innecesario
-        if new_from_balance == 0 {
-            env.storage().persistent().remove(&DataKey::Balance(from.clone()));
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Balance(from.clone()), // CORREGIDO: sin clone()
-                &new_from_balance
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Balance(from.clone()), 
-                100_000,
-                200_000
-            );
+        // 3. Guardar el rol
+        storage::write_role(&env, role, &account);
+        
+        // 4. Emitir evento
+        RoleGrantedEvent {
+            role,
+            account,
+            admin,
+        }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn revoke_role(env: Env, role: Role, account: Address) -> Result<(), TokenError> {
+        // 1. Solo el admin gestiona roles
+        let admin = token_core::require_admin(&env)?;
+        
+        // 2. Validar que el rol esté otorgado
+        if !storage::has_role(&env, role, &account) {
+            return Err(TokenError::RoleNotGranted);
         }
         
-        env.storage().persistent().set(
-            &DataKey::Balance(to.clone()), 
-            &new_to_balance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Balance(to.clone()), 
-            100_000,
-            200_000
-        );
+        // 3. Eliminar el rol
+        storage::remove_role(&env, role, &account);
         
-        // 7. Emitir evento con balances post-transferencia
-        TransferEvent {
-            from: from.clone(),
-            to: to.clone(),
-            amount,
-            from_new_balance: new_from_balance,  
-            to_new_balance: new_to_balance,        
+        // 4. Emitir evento
+        RoleRevokedEvent {
+            role,
+            account,
+            admin,
         }.publish(&env);
-                
+        
         Ok(())
     }
     
-    fn approve(
-        env: Env, 
-        from: Address, 
-        spender: Address, 
-        amount: i128
+    fn has_role(env: Env, role: Role, account: Address) -> bool {
+        storage::has_role(&env, role, &account)
+    }
+}
+
+#[contractimpl]
+impl PauseTrait for TokenBDB {
+    fn pause(env: Env, caller: Address, target: PauseTarget) -> Result<(), TokenError> {
+        // Solo cuentas con rol Pauser
+        require_role(&env, Role::Pauser, &caller)?;
+        pausable::pause(&env, caller, target);
+        
+        Ok(())
+    }
+    
+    fn unpause(env: Env, caller: Address, target: PauseTarget) -> Result<(), TokenError> {
+        // Solo cuentas con rol Pauser
+        require_role(&env, Role::Pauser, &caller)?;
+        pausable::unpause(&env, caller, target);
+        
+        Ok(())
+    }
+    
+    fn set_redeem_only(env: Env, caller: Address) -> Result<(), TokenError> {
+        // Solo cuentas con rol Pauser
+        require_role(&env, Role::Pauser, &caller)?;
+        pausable::set_redeem_only(&env, caller);
+        
+        Ok(())
+    }
+    
+    fn is_paused(env: Env, target: PauseTarget) -> bool {
+        pausable::is_paused(&env, target)
+    }
+}
+
+#[contractimpl]
+impl AuthorizationTrait for TokenBDB {
+    fn set_authorized(env: Env, id: Address, authorize: bool) -> Result<(), TokenError> {
+        admin::set_authorized(&env, id, authorize)
+    }
+    
+    fn authorized(env: Env, id: Address) -> bool {
+        admin::authorized(&env, &id)
+    }
+}
+
+#[contractimpl]
+impl SupplyCapTrait for TokenBDB {
+    fn max_supply(env: Env) -> Option<i128> {
+        capped::max_supply(&env)
+    }
+    
+    fn lower_max_supply(env: Env, new_max_supply: i128) -> Result<(), TokenError> {
+        capped::lower_max_supply(&env, new_max_supply)
+    }
+}
+
+#[contractimpl]
+impl UpgradeTrait for TokenBDB {
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), TokenError> {
+        admin::upgrade(&env, new_wasm_hash)
+    }
+    
+    fn migrate(env: Env) -> Result<(), TokenError> {
+        admin::migrate(&env)
+    }
+    
+    fn version(_env: Env) -> u32 {
+        CONTRACT_VERSION
+    }
+    
+    fn storage_version(env: Env) -> u32 {
+        admin::storage_version(&env)
+    }
+}
+
+#[contractimpl]
+impl BatchTrait for TokenBDB {
+    fn transfer_batch(
+        env: Env,
+        from: Address,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError> {
+        base::transfer_batch::<Self>(&env, from, recipients)
+    }
+    
+    fn mint_batch(
+        env: Env,
+        minter: Address, recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError> {
+        // Verificar inicialización y que `minter` tenga el rol Minter
+        require_role(&env, Role::Minter, &minter)?;
+        mintable::mint_batch::<Self>(&env, recipients)
+    }
+    
+    fn balances(env: Env, ids: Vec<Address>) -> Vec<i128> {
+        base::balances(&env, ids)
+    }
+}
+
+#[contractimpl]
+impl CheckpointTrait for TokenBDB {
+    fn balance_at(env: Env, id: Address, ledger: u32) -> Result<i128, TokenError> {
+        base::balance_at(&env, &id, ledger)
+    }
+    
+    fn total_supply_at(env: Env, ledger: u32) -> Result<i128, TokenError> {
+        base::total_supply_at(&env, ledger)
+    }
+}
+
+#[contractimpl]
+impl PermitTrait for TokenBDB {
+    fn register_permit_key(
+        env: Env,
+        owner: Address,
+        public_key: BytesN<32>
     ) -> Result<(), TokenError> {
         // 1. Verificar inicialización
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::NotInitialized);
-        }
+        require_initialized(&env)?;
         
         // 2. Verificar autorización del owner
-        from.require_auth();
+        owner.require_auth();
+        
+        // 3. Guardar la clave (reemplaza la anterior)
+        storage::write_permit_key(&env, &owner, &public_key);
         
-        // 3. Validación: amount debe ser >= 0 (permitir 0 para revocar)
+        // 4. Emitir evento
+        PermitKeyRegisteredEvent { owner, public_key }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn permit(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        nonce: u64,
+        signature: BytesN<64>
+    ) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
+        require_initialized(&env)?;
+        
+        // 2. Validaciones del permit
         if amount < 0 {
             return Err(TokenError::InvalidAmount);
         }
-        
-        // 4. Obtener allowance anterior para el evento
-        let old_allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
-        
-        // 5. Actualizar allowance
-        // CORREGIDO: sin clone() innecesario en Address
-        if amount == 0 {
-            // Optimización: eliminar key si allowance = 0
-            env.storage().persistent().remove(
-                &DataKey::Allowance(from.clone(), spender.clone()) // CORREGIDO: sin clone()
-            );
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Allowance(from.clone(), spender.clone()), // CORREGIDO: sin clone()
-                &amount
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Allowance(from.clone(), spender.clone()), // CORREGIDO: sin clone()
-                100_000,
-                200_000
-            );
+        if expiration_ledger < env.ledger().sequence() {
+            return Err(TokenError::PermitExpired);
+        }
+        if nonce != Self::permit_nonce(env.clone(), owner.clone()) {
+            return Err(TokenError::InvalidNonce);
         }
         
-        // 6. Evento mejorado con allowance anterior y nuevo
-        ApproveEvent {
-            from: from.clone(),
-            spender: spender.clone(),
-            old_allowance,
-            new_allowance: amount,
-        }.publish(&env);
+        // 3. Verificar la firma contra la clave registrada del owner
+        // (la verificación aborta la invocación si la firma no es válida)
+        let public_key = storage::read_permit_key(&env, &owner)
+            .ok_or(TokenError::PermitKeyNotRegistered)?;
+        let message = permit_message(&env, &owner, &spender, amount, expiration_ledger, nonce);
+        env.crypto().ed25519_verify(&public_key, &message, &signature);
+        
+        // 4. Consumir el nonce: la misma firma no se puede reenviar
+        let next_nonce = nonce.checked_add(1).ok_or(TokenError::OverflowError)?;
+        storage::write_permit_nonce(&env, &owner, next_nonce);
+        
+        // 5. Actualizar allowance y emitir el mismo evento que approve()
+        base::set_allowance(&env, owner, spender, amount, expiration_ledger)?;
         
         Ok(())
     }
     
-    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
-    env.storage().persistent()
-        .get(&DataKey::Allowance(from.clone(), spender.clone()))
-        .unwrap_or(0)
+    fn permit_key(env: Env, owner: Address) -> Option<BytesN<32>> {
+        storage::read_permit_key(&env, &owner)
+    }
+    
+    fn permit_nonce(env: Env, owner: Address) -> u64 {
+        storage::read_permit_nonce(&env, &owner)
+    }
 }
 
-fn transfer_from(
-    env: Env, 
-    spender: Address, 
-    from: Address, 
-    to: Address, 
-    amount: i128
-) -> Result<(), TokenError> {
-    // 1. Verificar inicialización
-    if !env.storage().instance().has(&DataKey::Initialized) {
-        return Err(TokenError::NotInitialized);
+#[contractimpl]
+impl VotesTrait for TokenBDB {
+    fn delegate(env: Env, delegator: Address, delegatee: Address) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
+        require_initialized(&env)?;
+        
+        // 2. Verificar autorización del delegador
+        delegator.require_auth();
+        
+        // 3. Guardar el nuevo delegado
+        let previous = storage::read_delegate(&env, &delegator);
+        storage::write_delegate(&env, &delegator, &delegatee);
+        
+        // 4. Mover el balance actual del delegado anterior al nuevo
+        let balance = base::balance(&env, &delegator);
+        move_delegate_votes(&env, previous.clone(), Some(delegatee.clone()), balance);
+        
+        // 5. Emitir evento
+        DelegateChangedEvent {
+            delegator,
+            from_delegate: previous,
+            to_delegate: delegatee,
+        }.publish(&env);
+        
+        Ok(())
     }
     
-    // 2. Verificar autorización del spender
-    spender.require_auth();
-    
-    // 3. Validaciones
-    if amount <= 0 {
-        return Err(TokenError::InvalidAmount);
+    fn delegates(env: Env, account: Address) -> Option<Address> {
+        storage::read_delegate(&env, &account)
     }
     
-    // 4. No permitir transferencia a sí mismo
-    if to == from {
-        return Err(TokenError::InvalidRecipient);
+    fn get_votes(env: Env, account: Address) -> i128 {
+        storage::read_votes(&env, &account)
     }
     
-    // 5. Verificar allowance
-    let allowed = Self::allowance(env.clone(), from.clone(), spender.clone());
-    if allowed < amount {
-        return Err(TokenError::InsufficientAllowance);
+    fn get_past_votes(env: Env, account: Address, ledger: u32) -> Result<i128, TokenError> {
+        require_past_ledger(&env, ledger)?;
+        storage::read_votes_at(&env, &account, ledger)
     }
-    
-    // 6. Verificar balance
-    let from_balance = Self::balance(env.clone(), from.clone());
-    if from_balance < amount {
-        return Err(TokenError::InsufficientBalance);
+}
+
+#[contractimpl]
+impl MetadataTrait for TokenBDB {
+    fn metadata(env: Env) -> Result<TokenMetadata, TokenError> {
+        base::metadata(&env)
     }
     
-    // 7. Calcular nuevos valores
-    let new_from_balance = from_balance - amount;
-    let to_balance = Self::balance(env.clone(), to.clone());
-    let new_to_balance = to_balance.checked_add(amount)
-        .ok_or(TokenError::OverflowError)?;
-    let new_allowance = allowed - amount;
+    fn update_metadata(
+        env: Env,
+        caller: Address,
+        metadata: TokenMetadata
+    ) -> Result<(), TokenError> {
+        // Verificar rol MetadataManager
+        require_role(&env, Role::MetadataManager, &caller)?;
+        base::update_metadata(&env, caller, metadata)
+    }
+}
+
+#[contractimpl]
+impl AllowanceTrait for TokenBDB {
+    fn increase_allowance(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32
+    ) -> Result<(), TokenError> {
+        base::increase_allowance(&env, from, spender, amount, expiration_ledger)
+    }
     
-    // 8. Actualizar estado atómicamente
-    if new_from_balance == 0 {
-        env.storage().persistent().remove(&DataKey::Balance(from.clone()));
-    } else {
-        env.storage().persistent().set(
-            &DataKey::Balance(from.clone()), 
-            &new_from_balance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Balance(from.clone()), 
-            100_000,
-            200_000
-        );
+    fn decrease_allowance(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128
+    ) -> Result<(), TokenError> {
+        base::decrease_allowance(&env, from, spender, amount)
+    }
+}
+
+#[contractimpl]
+impl HoldersTrait for TokenBDB {
+    fn holder_count(env: Env) -> Result<u32, TokenError> {
+        base::holder_count(&env)
     }
     
-    env.storage().persistent().set(
-        &DataKey::Balance(to.clone()), 
-        &new_to_balance
-    );
-    env.storage().persistent().extend_ttl(
-        &DataKey::Balance(to.clone()), 
-        100_000,
-        200_000
-    );
+    fn holders(env: Env, start: u32, limit: u32) -> Result<Vec<Address>, TokenError> {
+        base::holders(&env, start, limit)
+    }
     
-    if new_allowance == 0 {
-        env.storage().persistent().remove(
-            &DataKey::Allowance(from.clone(), spender.clone()) 
-        );
-    } else {
-        env.storage().persistent().set(
-            &DataKey::Allowance(from.clone(), spender.clone()), 
-            &new_allowance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Allowance(from.clone(), spender.clone()), 
-            100_000,
-            200_000
-        );
+    fn spender_count(env: Env, owner: Address) -> Result<u32, TokenError> {
+        base::spender_count(&env, &owner)
     }
     
-		// 9. Emitir evento CORREGIDO
-		TransferFromEvent {
-		    spender: spender.clone(),
-		    from: from.clone(), 
-		    to: to.clone(),
-		    amount: amount,
-		    from_new_balance: new_from_balance,
-		    to_new_balance: new_to_balance,
-		    new_allowance: new_allowance,
-		}.publish(&env);
-		
-		Ok(())
-	}
+    fn allowances_of(env: Env, owner: Address, start: u32, limit: u32) -> Result<Vec<Address>, TokenError> {
+        base::allowances_of(&env, &owner, start, limit)
+    }
+}
 
+#[contractimpl]
+impl MemoTrait for TokenBDB {
+    fn transfer_with_memo(
+        env: Env,
+        from: Address,
+        to: MuxedAddress,
+        amount: i128,
+        memo: String
+    ) -> Result<(), TokenError> {
+        base::transfer_with_memo::<Self>(&env, from, to, amount, Some(memo))
+    }
     
-    // Métodos de consulta
-    fn name(env: Env) -> String {
-        // Verificar inicialización antes de devolver metadata
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            // CORRECCIÓN: String::new() en lugar de String::from_str() que no existe
-            // VIEJA FORMA: String::from_str(&env, "") - método inexistente
-            // NUEVA FORMA: String::from_str(&env, "") - forma correcta de crear String vacío
-            return String::from_str(&env, "");
-        }
-        
-        env.storage().instance()
-            .get(&DataKey::TokenName)
-            .unwrap_or(String::from_str(&env, "")) 
+    fn mint_with_memo(
+        env: Env,
+        minter: Address,
+        to: Address,
+        amount: i128,
+        memo: String
+    ) -> Result<(), TokenError> {
+        // Verificar inicialización y que `minter` tenga el rol Minter
+        require_role(&env, Role::Minter, &minter)?;
+        mintable::mint_with_memo::<Self>(&env, to, amount, Some(memo))
     }
     
-    fn symbol(env: Env) -> String {
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return String::from_str(&env, ""); 
-        }
-        
-        env.storage().instance()
-            .get(&DataKey::TokenSymbol)
-            .unwrap_or(String::from_str(&env, ""))
+    fn transfer_from_with_memo(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+        memo: String
+    ) -> Result<(), TokenError> {
+        base::transfer_from_with_memo::<Self>(&env, spender, from, to, amount, Some(memo))
     }
     
-    fn decimals(env: Env) -> u32 {
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return 0;
-        }
-        
-        env.storage().instance()
-            .get(&DataKey::Decimals)
-            .unwrap_or(0)
+    fn transfer_batch_with_memo(
+        env: Env,
+        from: Address,
+        recipients: Vec<(Address, i128, String)>
+    ) -> Result<(), TokenError> {
+        base::transfer_batch_with_memo::<Self>(&env, from, recipients)
     }
     
-    fn total_supply(env: Env) -> i128 {
-        env.storage().instance()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0)
+    fn set_memo_required(env: Env, id: Address, required: bool) -> Result<(), TokenError> {
+        base::set_memo_required(&env, id, required)
     }
     
-    fn admin(env: Env) -> Address {
-        env.storage().instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not initialized")
+    fn memo_required(env: Env, id: Address) -> bool {
+        base::memo_required(&env, &id)
+    }
+}
+
+#[contractimpl]
+impl TransferAndCallTrait for TokenBDB {
+    fn transfer_and_call(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+        data: Bytes
+    ) -> Result<(), TokenError> {
+        // 1. Rechazar la reentrada desde el hook de otro receptor
+        if storage::is_locked(&env) {
+            return Err(TokenError::Reentrancy);
+        }
+        
+        // 2. Transferir (inicialización, pausa, autorización y balances)
+        base::transfer::<Self>(&env, from.clone(), to.clone().into(), amount)?;
+        
+        // 3. Solo los contratos con código reciben la notificación
+        if !matches!(to.executable(), Some(Executable::Wasm(_))) {
+            return Ok(());
+        }
+        
+        // 4. Llamar al hook con el guard activo; el host de Soroban además
+        // impide que el receptor vuelva a llamar al token durante el hook
+        storage::write_locked(&env, true);
+        let result = TokenReceiverClient::new(&env, &to).try_on_token_received(
+            &env.current_contract_address(),
+            &from,
+            &amount,
+            &data
+        );
+        storage::write_locked(&env, false);
+        
+        // 5. Cualquier respuesta distinta de true revierte la transferencia
+        // (balances, checkpoints, eventos y el guard) al devolver el error
+        if !matches!(result, Ok(Ok(true))) {
+            return Err(TokenError::TransferRejected);
+        }
+        
+        Ok(())
     }
 }
 
@@ -1,52 +1,141 @@
 // src/storage.rs
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, BytesN, Env};
 
-/// Enum que define todas las claves de almacenamiento
+use token_core::TokenError;
+
+// Claves y helpers comunes (balances, allowances, pausa, checkpoints...)
+pub use token_core::storage::*;
+
+/// Claves de almacenamiento propias de TokenBDB
 /// 
-/// Separamos los datos en dos tipos de storage:
-/// - Instance Storage: Metadatos globales (más barato)
-/// - Persistent Storage: Datos de usuarios (requiere TTL)
+/// Se suman a las `DataKey` del núcleo. Los nombres de las variantes son
+/// los mismos que antes de extraer token_core, así que las keys ya
+/// guardadas siguen siendo válidas (el historial de votos pasó a
+/// `VoteHistory` con el esquema v3).
 #[contracttype]
-pub enum DataKey {
-    /// Balance de cada usuario - Persistent Storage
-    /// Usa Address como key para acceso O(1)
-    Balance(Address),
-    
-    /// Permisos de gasto entre usuarios - Persistent Storage
-    /// Tupla (owner, spender) para lookup eficiente
-    Allowance(Address, Address),
+pub enum ExtensionKey {
+    /// Rol asignado a una cuenta - Persistent Storage
+    /// Tupla (rol, cuenta); solo existe si el rol está otorgado
+    Role(Role, Address),
     
-    /// Supply total de tokens - Instance Storage
-    /// Contador global de tokens en circulación
-    TotalSupply,
+    /// Delegado de votos de una cuenta - Persistent Storage
+    /// Solo existe si la cuenta delegó alguna vez
+    Delegate(Address),
     
-    /// Dirección del administrador - Instance Storage
-    /// Solo esta cuenta puede mintear tokens
-    Admin,
+    /// Historial de votos de un delegado - Persistent Storage
+    /// `History`; las páginas llenas van en la key (VoteHistory(id), n)
+    VoteHistory(Address),
     
-    /// Nombre del token - Instance Storage
-    /// Ejemplo: "Buen Día Builders Token"
-    TokenName,
+    /// Clave pública ed25519 para permits - Persistent Storage
+    /// Solo existe si la cuenta registró una clave
+    PermitKey(Address),
     
-    /// Símbolo del token - Instance Storage
-    /// Ejemplo: "BDB", "USDC", etc (máximo 32 caracteres)
-    TokenSymbol,
+    /// Próximo nonce de permit de una cuenta - Persistent Storage
+    /// Ausente equivale a 0
+    PermitNonce(Address),
     
-    /// Número de decimales - Instance Storage
-    /// Típicamente 7 para Stellar (alineado con XLM)
-    Decimals,
-    
-    /// Flag para verificar inicialización - Instance Storage
-    /// Previene re-inicialización del contrato
-    Initialized,
+    /// Guard de reentrada de transfer_and_call() - Instance Storage
+    /// Solo existe mientras se espera la respuesta de on_token_received()
+    ReentrancyLock,
 }
 
-/// Metadata struct para almacenar información del token
-/// Usado en initialize() para pasar múltiples parámetros
+/// Roles para las operaciones privilegiadas del token
+/// 
+/// El admin otorga y revoca roles; cada operación privilegiada
+/// verifica el rol correspondiente en lugar del admin
 #[contracttype]
-#[derive(Clone)]
-pub struct TokenMetadata {
-    pub name: String,
-    pub symbol: String,
-    pub decimals: u32,
-}
\ No newline at end of file
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// Puede crear tokens con mint()
+    Minter,
+    /// Puede quemar tokens de cualquier cuenta sin allowance (privileged_burn)
+    Burner,
+    /// Puede pausar y reanudar operaciones
+    Pauser,
+    /// Puede actualizar los metadatos del token
+    MetadataManager,
+}
+
+/// Indica si `account` tiene el rol `role`
+pub fn has_role(env: &Env, role: Role, account: &Address) -> bool {
+    env.storage().persistent().has(&ExtensionKey::Role(role, account.clone()))
+}
+
+/// Otorga el rol `role` a `account`
+pub fn write_role(env: &Env, role: Role, account: &Address) {
+    let key = ExtensionKey::Role(role, account.clone());
+    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+}
+
+/// Revoca el rol `role` de `account`
+pub fn remove_role(env: &Env, role: Role, account: &Address) {
+    env.storage().persistent().remove(&ExtensionKey::Role(role, account.clone()));
+}
+
+/// Delegado de votos de `id` (None si nunca delegó)
+pub fn read_delegate(env: &Env, id: &Address) -> Option<Address> {
+    env.storage().persistent().get(&ExtensionKey::Delegate(id.clone()))
+}
+
+/// Fija `delegatee` como delegado de votos de `id`
+pub fn write_delegate(env: &Env, id: &Address, delegatee: &Address) {
+    let key = ExtensionKey::Delegate(id.clone());
+    env.storage().persistent().set(&key, delegatee);
+    env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+}
+
+/// Votos actuales de `id` (el último checkpoint)
+pub fn read_votes(env: &Env, id: &Address) -> i128 {
+    read_last_checkpoint(env, &ExtensionKey::VoteHistory(id.clone())).unwrap_or(0)
+}
+
+/// Registra los votos de `id` en el ledger actual
+pub fn write_votes_checkpoint(env: &Env, id: &Address, previous: i128, votes: i128) {
+    write_checkpoint(env, &ExtensionKey::VoteHistory(id.clone()), previous, votes);
+}
+
+/// Votos de `id` al cierre de `ledger`
+pub fn read_votes_at(env: &Env, id: &Address, ledger: u32) -> Result<i128, TokenError> {
+    read_checkpoint(env, &ExtensionKey::VoteHistory(id.clone()), ledger, 0)
+}
+
+/// Clave pública de permits de `id` (None si no registró ninguna)
+pub fn read_permit_key(env: &Env, id: &Address) -> Option<BytesN<32>> {
+    env.storage().persistent().get(&ExtensionKey::PermitKey(id.clone()))
+}
+
+/// Registra `public_key` como clave de permits de `id`
+pub fn write_permit_key(env: &Env, id: &Address, public_key: &BytesN<32>) {
+    let key = ExtensionKey::PermitKey(id.clone());
+    env.storage().persistent().set(&key, public_key);
+    env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+}
+
+/// Próximo nonce de permit de `id`
+pub fn read_permit_nonce(env: &Env, id: &Address) -> u64 {
+    env.storage().persistent()
+        .get(&ExtensionKey::PermitNonce(id.clone()))
+        .unwrap_or(0)
+}
+
+/// Guarda el próximo nonce de permit de `id`
+pub fn write_permit_nonce(env: &Env, id: &Address, nonce: u64) {
+    let key = ExtensionKey::PermitNonce(id.clone());
+    env.storage().persistent().set(&key, &nonce);
+    env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+}
+
+/// Indica si hay un transfer_and_call() esperando a su receptor
+pub fn is_locked(env: &Env) -> bool {
+    env.storage().instance().has(&ExtensionKey::ReentrancyLock)
+}
+
+/// Activa o libera el guard de reentrada
+pub fn write_locked(env: &Env, locked: bool) {
+    if locked {
+        env.storage().instance().set(&ExtensionKey::ReentrancyLock, &true);
+    } else {
+        env.storage().instance().remove(&ExtensionKey::ReentrancyLock);
+    }
+}
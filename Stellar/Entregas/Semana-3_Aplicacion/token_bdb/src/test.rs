@@ -1,13 +1,18 @@
 // src/test.rs
 #![cfg(test)]
+extern crate std;
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Events},
-    Address, Env, String, symbol_short,
+    testutils::{Address as _, Events, Ledger, MuxedAddress as _},
+    token::TokenClient,
+    vec, Address, BytesN, Env, Event, MuxedAddress, String,
 };
 
+use crate::storage::DataKey;
 use crate::TokenBDBClient;
+use soroban_sdk::Map;
+use token_core::{MAX_METADATA_ENTRIES, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URL_LENGTH};
 
 /// Test básico de inicialización del token
 /// 
@@ -28,7 +33,7 @@ fn test_initialize() {
     let symbol = String::from_str(&env, "BDB");
     
     // Act: Inicializar el token
-    let result = client.initialize(&admin, &name, &symbol, &7);
+    client.initialize(&admin, &name, &symbol, &7, &None);
     
     // Assert: Verificar que los metadatos se guardaron correctamente
     assert_eq!(client.name(), name);
@@ -53,10 +58,10 @@ fn test_initialize_twice_fails() {
     let symbol = String::from_str(&env, "TOK");
     
     // Primera inicialización debe funcionar
-    client.initialize(&admin, &name, &symbol, &7);
+    client.initialize(&admin, &name, &symbol, &7, &None);
     
     // Segunda debe fallar con AlreadyInitialized
-    let result = client.try_initialize(&admin, &name, &symbol, &7);
+    let result = client.try_initialize(&admin, &name, &symbol, &7, &None);
     assert_eq!(result, Err(Ok(TokenError::AlreadyInitialized)));
 }
 
@@ -78,7 +83,8 @@ fn test_invalid_decimals() {
         &admin,
         &String::from_str(&env, "Token"), // CORREGIDO: from_slice en lugar de from_str
         &String::from_str(&env, "TOK"),   // CORREGIDO: método correcto del SDK
-        &19  // ❌ Inválido: excede MAX_DECIMALS (18)
+        &19,  // ❌ Inválido: excede MAX_DECIMALS (18)
+        &None
     );
     assert_eq!(result, Err(Ok(TokenError::InvalidDecimals)));
 }
@@ -104,14 +110,15 @@ fn test_mint_and_balance() {
         &admin, 
         &String::from_str(&env, "Builder Token"), // CORREGIDO: from_slice()
         &String::from_str(&env, "BDB"),           // CORREGIDO: método válido
-        &7
+        &7,
+        &None
     );
     
     // Mock auth: En tests, simulamos autorizaciones sin firmas reales
     env.mock_all_auths();
     
     // Mintear 1000 tokens
-    client.mint(&user, &1000);
+    client.mint(&admin, &user, &1000);
     
     // Verificar estado actualizado
     assert_eq!(client.balance(&user), 1000);
@@ -135,13 +142,14 @@ fn test_mint_zero_fails() {
         &admin,
         &String::from_str(&env, "Token"), // CORREGIDO: from_slice()
         &String::from_str(&env, "TOK"),   // CORREGIDO
-        &7
+        &7,
+        &None
     );
     
     env.mock_all_auths();
     
     // Mintear 0 debe fallar con InvalidAmount
-    let result = client.try_mint(&user, &0);
+    let result = client.try_mint(&admin, &user, &0);
     assert_eq!(result, Err(Ok(TokenError::InvalidAmount)));
 }
 
@@ -166,11 +174,12 @@ fn test_transfer() {
         &admin,
         &String::from_str(&env, "Builder Token"), // CORREGIDO
         &String::from_str(&env, "BDB"),           // CORREGIDO
-        &7
+        &7,
+        &None
     );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);
+    client.mint(&admin, &alice, &1000);
     
     // Act: Alice transfiere a Bob
     client.transfer(&alice, &bob, &250);
@@ -198,11 +207,12 @@ fn test_transfer_insufficient_balance() {
         &admin,
         &String::from_str(&env, "Token"), // CORREGIDO: from_slice()
         &String::from_str(&env, "TOK"),   // CORREGIDO
-        &7
+        &7,
+        &None
     );
     
     env.mock_all_auths();
-    client.mint(&alice, &100);
+    client.mint(&admin, &alice, &100);
     
     // Intentar transferir más de lo que tiene debe fallar
     let result = client.try_transfer(&alice, &bob, &200);
@@ -228,11 +238,12 @@ fn test_transfer_to_self() {
         &admin,
         &String::from_str(&env, "Token"), // CORREGIDO
         &String::from_str(&env, "TOK"),   // CORREGIDO
-        &7
+        &7,
+        &None
     );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);
+    client.mint(&admin, &alice, &1000);
     
     // Transfer a sí mismo debe fallar con InvalidRecipient
     let result = client.try_transfer(&alice, &alice, &100);
@@ -262,14 +273,15 @@ fn test_approve_and_transfer_from() {
         &admin,
         &String::from_str(&env, "Token"), // CORREGIDO: from_slice()
         &String::from_str(&env, "TOK"),   // CORREGIDO
-        &7
+        &7,
+        &None
     );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);
+    client.mint(&admin, &alice, &1000);
     
     // Alice aprueba a Bob para gastar hasta 300 tokens
-    client.approve(&alice, &bob, &300);
+    client.approve(&alice, &bob, &300, &1000);
     assert_eq!(client.allowance(&alice, &bob), 300);
     
     // Bob transfiere 200 tokens de Alice a Charlie
@@ -299,12 +311,13 @@ fn test_transfer_from_insufficient_allowance() {
         &admin,
         &String::from_str(&env, "Token"), // CORREGIDO
         &String::from_str(&env, "TOK"),   // CORREGIDO
-        &7
+        &7,
+        &None
     );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);
-    client.approve(&alice, &bob, &100);  // Solo 100 aprobados
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &bob, &100, &1000);  // Solo 100 aprobados
     
     // Bob intenta transferir más de lo aprobado
     let result = client.try_transfer_from(&bob, &alice, &charlie, &200);
@@ -328,11 +341,12 @@ fn test_burn() {
         &admin,
         &String::from_str(&env, "Token"), // CORREGIDO: from_slice()
         &String::from_str(&env, "TOK"),   // CORREGIDO
-        &7
+        &7,
+        &None
     );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000);
+    client.mint(&admin, &alice, &1000);
     
     // Alice quema 300 de sus tokens
     client.burn(&alice, &300);
@@ -359,7 +373,7 @@ fn test_operations_without_init() {
     
     // Todas las operaciones deben fallar con NotInitialized
     assert_eq!(
-        client.try_mint(&alice, &100),
+        client.try_mint(&alice, &alice, &100),
         Err(Ok(TokenError::NotInitialized))
     );
     
@@ -388,7 +402,8 @@ fn test_initialize_empty_name_fails() {
         &admin,
         &String::from_str(&env, ""),  // Nombre vacío
         &String::from_str(&env, "BDB"),
-        &7
+        &7,
+        &None
     );
     assert_eq!(result, Err(Ok(TokenError::InvalidMetadata)));
 }
@@ -407,7 +422,3001 @@ fn test_initialize_empty_symbol_fails() {
         &admin,
         &String::from_str(&env, "Builder Token"),
         &String::from_str(&env, ""),  // Símbolo vacío
-        &7
+        &7,
+        &None
     );
     assert_eq!(result, Err(Ok(TokenError::InvalidMetadata)));
 }
+
+// ============================================================
+// Conformidad SEP-41: el contrato usado vía soroban_sdk::token::TokenClient
+// ============================================================
+
+/// Helper: registra e inicializa el token con 1000 tokens para `holder`
+///
+/// Devuelve el address del contrato para crear un `TokenClient`
+fn setup_sep41(env: &Env, holder: &Address) -> Address {
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(env, "Builder Token"),
+        &String::from_str(env, "BDB"),
+        &7,
+        &None
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, holder, &1000);
+    
+    contract_id
+}
+
+/// Test: metadatos y balance leídos con el cliente estándar
+#[test]
+fn test_sep41_metadata_and_balance() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let token = TokenClient::new(&env, &setup_sep41(&env, &alice));
+    
+    assert_eq!(token.name(), String::from_str(&env, "Builder Token"));
+    assert_eq!(token.symbol(), String::from_str(&env, "BDB"));
+    assert_eq!(token.decimals(), 7);
+    assert_eq!(token.balance(&alice), 1000);
+}
+
+/// Test: transfer con el cliente estándar, incluyendo destino muxed
+///
+/// Los tokens enviados a una dirección muxed se acreditan a la cuenta base
+#[test]
+fn test_sep41_transfer() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token = TokenClient::new(&env, &setup_sep41(&env, &alice));
+    
+    token.transfer(&alice, &bob, &250);
+    assert_eq!(token.balance(&alice), 750);
+    assert_eq!(token.balance(&bob), 250);
+    
+    let muxed = MuxedAddress::generate(&env);
+    token.transfer(&alice, &muxed, &50);
+    assert_eq!(token.balance(&muxed.address()), 50);
+    assert_eq!(token.balance(&alice), 700);
+}
+
+/// Test: approve + transfer_from con el cliente estándar
+#[test]
+fn test_sep41_approve_and_transfer_from() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    let token = TokenClient::new(&env, &setup_sep41(&env, &alice));
+    
+    let expiration = env.ledger().sequence() + 100;
+    token.approve(&alice, &bob, &300, &expiration);
+    assert_eq!(token.allowance(&alice, &bob), 300);
+    
+    token.transfer_from(&bob, &alice, &charlie, &200);
+    assert_eq!(token.balance(&alice), 800);
+    assert_eq!(token.balance(&charlie), 200);
+    assert_eq!(token.allowance(&alice, &bob), 100);
+}
+
+/// Test: el allowance deja de valer después de `expiration_ledger`
+///
+/// En el ledger de expiración todavía se puede gastar,
+/// en el siguiente el allowance vale 0
+#[test]
+fn test_sep41_allowance_expires() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    let token = TokenClient::new(&env, &setup_sep41(&env, &alice));
+    
+    let expiration = env.ledger().sequence() + 10;
+    token.approve(&alice, &bob, &300, &expiration);
+    
+    env.ledger().set_sequence_number(expiration);
+    token.transfer_from(&bob, &alice, &charlie, &100);
+    assert_eq!(token.allowance(&alice, &bob), 200);
+    
+    env.ledger().set_sequence_number(expiration + 1);
+    assert_eq!(token.allowance(&alice, &bob), 0);
+    
+    let result = token.try_transfer_from(&bob, &alice, &charlie, &100);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance.into())));
+}
+
+/// Test: approve con un ledger de expiración ya pasado debe fallar
+///
+/// Revocar (amount = 0) sí se permite con cualquier expiración
+#[test]
+fn test_sep41_approve_expired_ledger_fails() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token = TokenClient::new(&env, &setup_sep41(&env, &alice));
+    
+    env.ledger().set_sequence_number(100);
+    
+    let result = token.try_approve(&alice, &bob, &300, &99);
+    assert_eq!(result, Err(Ok(TokenError::InvalidExpiration.into())));
+    
+    token.approve(&alice, &bob, &300, &200);
+    token.approve(&alice, &bob, &0, &0);
+    assert_eq!(token.allowance(&alice, &bob), 0);
+}
+
+/// Test: burn y burn_from con el cliente estándar
+///
+/// burn_from consume allowance y reduce el supply total
+#[test]
+fn test_sep41_burn_and_burn_from() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let contract_id = setup_sep41(&env, &alice);
+    let token = TokenClient::new(&env, &contract_id);
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    token.burn(&alice, &100);
+    assert_eq!(token.balance(&alice), 900);
+    
+    token.approve(&alice, &bob, &300, &(env.ledger().sequence() + 100));
+    token.burn_from(&bob, &alice, &200);
+    
+    assert_eq!(token.balance(&alice), 700);
+    assert_eq!(token.allowance(&alice, &bob), 100);
+    assert_eq!(client.total_supply(), 700);
+}
+
+/// Test: burn_from sin allowance suficiente debe fallar
+#[test]
+fn test_sep41_burn_from_insufficient_allowance() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token = TokenClient::new(&env, &setup_sep41(&env, &alice));
+    
+    token.approve(&alice, &bob, &50, &(env.ledger().sequence() + 100));
+    
+    let result = token.try_burn_from(&bob, &alice, &100);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance.into())));
+    assert_eq!(token.balance(&alice), 1000);
+}
+
+// ============================================================
+// Gestión del admin: transferencia en dos pasos y renuncia
+// ============================================================
+
+/// Helper: registra e inicializa el token, devuelve (client, admin)
+fn setup_admin(env: &Env) -> (TokenBDBClient<'_>, Address) {
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(env, "Builder Token"),
+        &String::from_str(env, "BDB"),
+        &7,
+        &None
+    );
+    env.mock_all_auths();
+    
+    (client, admin)
+}
+
+/// Test: flujo completo propose_admin + accept_admin
+/// 
+/// Cada paso requiere la firma de una de las dos partes
+#[test]
+fn test_propose_and_accept_admin() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let new_admin = Address::generate(&env);
+    
+    client.propose_admin(&new_admin);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.admin(), Some(admin.clone()));  // Aún no cambia
+    
+    client.accept_admin();
+    assert_eq!(env.auths()[0].0, new_admin);
+    assert_eq!(client.admin(), Some(new_admin.clone()));
+    assert_eq!(client.pending_admin(), None);
+    
+    // El nuevo admin gestiona roles: se otorga Minter y mintea
+    let user = Address::generate(&env);
+    client.grant_role(&Role::Minter, &new_admin);
+    assert_eq!(env.auths()[0].0, new_admin);
+    client.mint(&new_admin, &user, &100);
+    assert_eq!(client.balance(&user), 100);
+}
+
+/// Test: accept_admin sin propuesta previa debe fallar
+#[test]
+fn test_accept_admin_without_proposal_fails() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    
+    let result = client.try_accept_admin();
+    assert_eq!(result, Err(Ok(TokenError::NoPendingAdmin)));
+}
+
+/// Test: renounce_admin deshabilita el mint para siempre
+/// 
+/// admin() informa el estado en lugar de hacer panic
+#[test]
+fn test_renounce_admin_disables_mint() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let user = Address::generate(&env);
+    let candidate = Address::generate(&env);
+    let service = Address::generate(&env);
+    
+    client.mint(&admin, &user, &500);
+    client.grant_role(&Role::Minter, &service);
+    client.propose_admin(&candidate);
+    
+    client.renounce_admin();
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.admin(), None);
+    assert_eq!(client.pending_admin(), None);
+    assert!(client.admin_renounced());
+    
+    // Nadie puede mintear ni recuperar el admin
+    assert_eq!(client.try_mint(&service, &user, &1), Err(Ok(TokenError::AdminRenounced)));
+    assert_eq!(
+        client.try_propose_admin(&candidate),
+        Err(Ok(TokenError::AdminRenounced))
+    );
+    assert_eq!(client.try_accept_admin(), Err(Ok(TokenError::NoPendingAdmin)));
+    assert_eq!(client.total_supply(), 500);
+    
+    // Las operaciones de los holders siguen funcionando
+    client.transfer(&user, &candidate, &200);
+    assert_eq!(client.balance(&candidate), 200);
+}
+
+/// Test: admin() devuelve None antes de initialize (sin panic)
+#[test]
+fn test_admin_not_initialized() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    assert_eq!(client.admin(), None);
+    assert!(!client.admin_renounced());
+}
+
+// ============================================================
+// Roles: Minter, Burner, Pauser, MetadataManager
+// ============================================================
+
+/// Test: el admin inicial recibe todos los roles
+#[test]
+fn test_initialize_grants_all_roles_to_admin() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    
+    assert!(client.has_role(&Role::Minter, &admin));
+    assert!(client.has_role(&Role::Burner, &admin));
+    assert!(client.has_role(&Role::Pauser, &admin));
+    assert!(client.has_role(&Role::MetadataManager, &admin));
+}
+
+/// Test: delegar el mint a un servicio sin entregarle el admin
+#[test]
+fn test_grant_minter_role() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let service = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    // Sin rol, el servicio no puede mintear
+    assert_eq!(
+        client.try_mint(&service, &user, &100),
+        Err(Ok(TokenError::MissingRole))
+    );
+    
+    client.grant_role(&Role::Minter, &service);
+    assert_eq!(env.auths()[0].0, admin);
+    assert!(client.has_role(&Role::Minter, &service));
+    
+    // El servicio mintea con su propia firma
+    client.mint(&service, &user, &100);
+    assert_eq!(env.auths()[0].0, service);
+    assert_eq!(client.balance(&user), 100);
+    
+    // Pero no tiene permisos de admin
+    assert_eq!(client.admin(), Some(admin));
+    assert!(!client.has_role(&Role::Pauser, &service));
+}
+
+/// Test: revoke_role quita el permiso de mint
+#[test]
+fn test_revoke_minter_role() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let service = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    client.grant_role(&Role::Minter, &service);
+    client.revoke_role(&Role::Minter, &service);
+    
+    assert!(!client.has_role(&Role::Minter, &service));
+    assert_eq!(
+        client.try_mint(&service, &user, &100),
+        Err(Ok(TokenError::MissingRole))
+    );
+}
+
+/// Test: el Burner quema tokens de otra cuenta sin su firma ni allowance
+#[test]
+fn test_privileged_burn_requires_burner_role() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let service = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.mint(&admin, &user, &1_000);
+    
+    // Sin rol no se puede
+    assert_eq!(
+        client.try_privileged_burn(&service, &user, &100),
+        Err(Ok(TokenError::MissingRole))
+    );
+    
+    client.grant_role(&Role::Burner, &service);
+    client.privileged_burn(&service, &user, &100);
+    assert_eq!(env.auths()[0].0, service);
+    assert_eq!(env.auths().len(), 1);
+    
+    let event = PrivilegedBurnEvent {
+        burner: service.clone(),
+        from: user.clone(),
+        amount: 100,
+        new_balance: 900,
+        new_supply: 900,
+    };
+    let sep41 = Sep41BurnEvent { from: user.clone(), amount: 100 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &event), emitted(&env, &client, &sep41)]
+    );
+    assert_eq!(client.balance(&user), 900);
+    assert_eq!(client.total_supply(), 900);
+    
+    // Respeta la pausa de Burn y el saldo
+    client.pause(&admin, &PauseTarget::Burn);
+    assert_eq!(
+        client.try_privileged_burn(&service, &user, &100),
+        Err(Ok(TokenError::Paused))
+    );
+    client.unpause(&admin, &PauseTarget::Burn);
+    assert_eq!(
+        client.try_privileged_burn(&service, &user, &901),
+        Err(Ok(TokenError::InsufficientBalance))
+    );
+    assert_eq!(
+        client.try_privileged_burn(&service, &user, &0),
+        Err(Ok(TokenError::InvalidAmount))
+    );
+    
+    // Después de renunciar al admin nadie puede forzar quemas
+    client.renounce_admin();
+    assert_eq!(
+        client.try_privileged_burn(&service, &user, &100),
+        Err(Ok(TokenError::AdminRenounced))
+    );
+}
+
+/// Test: grant duplicado y revoke de un rol no otorgado fallan
+#[test]
+fn test_role_grant_revoke_errors() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let service = Address::generate(&env);
+    
+    assert_eq!(
+        client.try_grant_role(&Role::Minter, &admin),
+        Err(Ok(TokenError::RoleAlreadyGranted))
+    );
+    assert_eq!(
+        client.try_revoke_role(&Role::Pauser, &service),
+        Err(Ok(TokenError::RoleNotGranted))
+    );
+}
+
+/// Test: el admin saliente pierde sus roles; los delegados se mantienen
+/// 
+/// Rotar una clave comprometida no sirve si conserva el mint o la pausa
+#[test]
+fn test_accept_admin_revokes_previous_admin_roles() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let new_admin = Address::generate(&env);
+    let service = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.grant_role(&Role::Minter, &service);
+    
+    client.propose_admin(&new_admin);
+    client.accept_admin();
+    
+    for role in [Role::Minter, Role::Burner, Role::Pauser, Role::MetadataManager] {
+        assert!(!client.has_role(&role, &admin));
+        assert!(!client.has_role(&role, &new_admin));
+    }
+    assert_eq!(client.try_mint(&admin, &user, &1), Err(Ok(TokenError::MissingRole)));
+    assert_eq!(
+        client.try_pause(&admin, &PauseTarget::All),
+        Err(Ok(TokenError::MissingRole))
+    );
+    
+    // El servicio delegado sigue minteando
+    client.mint(&service, &user, &100);
+    assert_eq!(client.balance(&user), 100);
+}
+
+/// Test: renounce_admin también revoca los roles del admin
+#[test]
+fn test_renounce_admin_revokes_roles() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    
+    client.renounce_admin();
+    
+    for role in [Role::Minter, Role::Burner, Role::Pauser, Role::MetadataManager] {
+        assert!(!client.has_role(&role, &admin));
+    }
+    assert_eq!(
+        client.try_pause(&admin, &PauseTarget::All),
+        Err(Ok(TokenError::MissingRole))
+    );
+}
+
+/// Test: después de renunciar al admin no se pueden gestionar roles
+#[test]
+fn test_roles_locked_after_renounce() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let service = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.grant_role(&Role::Minter, &service);
+    
+    client.renounce_admin();
+    
+    assert_eq!(
+        client.try_grant_role(&Role::Pauser, &service),
+        Err(Ok(TokenError::AdminRenounced))
+    );
+    // El Minter delegado tampoco puede mintear
+    assert_eq!(
+        client.try_mint(&service, &user, &1),
+        Err(Ok(TokenError::AdminRenounced))
+    );
+}
+
+// ============================================================
+// Pausa (circuit breaker) y modo redeem only
+// ============================================================
+
+/// Test: la pausa global bloquea transfer, transfer_from, mint y burn
+#[test]
+fn test_pause_all_blocks_operations() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &bob, &500, &(env.ledger().sequence() + 100));
+    
+    client.pause(&admin, &PauseTarget::All);
+    assert!(client.is_paused(&PauseTarget::All));
+    assert!(client.is_paused(&PauseTarget::Transfer));
+    
+    assert_eq!(client.try_transfer(&alice, &bob, &10), Err(Ok(TokenError::Paused)));
+    assert_eq!(
+        client.try_transfer_from(&bob, &alice, &bob, &10),
+        Err(Ok(TokenError::Paused))
+    );
+    assert_eq!(client.try_mint(&admin, &alice, &10), Err(Ok(TokenError::Paused)));
+    assert_eq!(client.try_burn(&alice, &10), Err(Ok(TokenError::Paused)));
+    assert_eq!(client.try_burn_from(&bob, &alice, &10), Err(Ok(TokenError::Paused)));
+    
+    // approve no se pausa
+    client.approve(&alice, &bob, &0, &0);
+    
+    client.unpause(&admin, &PauseTarget::All);
+    assert!(!client.is_paused(&PauseTarget::Transfer));
+    client.transfer(&alice, &bob, &10);
+    assert_eq!(client.balance(&bob), 10);
+}
+
+/// Test: pausar una sola clase de operación
+#[test]
+fn test_pause_single_operation() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &1000);
+    client.pause(&admin, &PauseTarget::Mint);
+    
+    assert!(client.is_paused(&PauseTarget::Mint));
+    assert!(!client.is_paused(&PauseTarget::All));
+    assert_eq!(client.try_mint(&admin, &alice, &10), Err(Ok(TokenError::Paused)));
+    
+    // El resto sigue operando
+    client.transfer(&alice, &bob, &100);
+    client.burn(&alice, &100);
+    assert_eq!(client.balance(&alice), 800);
+}
+
+/// Test: modo redeem only, el burn sigue funcionando
+#[test]
+fn test_redeem_only_mode() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &1000);
+    client.set_redeem_only(&admin);
+    
+    assert!(client.is_paused(&PauseTarget::Transfer));
+    assert!(client.is_paused(&PauseTarget::Mint));
+    assert!(!client.is_paused(&PauseTarget::Burn));
+    
+    assert_eq!(client.try_transfer(&alice, &bob, &10), Err(Ok(TokenError::Paused)));
+    assert_eq!(client.try_mint(&admin, &alice, &10), Err(Ok(TokenError::Paused)));
+    
+    client.burn(&alice, &400);
+    assert_eq!(client.balance(&alice), 600);
+    assert_eq!(client.total_supply(), 600);
+}
+
+/// Test: solo las cuentas con rol Pauser pueden pausar
+#[test]
+fn test_pause_requires_pauser_role() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let guardian = Address::generate(&env);
+    
+    assert_eq!(
+        client.try_pause(&guardian, &PauseTarget::All),
+        Err(Ok(TokenError::MissingRole))
+    );
+    
+    client.grant_role(&Role::Pauser, &guardian);
+    client.pause(&guardian, &PauseTarget::All);
+    assert_eq!(env.auths()[0].0, guardian);
+    assert!(client.is_paused(&PauseTarget::All));
+}
+
+/// Test: pausar y reanudar emite eventos
+#[test]
+fn test_pause_emits_events() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    
+    client.pause(&admin, &PauseTarget::Transfer);
+    let event = PausedEvent {
+        target: PauseTarget::Transfer,
+        caller: admin.clone(),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    
+    client.unpause(&admin, &PauseTarget::Transfer);
+    let event = UnpausedEvent {
+        target: PauseTarget::Transfer,
+        caller: admin,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+}
+
+// ============================================================
+// Congelamiento de cuentas (set_authorized / authorized)
+// ============================================================
+
+/// Test: una cuenta congelada no puede enviar ni recibir
+#[test]
+fn test_frozen_account_cannot_send_or_receive() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &1000);
+    client.mint(&admin, &bob, &1000);
+    assert!(client.authorized(&alice));
+    
+    client.set_authorized(&alice, &false);
+    assert_eq!(env.auths()[0].0, admin);
+    assert!(!client.authorized(&alice));
+    
+    // No puede enviar
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &10),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    assert_eq!(client.try_burn(&alice, &10), Err(Ok(TokenError::AccountFrozen)));
+    
+    // No puede recibir
+    assert_eq!(
+        client.try_transfer(&bob, &alice, &10),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    assert_eq!(
+        client.try_mint(&admin, &alice, &10),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    assert_eq!(client.balance(&alice), 1000);
+    
+    // Al descongelar vuelve a operar
+    client.set_authorized(&alice, &true);
+    client.transfer(&alice, &bob, &10);
+    assert_eq!(client.balance(&bob), 1010);
+}
+
+/// Test: el freeze aplica a transfer_from y burn_from
+/// 
+/// Un spender no puede mover ni quemar tokens de una cuenta congelada,
+/// ni enviarlos a una cuenta congelada
+#[test]
+fn test_frozen_account_blocks_allowance_operations() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &bob, &500, &(env.ledger().sequence() + 100));
+    
+    client.set_authorized(&charlie, &false);
+    assert_eq!(
+        client.try_transfer_from(&bob, &alice, &charlie, &10),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    
+    client.set_authorized(&alice, &false);
+    assert_eq!(
+        client.try_transfer_from(&bob, &alice, &bob, &10),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    assert_eq!(
+        client.try_burn_from(&bob, &alice, &10),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    assert_eq!(client.allowance(&alice, &bob), 500);
+}
+
+/// Test: set_authorized emite el evento para compliance
+#[test]
+fn test_set_authorized_emits_event() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    client.set_authorized(&alice, &false);
+    let event = SetAuthorizedEvent {
+        admin,
+        id: alice,
+        authorize: false,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+}
+
+// ============================================================================
+// Tope de supply
+// ============================================================================
+
+/// Helper: token con tope de supply configurado en initialize
+fn setup_capped(env: &Env, max_supply: i128) -> (TokenBDBClient<'_>, Address) {
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(env, "Builder Token"),
+        &String::from_str(env, "BDB"),
+        &7,
+        &Some(max_supply)
+    );
+    env.mock_all_auths();
+    
+    (client, admin)
+}
+
+/// Test: sin tope configurado, max_supply() devuelve None
+#[test]
+fn test_max_supply_none_by_default() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    
+    assert_eq!(client.max_supply(), None);
+}
+
+/// Test: mint hasta el tope funciona, superarlo falla con SupplyCapExceeded
+#[test]
+fn test_mint_respects_supply_cap() {
+    let env = Env::default();
+    let (client, admin) = setup_capped(&env, 1000);
+    let alice = Address::generate(&env);
+    
+    assert_eq!(client.max_supply(), Some(1000));
+    
+    client.mint(&admin, &alice, &600);
+    client.mint(&admin, &alice, &400);
+    assert_eq!(client.total_supply(), 1000);
+    
+    let result = client.try_mint(&admin, &alice, &1);
+    assert_eq!(result, Err(Ok(TokenError::SupplyCapExceeded)));
+    
+    // El intento fallido no modifica el estado
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.total_supply(), 1000);
+}
+
+/// Test: el burn libera espacio bajo el tope
+#[test]
+fn test_burn_frees_room_under_cap() {
+    let env = Env::default();
+    let (client, admin) = setup_capped(&env, 1000);
+    let alice = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &1000);
+    client.burn(&alice, &300);
+    client.mint(&admin, &alice, &300);
+    
+    assert_eq!(client.total_supply(), 1000);
+}
+
+/// Test: initialize rechaza un tope <= 0
+#[test]
+fn test_initialize_invalid_supply_cap() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    
+    let result = client.try_initialize(
+        &admin,
+        &String::from_str(&env, "Builder Token"),
+        &String::from_str(&env, "BDB"),
+        &7,
+        &Some(0)
+    );
+    assert_eq!(result, Err(Ok(TokenError::InvalidSupplyCap)));
+}
+
+/// Test: el tope se puede bajar pero nunca subir
+#[test]
+fn test_lower_max_supply() {
+    let env = Env::default();
+    let (client, admin) = setup_capped(&env, 1000);
+    let alice = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &500);
+    
+    client.lower_max_supply(&800);
+    assert_eq!(client.max_supply(), Some(800));
+    
+    // Subir (o repetir) el tope falla
+    assert_eq!(
+        client.try_lower_max_supply(&900),
+        Err(Ok(TokenError::InvalidSupplyCap))
+    );
+    assert_eq!(
+        client.try_lower_max_supply(&800),
+        Err(Ok(TokenError::InvalidSupplyCap))
+    );
+    
+    // No puede quedar por debajo del supply en circulación
+    assert_eq!(
+        client.try_lower_max_supply(&499),
+        Err(Ok(TokenError::InvalidSupplyCap))
+    );
+    
+    // Igual al supply actual sí es válido: no se puede mintear más
+    client.lower_max_supply(&500);
+    assert_eq!(
+        client.try_mint(&admin, &alice, &1),
+        Err(Ok(TokenError::SupplyCapExceeded))
+    );
+}
+
+/// Test: un token sin tope puede fijar uno más tarde
+#[test]
+fn test_lower_max_supply_sets_first_cap() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    
+    client.lower_max_supply(&5000);
+    
+    let event = MaxSupplyUpdatedEvent {
+        admin,
+        old_max_supply: None,
+        new_max_supply: 5000,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(client.max_supply(), Some(5000));
+}
+
+/// Test: solo el admin puede bajar el tope
+#[test]
+#[should_panic]
+fn test_lower_max_supply_requires_admin() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Builder Token"),
+        &String::from_str(&env, "BDB"),
+        &7,
+        &Some(1000)
+    );
+    
+    // Sin mock_all_auths: require_auth del admin falla
+    client.lower_max_supply(&500);
+}
+
+// ============================================================================
+// Upgrades
+// ============================================================================
+
+// Wasm de prueba para los tests de upgrade: v1 es un build del primer release
+// con upgrade() (esquema v1) y v3 es un build del código actual, así que los
+// tests recorren la migración real desde v1 hasta HEAD.
+// Regenerar v3 con: ../fixtures.sh build (test_fixtures_are_current falla si
+// quedó desactualizado)
+mod token_bdb_v1 {
+    soroban_sdk::contractimport!(file = "testdata/token_bdb_v1.wasm");
+}
+
+mod token_bdb_v3 {
+    soroban_sdk::contractimport!(file = "testdata/token_bdb_v3.wasm");
+}
+
+/// Huella de las fuentes de un token, calculada igual que fixtures.sh
+/// 
+/// sha256 de la ruta y el contenido de cada archivo, en orden de bytes:
+/// Cargo.toml y src/ (sin test.rs) del token y de token_core, y el
+/// Cargo.lock del token
+fn sources_fingerprint(env: &Env, token: &str, src: &str) -> std::string::String {
+    use std::{fs, path::Path, string::String, vec::Vec};
+    
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let mut files: Vec<String> = Vec::new();
+    for (manifest, dir) in [("token_core", "token_core/src"), (token, src)] {
+        files.push(std::format!("{manifest}/Cargo.toml"));
+        for entry in fs::read_dir(root.join(dir)).unwrap() {
+            let name = entry.unwrap().file_name().into_string().unwrap();
+            if name.ends_with(".rs") && name != "test.rs" {
+                files.push(std::format!("{dir}/{name}"));
+            }
+        }
+    }
+    files.push(std::format!("{token}/Cargo.lock"));
+    files.sort();
+    
+    let mut data = Vec::new();
+    for file in files {
+        data.extend_from_slice(file.as_bytes());
+        data.push(b'\n');
+        data.extend_from_slice(&fs::read(root.join(&file)).unwrap());
+    }
+    env.cost_estimate().budget().reset_unlimited();
+    let hash = env.crypto().sha256(&Bytes::from_slice(env, &data));
+    hash.to_array().iter().map(|byte| std::format!("{byte:02x}")).collect()
+}
+
+/// Test: todos los wasm de testdata corresponden a las fuentes actuales
+/// 
+/// Único chequeo en Rust de fixtures.sh check: recorre su lista FIXTURES,
+/// así que también cubre los wasm de airdrop, vesting, governor y factory
+#[test]
+fn test_fixtures_are_current() {
+    use std::{fs, path::Path, vec::Vec};
+    
+    let env = Env::default();
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let script = fs::read_to_string(root.join("fixtures.sh")).unwrap();
+    
+    let mut stale = Vec::new();
+    let mut checked = 0;
+    for line in script.lines() {
+        let Some((wasm, token)) = line.trim().trim_matches('"').split_once(".wasm:") else {
+            continue;
+        };
+        let src = match token {
+            "worx_token" => std::format!("{token}/contracts/src"),
+            _ => std::format!("{token}/src"),
+        };
+        let recorded = fs::read_to_string(root.join(std::format!("{wasm}.wasm.sha256")))
+            .unwrap_or_default();
+        if recorded.trim() != sources_fingerprint(&env, token, &src) {
+            stale.push(std::format!("{wasm}.wasm"));
+        }
+        checked += 1;
+    }
+    
+    assert_eq!(checked, 6);
+    assert!(stale.is_empty(), "desactualizados: {stale:?} (correr ../fixtures.sh build)");
+}
+
+/// Helper: despliega el wasm v1 ya inicializado
+fn setup_v1(env: &Env) -> (token_bdb_v1::Client<'_>, Address) {
+    let contract_id = env.register(token_bdb_v1::WASM, ());
+    let client = token_bdb_v1::Client::new(env, &contract_id);
+    let admin = Address::generate(env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(env, "Builder Token"),
+        &String::from_str(env, "BDB"),
+        &7,
+        &None
+    );
+    env.mock_all_auths();
+    
+    (client, admin)
+}
+
+/// Test: upgrade de v1 al código actual + migrate, de punta a punta
+/// 
+/// Balances, allowances y metadata sobreviven; el historial empieza en la
+/// migración y las listas de holders quedan deshabilitadas
+#[test]
+fn test_upgrade_preserves_balances_and_allowances() {
+    let env = Env::default();
+    let (v1, admin) = setup_v1(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    // Estado creado con el código v1
+    set_sequence(&env, 10);
+    v1.mint(&admin, &alice, &1000);
+    v1.transfer(&alice, &bob, &300);
+    v1.approve(&alice, &bob, &200, &1000);
+    assert_eq!(v1.version(), 1);
+    assert_eq!(v1.storage_version(), 1);
+    
+    // Subir el wasm actual y reemplazar el código
+    set_sequence(&env, 20);
+    let v3_hash = env.deployer().upload_contract_wasm(token_bdb_v3::WASM);
+    v1.upgrade(&v3_hash);
+    let event = UpgradedEvent {
+        admin: admin.clone(),
+        new_wasm_hash: v3_hash.clone(),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (v1.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    
+    let v3 = token_bdb_v3::Client::new(&env, &v1.address);
+    assert_eq!(v3.version(), CONTRACT_VERSION);
+    assert_eq!(v3.storage_version(), 1);
+    
+    // El storage no cambió con el upgrade
+    assert_eq!(v3.balance(&alice), 700);
+    assert_eq!(v3.balance(&bob), 300);
+    assert_eq!(v3.allowance(&alice, &bob), 200);
+    assert_eq!(v3.total_supply(), 1000);
+    assert_eq!(v3.admin(), Some(admin.clone()));
+    
+    // migrate aplica todos los pasos pendientes hasta el esquema actual
+    v3.migrate();
+    let event = MigratedEvent {
+        admin,
+        from_version: 1,
+        to_version: STORAGE_VERSION,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (v3.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(v3.storage_version(), STORAGE_VERSION);
+    assert_eq!(v3.symbol(), String::from_str(&env, "BDB"));
+    assert_eq!(v3.decimals(), 7);
+    
+    // El allowance migrado sigue siendo utilizable
+    set_sequence(&env, 30);
+    v3.transfer_from(&bob, &alice, &bob, &150);
+    assert_eq!(v3.balance(&bob), 450);
+    assert_eq!(v3.allowance(&alice, &bob), 50);
+    
+    // El historial arranca en la migración; las listas no se pueden reconstruir
+    set_sequence(&env, 40);
+    assert_eq!(
+        v3.try_balance_at(&alice, &15),
+        Err(Ok(token_bdb_v3::TokenError::CheckpointUnavailable))
+    );
+    assert_eq!(v3.balance_at(&alice, &20), 700);
+    assert_eq!(v3.balance_at(&alice, &30), 550);
+    assert_eq!(v3.total_supply_at(&25), 1000);
+    assert_eq!(v3.try_holder_count(), Err(Ok(token_bdb_v3::TokenError::ListsIncomplete)));
+}
+
+/// Test: migrate corre una sola vez por cambio de esquema
+#[test]
+fn test_migrate_runs_once() {
+    let env = Env::default();
+    let (v1, _admin) = setup_v1(&env);
+    
+    let v3_hash = env.deployer().upload_contract_wasm(token_bdb_v3::WASM);
+    v1.upgrade(&v3_hash);
+    
+    let v3 = token_bdb_v3::Client::new(&env, &v1.address);
+    v3.migrate();
+    assert_eq!(
+        v3.try_migrate(),
+        Err(Ok(token_bdb_v3::TokenError::MigrationNotNeeded))
+    );
+}
+
+/// Test: un contrato recién inicializado ya está en el esquema actual
+#[test]
+fn test_migrate_not_needed_when_up_to_date() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    
+    assert_eq!(client.version(), CONTRACT_VERSION);
+    assert_eq!(client.storage_version(), STORAGE_VERSION);
+    assert_eq!(client.try_migrate(), Err(Ok(TokenError::MigrationNotNeeded)));
+}
+
+/// Test: solo el admin puede hacer upgrade
+#[test]
+#[should_panic]
+fn test_upgrade_requires_admin() {
+    let env = Env::default();
+    let contract_id = env.register(token_bdb_v1::WASM, ());
+    let client = token_bdb_v1::Client::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Builder Token"),
+        &String::from_str(&env, "BDB"),
+        &7,
+        &None
+    );
+    let v3_hash = env.deployer().upload_contract_wasm(token_bdb_v3::WASM);
+    
+    // Sin mock_all_auths: require_auth del admin falla
+    client.upgrade(&v3_hash);
+}
+
+/// Test: con el admin renunciado el código queda inmutable
+#[test]
+fn test_upgrade_after_renounce_fails() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    
+    client.renounce_admin();
+    assert_eq!(
+        client.try_upgrade(&BytesN::from_array(&env, &[0; 32])),
+        Err(Ok(TokenError::AdminRenounced))
+    );
+}
+
+// ============================================================================
+// Operaciones en lote
+// ============================================================================
+
+/// Helper: lote de `n` destinatarios nuevos, cada uno con `amount`
+fn batch_recipients(env: &Env, n: u32, amount: i128) -> soroban_sdk::Vec<(Address, i128)> {
+    let mut recipients = soroban_sdk::Vec::new(env);
+    for _ in 0..n {
+        recipients.push_back((Address::generate(env), amount));
+    }
+    recipients
+}
+
+/// Test: transfer_batch reparte a todos con una sola firma y un evento por destinatario
+#[test]
+fn test_transfer_batch() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &1000);
+    client.transfer_batch(&alice, &vec![&env, (bob.clone(), 300), (charlie.clone(), 200)]);
+    
+    // Una sola autorización: la de alice
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, alice);
+    
+    let to_bob = TransferEvent {
+        from: alice.clone(),
+        to: bob.clone(),
+        amount: 300,
+        fee: 0,
+        net_amount: 300,
+        from_new_balance: 700,
+        to_new_balance: 300,
+        memo: None,
+    };
+    let to_charlie = TransferEvent {
+        from: alice.clone(),
+        to: charlie.clone(),
+        amount: 200,
+        fee: 0,
+        net_amount: 200,
+        from_new_balance: 500,
+        to_new_balance: 200,
+        memo: None,
+    };
+    let std_to_bob = Sep41TransferEvent { from: alice.clone(), to: bob.clone(), amount: 300 };
+    let std_to_charlie = Sep41TransferEvent { from: alice.clone(), to: charlie.clone(), amount: 200 };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (client.address.clone(), to_bob.topics(&env), to_bob.data(&env)),
+            (client.address.clone(), std_to_bob.topics(&env), std_to_bob.data(&env)),
+            (client.address.clone(), to_charlie.topics(&env), to_charlie.data(&env)),
+            (client.address.clone(), std_to_charlie.topics(&env), std_to_charlie.data(&env)),
+        ]
+    );
+    
+    assert_eq!(
+        client.balances(&vec![&env, alice.clone(), bob.clone(), charlie.clone()]),
+        vec![&env, 500, 300, 200]
+    );
+    assert_eq!(client.total_supply(), 1000);
+}
+
+/// Test: transfer_batch es todo-o-nada
+#[test]
+fn test_transfer_batch_all_or_nothing() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &1000);
+    
+    // El total (1100) supera el balance aunque cada monto por separado no
+    assert_eq!(
+        client.try_transfer_batch(&alice, &vec![&env, (bob.clone(), 600), (charlie.clone(), 500)]),
+        Err(Ok(TokenError::InsufficientBalance))
+    );
+    
+    // Un destinatario inválido al final revierte todo el lote
+    assert_eq!(
+        client.try_transfer_batch(&alice, &vec![&env, (bob.clone(), 100), (charlie.clone(), 0)]),
+        Err(Ok(TokenError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_transfer_batch(&alice, &vec![&env, (bob.clone(), 100), (alice.clone(), 100)]),
+        Err(Ok(TokenError::InvalidRecipient))
+    );
+    client.set_authorized(&charlie, &false);
+    assert_eq!(
+        client.try_transfer_batch(&alice, &vec![&env, (bob.clone(), 100), (charlie.clone(), 100)]),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    
+    assert_eq!(
+        client.balances(&vec![&env, alice.clone(), bob.clone(), charlie.clone()]),
+        vec![&env, 1000, 0, 0]
+    );
+}
+
+/// Test: un mismo destinatario repetido acumula los montos
+#[test]
+fn test_transfer_batch_repeated_recipient() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &1000);
+    client.transfer_batch(&alice, &vec![&env, (bob.clone(), 400), (bob.clone(), 600)]);
+    
+    assert_eq!(client.balance(&bob), 1000);
+    assert_eq!(client.balance(&alice), 0);
+}
+
+/// Test: lote vacío o con más de MAX_BATCH_SIZE destinatarios
+#[test]
+fn test_batch_size_limits() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &1000);
+    
+    assert_eq!(
+        client.try_transfer_batch(&alice, &soroban_sdk::Vec::new(&env)),
+        Err(Ok(TokenError::InvalidBatchSize))
+    );
+    assert_eq!(
+        client.try_transfer_batch(&alice, &batch_recipients(&env, MAX_BATCH_SIZE + 1, 1)),
+        Err(Ok(TokenError::InvalidBatchSize))
+    );
+    assert_eq!(
+        client.try_mint_batch(&admin, &batch_recipients(&env, MAX_BATCH_SIZE + 1, 1)),
+        Err(Ok(TokenError::InvalidBatchSize))
+    );
+}
+
+/// Test: mint_batch acredita a todos y actualiza el supply una vez
+#[test]
+fn test_mint_batch() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint_batch(&admin, &vec![&env, (alice.clone(), 100), (bob.clone(), 250)]);
+    
+    let to_alice = MintEvent {
+        to: alice.clone(),
+        amount: 100,
+        new_balance: 100,
+        new_supply: 100,
+        memo: None,
+    };
+    let to_bob = MintEvent {
+        to: bob.clone(),
+        amount: 250,
+        new_balance: 250,
+        new_supply: 350,
+        memo: None,
+    };
+    let std_to_alice = Sep41MintEvent { to: alice.clone(), amount: 100 };
+    let std_to_bob = Sep41MintEvent { to: bob.clone(), amount: 250 };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (client.address.clone(), to_alice.topics(&env), to_alice.data(&env)),
+            (client.address.clone(), std_to_alice.topics(&env), std_to_alice.data(&env)),
+            (client.address.clone(), to_bob.topics(&env), to_bob.data(&env)),
+            (client.address.clone(), std_to_bob.topics(&env), std_to_bob.data(&env)),
+        ]
+    );
+    
+    assert_eq!(client.balances(&vec![&env, alice, bob]), vec![&env, 100, 250]);
+    assert_eq!(client.total_supply(), 350);
+}
+
+/// Test: mint_batch verifica el tope de supply contra el total del lote
+#[test]
+fn test_mint_batch_respects_supply_cap() {
+    let env = Env::default();
+    let (client, admin) = setup_capped(&env, 1000);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    assert_eq!(
+        client.try_mint_batch(&admin, &vec![&env, (alice.clone(), 600), (bob.clone(), 401)]),
+        Err(Ok(TokenError::SupplyCapExceeded))
+    );
+    assert_eq!(client.total_supply(), 0);
+    
+    client.mint_batch(&admin, &vec![&env, (alice, 600), (bob, 400)]);
+    assert_eq!(client.total_supply(), 1000);
+}
+
+/// Test: un lote de MAX_BATCH_SIZE destinatarios entra en el presupuesto por defecto
+/// 
+/// El Env de test aplica los límites de CPU y memoria por defecto de Soroban;
+/// si el lote no entrara, la invocación fallaría. Los costos medidos con el
+/// contrato nativo subestiman los del wasm, por eso se exige un margen amplio.
+#[test]
+fn test_batch_fits_default_budget() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    let recipients = batch_recipients(&env, MAX_BATCH_SIZE, 10);
+    client.mint(&admin, &alice, &(10 * MAX_BATCH_SIZE as i128));
+    
+    client.transfer_batch(&alice, &recipients);
+    let resources = env.cost_estimate().resources();
+    let cpu = env.cost_estimate().budget().cpu_instruction_cost();
+    // Balance, checkpoints y lugar en la lista de holders (dos entries) por
+    // destinatario nuevo; balance y checkpoints del sender, que queda en 0 y
+    // cede su lugar al último holder (dos entries más); la instance (cantidad
+    // de holders) y el nonce de su firma
+    assert_eq!(resources.write_entries, 4 * MAX_BATCH_SIZE + 6);
+    assert!(cpu < 100_000_000 / 4, "transfer_batch: {cpu} instrucciones");
+    
+    client.mint_batch(&admin, &recipients);
+    let resources = env.cost_estimate().resources();
+    let cpu = env.cost_estimate().budget().cpu_instruction_cost();
+    // Balance y checkpoints por destinatario (ya son holders), más la instance
+    // (total supply), los checkpoints del supply y el nonce
+    assert_eq!(resources.write_entries, 2 * MAX_BATCH_SIZE + 3);
+    assert!(cpu < 100_000_000 / 4, "mint_batch: {cpu} instrucciones");
+}
+
+/// Test: balances() devuelve 0 para cuentas sin tokens
+#[test]
+fn test_balances_query() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &42);
+    
+    assert_eq!(client.balances(&vec![&env, bob, alice]), vec![&env, 0, 42]);
+    assert_eq!(client.balances(&soroban_sdk::Vec::new(&env)), soroban_sdk::Vec::new(&env));
+}
+
+// ============================================================
+// Checkpoints: balances y supply históricos
+// ============================================================
+
+/// Helper: fija el número de secuencia del ledger
+fn set_sequence(env: &Env, sequence: u32) {
+    env.ledger().with_mut(|li| {
+        li.sequence_number = sequence;
+    });
+}
+
+/// Test: balance_at y total_supply_at reconstruyen el historial
+#[test]
+fn test_balance_at_and_total_supply_at() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    
+    set_sequence(&env, 10);
+    client.mint(&admin, &alice, &100);
+    set_sequence(&env, 20);
+    client.transfer(&alice, &bob, &30);
+    set_sequence(&env, 30);
+    client.burn(&alice, &20);
+    set_sequence(&env, 40);
+    client.approve(&bob, &carol, &10, &1000);
+    client.transfer_from(&carol, &bob, &carol, &10);
+    set_sequence(&env, 50);
+    
+    assert_eq!(client.balance_at(&alice, &9), 0);
+    assert_eq!(client.balance_at(&alice, &10), 100);
+    assert_eq!(client.balance_at(&alice, &19), 100);
+    assert_eq!(client.balance_at(&alice, &20), 70);
+    assert_eq!(client.balance_at(&alice, &30), 50);
+    assert_eq!(client.balance_at(&bob, &19), 0);
+    assert_eq!(client.balance_at(&bob, &39), 30);
+    assert_eq!(client.balance_at(&bob, &40), 20);
+    assert_eq!(client.balance_at(&carol, &40), 10);
+    
+    assert_eq!(client.total_supply_at(&9), 0);
+    assert_eq!(client.total_supply_at(&29), 100);
+    assert_eq!(client.total_supply_at(&49), 80);
+    
+    // Una cuenta sin movimientos vale 0 en cualquier ledger
+    assert_eq!(client.balance_at(&Address::generate(&env), &25), 0);
+}
+
+/// Test: solo se pueden consultar ledgers ya cerrados
+#[test]
+fn test_balance_at_requires_past_ledger() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    set_sequence(&env, 10);
+    client.mint(&admin, &alice, &100);
+    
+    assert_eq!(client.try_balance_at(&alice, &10), Err(Ok(TokenError::LedgerNotFinalized)));
+    assert_eq!(client.try_total_supply_at(&11), Err(Ok(TokenError::LedgerNotFinalized)));
+    assert_eq!(client.balance_at(&alice, &9), 0);
+}
+
+/// Test: varios cambios en un mismo ledger dejan un solo checkpoint
+#[test]
+fn test_checkpoints_same_ledger() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    set_sequence(&env, 10);
+    client.mint(&admin, &alice, &100);
+    client.transfer(&alice, &bob, &10);
+    client.transfer_batch(&alice, &vec![&env, (bob.clone(), 5)]);
+    set_sequence(&env, 11);
+    
+    assert_eq!(client.balance_at(&alice, &10), 85);
+    assert_eq!(client.balance_at(&bob, &10), 15);
+    
+    let history = env.as_contract(&client.address, || {
+        env.storage().persistent()
+            .get::<_, storage::History>(&DataKey::BalanceHistory(alice.clone()))
+            .unwrap()
+    });
+    assert_eq!(history.recent, vec![&env, storage::Checkpoint { ledger: 10, value: 85 }]);
+}
+
+/// Test: un contrato sin historial (anterior a los checkpoints) no inventa 0
+/// 
+/// Después de migrar al esquema v3 los ledgers previos fallan con
+/// CheckpointUnavailable y los balances viejos se registran en su primer cambio
+#[test]
+fn test_migrate_starts_history() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    // Simular balances creados por un código que no guardaba checkpoints
+    set_sequence(&env, 10);
+    client.mint(&admin, &alice, &100);
+    env.as_contract(&client.address, || {
+        env.storage().persistent().remove(&DataKey::BalanceHistory(alice.clone()));
+        env.storage().persistent().remove(&DataKey::SupplyHistory);
+        env.storage().instance().set(&DataKey::StorageVersion, &2u32);
+    });
+    set_sequence(&env, 15);
+    assert_eq!(client.try_balance_at(&alice, &12), Err(Ok(TokenError::CheckpointUnavailable)));
+    
+    set_sequence(&env, 20);
+    client.migrate();
+    assert_eq!(client.storage_version(), STORAGE_VERSION);
+    
+    set_sequence(&env, 30);
+    client.transfer(&alice, &bob, &40);
+    set_sequence(&env, 40);
+    
+    // Antes de la migración no hay datos
+    assert_eq!(client.try_balance_at(&alice, &19), Err(Ok(TokenError::CheckpointUnavailable)));
+    assert_eq!(client.try_balance_at(&bob, &19), Err(Ok(TokenError::CheckpointUnavailable)));
+    assert_eq!(client.try_total_supply_at(&19), Err(Ok(TokenError::CheckpointUnavailable)));
+    
+    // Desde la migración, el balance previo quedó registrado
+    assert_eq!(client.balance_at(&alice, &20), 100);
+    assert_eq!(client.balance_at(&alice, &29), 100);
+    assert_eq!(client.balance_at(&alice, &30), 60);
+    assert_eq!(client.balance_at(&bob, &29), 0);
+    assert_eq!(client.balance_at(&bob, &30), 40);
+    assert_eq!(client.total_supply_at(&25), 100);
+}
+
+/// Test: el historial no se poda; las páginas llenas se archivan
+#[test]
+fn test_checkpoints_paged() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    // Un mint por ledger: tres páginas archivadas y 4 checkpoints en curso
+    let changes = 3 * storage::CHECKPOINTS_PER_PAGE + 4;
+    for sequence in 1..=changes {
+        set_sequence(&env, sequence);
+        client.mint(&admin, &alice, &1);
+    }
+    set_sequence(&env, changes + 1);
+    
+    let history = env.as_contract(&client.address, || {
+        env.storage().persistent()
+            .get::<_, storage::History>(&DataKey::BalanceHistory(alice.clone()))
+            .unwrap()
+    });
+    assert_eq!(history.pages, 3);
+    assert_eq!(history.recent.len(), 4);
+    
+    // Todos los ledgers siguen cubiertos, en cualquier página
+    assert_eq!(client.balance_at(&alice, &0), 0);
+    for ledger in [1, 5, 32, 33, 64, 65, 96, 97, changes] {
+        assert_eq!(client.balance_at(&alice, &ledger), ledger as i128);
+        assert_eq!(client.total_supply_at(&ledger), ledger as i128);
+    }
+}
+
+// ============================================================
+// Delegación de votos
+// ============================================================
+
+/// Test: delegate mueve el balance al delegado y emite eventos
+#[test]
+fn test_delegate_and_get_votes() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &100);
+    assert_eq!(client.get_votes(&alice), 0);
+    assert_eq!(client.delegates(&alice), None);
+    
+    client.delegate(&alice, &alice);
+    assert_eq!(env.auths()[0].0, alice);
+    
+    let votes_event = DelegateVotesChangedEvent {
+        delegate: alice.clone(),
+        previous_votes: 0,
+        new_votes: 100,
+    };
+    let delegate_event = DelegateChangedEvent {
+        delegator: alice.clone(),
+        from_delegate: None,
+        to_delegate: alice.clone(),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (client.address.clone(), votes_event.topics(&env), votes_event.data(&env)),
+            (client.address.clone(), delegate_event.topics(&env), delegate_event.data(&env)),
+        ]
+    );
+    assert_eq!(client.get_votes(&alice), 100);
+    assert_eq!(client.delegates(&alice), Some(alice.clone()));
+}
+
+/// Test: los votos siguen cada cambio de balance
+#[test]
+fn test_votes_follow_balance_changes() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let dave = Address::generate(&env);
+    
+    client.delegate(&alice, &dave);
+    client.delegate(&bob, &bob);
+    
+    client.mint(&admin, &alice, &100);
+    assert_eq!(client.get_votes(&dave), 100);
+    
+    client.transfer(&alice, &bob, &30);
+    assert_eq!(client.get_votes(&dave), 70);
+    assert_eq!(client.get_votes(&bob), 30);
+    
+    client.burn(&bob, &10);
+    assert_eq!(client.get_votes(&bob), 20);
+    
+    client.approve(&alice, &dave, &20, &1000);
+    client.transfer_from(&dave, &alice, &bob, &10);
+    client.burn_from(&dave, &alice, &10);
+    assert_eq!(client.get_votes(&dave), 50);
+    assert_eq!(client.get_votes(&bob), 30);
+    
+    client.transfer_batch(&bob, &vec![&env, (alice.clone(), 5)]);
+    client.mint_batch(&admin, &vec![&env, (bob.clone(), 7)]);
+    assert_eq!(client.get_votes(&dave), 55);
+    assert_eq!(client.get_votes(&bob), 32);
+    
+    // Re-delegar mueve todo el balance de alice de dave a bob
+    client.delegate(&alice, &bob);
+    assert_eq!(client.get_votes(&dave), 0);
+    assert_eq!(client.get_votes(&bob), 87);
+    assert_eq!(client.get_votes(&bob), client.total_supply());
+}
+
+/// Test: get_past_votes consulta los votos en ledgers cerrados
+#[test]
+fn test_get_past_votes() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    set_sequence(&env, 10);
+    client.mint(&admin, &alice, &100);
+    client.delegate(&alice, &alice);
+    set_sequence(&env, 20);
+    client.transfer(&alice, &bob, &40);
+    set_sequence(&env, 30);
+    client.delegate(&alice, &bob);
+    set_sequence(&env, 40);
+    
+    assert_eq!(client.get_past_votes(&alice, &9), 0);
+    assert_eq!(client.get_past_votes(&alice, &10), 100);
+    assert_eq!(client.get_past_votes(&alice, &20), 60);
+    assert_eq!(client.get_past_votes(&alice, &30), 0);
+    assert_eq!(client.get_past_votes(&bob, &29), 0);
+    assert_eq!(client.get_past_votes(&bob, &30), 60);
+    assert_eq!(
+        client.try_get_past_votes(&bob, &40),
+        Err(Ok(TokenError::LedgerNotFinalized))
+    );
+}
+
+/// Test: mover la cuenta del delegador en cada ledger no borra un snapshot
+/// 
+/// Antes el historial se podaba a 50 checkpoints: 50 transferencias de
+/// polvo, una por ledger, dejaban sin votos al delegado en una votación
+#[test]
+fn test_past_votes_survive_dust_transfers() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    
+    set_sequence(&env, 10);
+    client.mint(&admin, &alice, &1000);
+    client.mint(&admin, &attacker, &200);
+    client.delegate(&alice, &delegate);
+    let snapshot = 10;
+    
+    // 60 transferencias de 1 stroop y 60 burns, uno por ledger
+    for sequence in 11..=70 {
+        set_sequence(&env, sequence);
+        client.transfer(&attacker, &alice, &1);
+        client.burn(&attacker, &1);
+    }
+    set_sequence(&env, 71);
+    
+    assert_eq!(client.get_past_votes(&delegate, &snapshot), 1000);
+    assert_eq!(client.balance_at(&alice, &snapshot), 1000);
+    assert_eq!(client.total_supply_at(&snapshot), 1200);
+    assert_eq!(client.get_votes(&delegate), 1060);
+    assert_eq!(client.total_supply(), 1140);
+}
+
+// ============================================================
+// Permits firmados (ed25519)
+// ============================================================
+
+/// Helper: clave ed25519 determinística para tests
+fn permit_signer(seed: u8) -> ed25519_dalek::SigningKey {
+    ed25519_dalek::SigningKey::from_bytes(&[seed; 32])
+}
+
+/// Helper: registra la clave de `owner` en el token
+fn register_signer(env: &Env, client: &TokenBDBClient, owner: &Address, signer: &ed25519_dalek::SigningKey) {
+    client.register_permit_key(owner, &BytesN::from_array(env, &signer.verifying_key().to_bytes()));
+}
+
+/// Helper: firma off-chain el permit como lo haría la wallet del owner
+fn sign_permit(
+    client: &TokenBDBClient,
+    signer: &ed25519_dalek::SigningKey,
+    owner: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+    nonce: u64,
+) -> BytesN<64> {
+    use ed25519_dalek::Signer;
+    
+    let env = &client.env;
+    let message = env.as_contract(&client.address, || {
+        permit_message(env, owner, spender, amount, expiration_ledger, nonce)
+    });
+    let message: std::vec::Vec<u8> = message.iter().collect();
+    BytesN::from_array(env, &signer.sign(&message).to_bytes())
+}
+
+/// Test: un relayer aplica el approve firmado por el owner
+#[test]
+fn test_permit() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let signer = permit_signer(1);
+    
+    client.mint(&admin, &owner, &1000);
+    register_signer(&env, &client, &owner, &signer);
+    assert_eq!(client.permit_nonce(&owner), 0);
+    
+    let signature = sign_permit(&client, &signer, &owner, &spender, 300, 1000, 0);
+    client.permit(&owner, &spender, &300, &1000, &0, &signature);
+    
+    // El permit no pide la firma Soroban del owner
+    assert!(env.auths().is_empty());
+    let event = ApproveEvent {
+        from: owner.clone(),
+        spender: spender.clone(),
+        old_allowance: 0,
+        new_allowance: 300,
+        expiration_ledger: 1000,
+    };
+    let std_event = Sep41ApproveEvent {
+        from: owner.clone(),
+        spender: spender.clone(),
+        amount: 300,
+        expiration_ledger: 1000,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (client.address.clone(), event.topics(&env), event.data(&env)),
+            (client.address.clone(), std_event.topics(&env), std_event.data(&env)),
+        ]
+    );
+    assert_eq!(client.allowance(&owner, &spender), 300);
+    assert_eq!(client.permit_nonce(&owner), 1);
+    
+    client.transfer_from(&spender, &owner, &spender, &300);
+    assert_eq!(client.balance(&spender), 300);
+}
+
+/// Test: la misma firma no se puede reenviar (replay)
+#[test]
+fn test_permit_replay_fails() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let signer = permit_signer(1);
+    register_signer(&env, &client, &owner, &signer);
+    
+    let signature = sign_permit(&client, &signer, &owner, &spender, 300, 1000, 0);
+    client.permit(&owner, &spender, &300, &1000, &0, &signature);
+    
+    // Reenviar el permit después de que el spender gastó el allowance
+    assert_eq!(
+        client.try_permit(&owner, &spender, &300, &1000, &0, &signature),
+        Err(Ok(TokenError::InvalidNonce))
+    );
+    // Un nonce futuro tampoco se acepta
+    let future = sign_permit(&client, &signer, &owner, &spender, 300, 1000, 5);
+    assert_eq!(
+        client.try_permit(&owner, &spender, &300, &1000, &5, &future),
+        Err(Ok(TokenError::InvalidNonce))
+    );
+}
+
+/// Test: el último nonce posible no se consume con overflow
+#[test]
+fn test_permit_nonce_overflow() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let signer = permit_signer(1);
+    register_signer(&env, &client, &owner, &signer);
+    env.as_contract(&client.address, || storage::write_permit_nonce(&env, &owner, u64::MAX));
+    
+    let signature = sign_permit(&client, &signer, &owner, &spender, 300, 1000, u64::MAX);
+    assert_eq!(
+        client.try_permit(&owner, &spender, &300, &1000, &u64::MAX, &signature),
+        Err(Ok(TokenError::OverflowError))
+    );
+    assert_eq!(client.permit_nonce(&owner), u64::MAX);
+    assert_eq!(client.allowance(&owner, &spender), 0);
+}
+
+/// Test: un permit vencido no se acepta
+#[test]
+fn test_permit_expired_fails() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let signer = permit_signer(1);
+    register_signer(&env, &client, &owner, &signer);
+    
+    let signature = sign_permit(&client, &signer, &owner, &spender, 300, 100, 0);
+    set_sequence(&env, 101);
+    assert_eq!(
+        client.try_permit(&owner, &spender, &300, &100, &0, &signature),
+        Err(Ok(TokenError::PermitExpired))
+    );
+    // Cambiar la expiración invalida la firma (error del host, no TokenError)
+    assert!(matches!(
+        client.try_permit(&owner, &spender, &300, &200, &0, &signature),
+        Err(Err(_))
+    ));
+    assert_eq!(client.permit_nonce(&owner), 0);
+}
+
+/// Test: firmas de otra clave, otro contrato o parámetros alterados fallan
+#[test]
+fn test_permit_invalid_signature_fails() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let (other_token, _other_admin) = setup_admin(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let signer = permit_signer(1);
+    
+    let signature = sign_permit(&client, &signer, &owner, &spender, 300, 1000, 0);
+    assert_eq!(
+        client.try_permit(&owner, &spender, &300, &1000, &0, &signature),
+        Err(Ok(TokenError::PermitKeyNotRegistered))
+    );
+    
+    register_signer(&env, &client, &owner, &signer);
+    register_signer(&env, &other_token, &owner, &signer);
+    
+    // Una firma que no verifica aborta con un error del host, no con un TokenError
+    // Monto alterado por el relayer
+    assert!(matches!(
+        client.try_permit(&owner, &spender, &999, &1000, &0, &signature),
+        Err(Err(_))
+    ));
+    // Firma de una clave que no es la registrada
+    let forged = sign_permit(&client, &permit_signer(2), &owner, &spender, 300, 1000, 0);
+    assert!(matches!(
+        client.try_permit(&owner, &spender, &300, &1000, &0, &forged),
+        Err(Err(_))
+    ));
+    // Firma válida para este token, enviada a otro token (separación de dominio)
+    assert!(matches!(
+        other_token.try_permit(&owner, &spender, &300, &1000, &0, &signature),
+        Err(Err(_))
+    ));
+    assert_eq!(other_token.allowance(&owner, &spender), 0);
+    
+    client.permit(&owner, &spender, &300, &1000, &0, &signature);
+    assert_eq!(client.allowance(&owner, &spender), 300);
+}
+
+/// Test: una firma falsificada aborta la invocación (trap del host)
+/// 
+/// Los errores que se detectan antes de verificar (nonce, plazo, clave)
+/// sí llegan como TokenError; ver los tests anteriores
+#[test]
+#[should_panic]
+fn test_permit_forged_signature_traps() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    register_signer(&env, &client, &owner, &permit_signer(1));
+    
+    let forged = sign_permit(&client, &permit_signer(2), &owner, &spender, 300, 1000, 0);
+    client.permit(&owner, &spender, &300, &1000, &0, &forged);
+}
+
+/// Test: registrar la clave requiere la firma del owner
+#[test]
+fn test_register_permit_key_requires_owner() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let owner = Address::generate(&env);
+    let signer = permit_signer(1);
+    
+    register_signer(&env, &client, &owner, &signer);
+    assert_eq!(env.auths()[0].0, owner);
+    assert_eq!(
+        client.permit_key(&owner),
+        Some(BytesN::from_array(&env, &signer.verifying_key().to_bytes()))
+    );
+}
+
+// ============================================================
+// Metadatos extendidos
+// ============================================================
+
+/// Helper: metadatos completos de ejemplo
+fn sample_metadata(env: &Env) -> TokenMetadata {
+    let mut extra = Map::new(env);
+    extra.set(String::from_str(env, "website"), String::from_str(env, "https://example.com"));
+    
+    TokenMetadata {
+        name: String::from_str(env, "Builder Token v2"),
+        symbol: String::from_str(env, "BDB"),
+        decimals: 7,
+        description: String::from_str(env, "Token de la comunidad"),
+        icon_url: String::from_str(env, "https://example.com/icon.png"),
+        home_domain: String::from_str(env, "example.com"),
+        extra,
+    }
+}
+
+/// Test: initialize guarda los metadatos con los campos nuevos vacíos
+#[test]
+fn test_metadata_after_initialize() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    
+    let metadata = client.metadata();
+    assert_eq!(metadata.name, String::from_str(&env, "Builder Token"));
+    assert_eq!(metadata.symbol, String::from_str(&env, "BDB"));
+    assert_eq!(metadata.decimals, 7);
+    assert!(metadata.description.is_empty());
+    assert!(metadata.extra.is_empty());
+}
+
+/// Test: update_metadata reemplaza los metadatos y emite evento
+#[test]
+fn test_update_metadata() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let metadata = sample_metadata(&env);
+    
+    client.update_metadata(&admin, &metadata);
+    assert_eq!(env.auths()[0].0, admin);
+    
+    let event = MetadataUpdatedEvent {
+        caller: admin.clone(),
+        metadata: metadata.clone(),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(client.metadata(), metadata);
+    assert_eq!(client.name(), String::from_str(&env, "Builder Token v2"));
+}
+
+/// Test: update_metadata vuelve a validar los límites
+#[test]
+fn test_update_metadata_validations() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let metadata = sample_metadata(&env);
+    
+    let long_name = String::from_str(&env, &"a".repeat(MAX_NAME_LENGTH as usize + 1));
+    let long_symbol = String::from_str(&env, &"S".repeat(MAX_SYMBOL_LENGTH as usize + 1));
+    let long_url = String::from_str(&env, &"u".repeat(MAX_URL_LENGTH as usize + 1));
+    let mut too_many = Map::new(&env);
+    for i in 0..=MAX_METADATA_ENTRIES {
+        too_many.set(String::from_str(&env, &std::format!("key{i}")), String::from_str(&env, "v"));
+    }
+    
+    let invalid = [
+        TokenMetadata { name: String::from_str(&env, ""), ..metadata.clone() },
+        TokenMetadata { name: long_name, ..metadata.clone() },
+        TokenMetadata { symbol: long_symbol, ..metadata.clone() },
+        TokenMetadata { icon_url: long_url.clone(), ..metadata.clone() },
+        TokenMetadata { home_domain: long_url, ..metadata.clone() },
+        TokenMetadata { extra: too_many, ..metadata.clone() },
+    ];
+    for bad in invalid {
+        assert_eq!(client.try_update_metadata(&admin, &bad), Err(Ok(TokenError::InvalidMetadata)));
+    }
+    
+    // Los decimales no se pueden cambiar
+    let other_decimals = TokenMetadata { decimals: 6, ..metadata };
+    assert_eq!(client.try_update_metadata(&admin, &other_decimals), Err(Ok(TokenError::InvalidDecimals)));
+}
+
+/// Test: migrate pasa los metadatos del esquema v1 a `Metadata`
+#[test]
+fn test_migrate_metadata_from_v1_layout() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    
+    // Simular el storage de un contrato inicializado con el esquema v1
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&DataKey::Metadata);
+        env.storage().instance().set(&DataKey::TokenName, &String::from_str(&env, "Builder Token"));
+        env.storage().instance().set(&DataKey::TokenSymbol, &String::from_str(&env, "BDB"));
+        env.storage().instance().set(&DataKey::Decimals, &7u32);
+        env.storage().instance().set(&DataKey::StorageVersion, &1u32);
+    });
+    
+    // Antes de migrar los getters siguen funcionando
+    assert_eq!(client.symbol(), String::from_str(&env, "BDB"));
+    
+    client.migrate();
+    assert_eq!(client.storage_version(), STORAGE_VERSION);
+    assert_eq!(client.decimals(), 7);
+    assert_eq!(client.metadata().name, String::from_str(&env, "Builder Token"));
+    env.as_contract(&client.address, || {
+        assert!(env.storage().instance().has(&DataKey::Metadata));
+        assert!(!env.storage().instance().has(&DataKey::TokenName));
+        assert!(!env.storage().instance().has(&DataKey::TokenSymbol));
+        assert!(!env.storage().instance().has(&DataKey::Decimals));
+    });
+}
+
+/// Test: update_metadata requiere el rol MetadataManager
+#[test]
+fn test_update_metadata_requires_role() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let manager = Address::generate(&env);
+    
+    assert_eq!(
+        client.try_update_metadata(&manager, &sample_metadata(&env)),
+        Err(Ok(TokenError::MissingRole))
+    );
+    
+    client.grant_role(&Role::MetadataManager, &manager);
+    client.update_metadata(&manager, &sample_metadata(&env));
+    assert_eq!(client.metadata(), sample_metadata(&env));
+}
+
+// ============================================================
+// Eventos: detallados + estándar SEP-41 en cada método que cambia estado
+// ============================================================
+
+/// Helper: entrada de `env.events().all()` para `event` publicado por `client`
+fn emitted(
+    env: &Env,
+    client: &TokenBDBClient,
+    event: &impl Event
+) -> (Address, soroban_sdk::Vec<soroban_sdk::Val>, soroban_sdk::Val) {
+    (client.address.clone(), event.topics(env), event.data(env))
+}
+
+/// Test: initialize emite InitEvent
+#[test]
+fn test_initialize_emits_event() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    
+    // setup_admin ya inicializó: la última invocación fue initialize
+    let event = InitEvent {
+        admin,
+        name: String::from_str(&env, "Builder Token"),
+        symbol: String::from_str(&env, "BDB"),
+        decimals: 7,
+    };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+}
+
+/// Test: mint emite MintEvent y el `mint` estándar
+#[test]
+fn test_mint_emits_events() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &1000);
+    
+    let detailed = MintEvent {
+        to: alice.clone(),
+        amount: 1000,
+        new_balance: 1000,
+        new_supply: 1000,
+        memo: None,
+    };
+    let standard = Sep41MintEvent { to: alice, amount: 1000 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: burn emite BurnEvent y el `burn` estándar
+#[test]
+fn test_burn_emits_events() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    client.mint(&admin, &alice, &1000);
+    
+    client.burn(&alice, &300);
+    
+    let detailed = BurnEvent {
+        from: alice.clone(),
+        amount: 300,
+        new_balance: 700,
+        new_supply: 700,
+    };
+    let standard = Sep41BurnEvent { from: alice, amount: 300 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: burn_from emite BurnFromEvent y el `burn` estándar
+#[test]
+fn test_burn_from_emits_events() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &bob, &500, &1000);
+    
+    client.burn_from(&bob, &alice, &200);
+    
+    let detailed = BurnFromEvent {
+        spender: bob,
+        from: alice.clone(),
+        amount: 200,
+        new_balance: 800,
+        new_supply: 800,
+        new_allowance: 300,
+    };
+    let standard = Sep41BurnEvent { from: alice, amount: 200 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: transfer emite TransferEvent y el `transfer` estándar
+/// 
+/// Con destino muxed el estándar lleva `to_muxed_id` (CAP-67)
+#[test]
+fn test_transfer_emits_events() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.mint(&admin, &alice, &1000);
+    
+    client.transfer(&alice, MuxedAddress::from(bob.clone()), &300);
+    
+    let detailed = TransferEvent {
+        from: alice.clone(),
+        to: bob.clone(),
+        amount: 300,
+        fee: 0,
+        net_amount: 300,
+        from_new_balance: 700,
+        to_new_balance: 300,
+        memo: None,
+    };
+    let standard = Sep41TransferEvent { from: alice.clone(), to: bob, amount: 300 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+    
+    let muxed = MuxedAddress::generate(&env);
+    client.transfer(&alice, &muxed, &100);
+    
+    let detailed = TransferEvent {
+        from: alice.clone(),
+        to: muxed.address(),
+        amount: 100,
+        fee: 0,
+        net_amount: 100,
+        from_new_balance: 600,
+        to_new_balance: 100,
+        memo: None,
+    };
+    let standard = Sep41TransferMuxedEvent {
+        from: alice,
+        to: muxed.address(),
+        amount: 100,
+        to_muxed_id: muxed.id().unwrap(),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: approve emite ApproveEvent y el `approve` estándar
+#[test]
+fn test_approve_emits_events() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.approve(&alice, &bob, &500, &1000);
+    
+    let detailed = ApproveEvent {
+        from: alice.clone(),
+        spender: bob.clone(),
+        old_allowance: 0,
+        new_allowance: 500,
+        expiration_ledger: 1000,
+    };
+    let standard = Sep41ApproveEvent {
+        from: alice,
+        spender: bob,
+        amount: 500,
+        expiration_ledger: 1000,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: transfer_from emite TransferFromEvent y el `transfer` estándar
+#[test]
+fn test_transfer_from_emits_events() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &bob, &500, &1000);
+    
+    client.transfer_from(&bob, &alice, &charlie, &200);
+    
+    let detailed = TransferFromEvent {
+        spender: bob,
+        from: alice.clone(),
+        to: charlie.clone(),
+        amount: 200,
+        fee: 0,
+        net_amount: 200,
+        from_new_balance: 800,
+        to_new_balance: 200,
+        new_allowance: 300,
+        memo: None,
+    };
+    let standard = Sep41TransferEvent { from: alice, to: charlie, amount: 200 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: propose_admin, accept_admin y renounce_admin emiten su evento
+#[test]
+fn test_admin_changes_emit_events() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let new_admin = Address::generate(&env);
+    
+    client.propose_admin(&new_admin);
+    let event = AdminProposedEvent { admin: admin.clone(), pending_admin: new_admin.clone() };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+    
+    client.accept_admin();
+    let event = AdminTransferredEvent { previous_admin: admin.clone(), new_admin: new_admin.clone() };
+    let mut expected = vec![&env, emitted(&env, &client, &event)];
+    for role in [Role::Minter, Role::Burner, Role::Pauser, Role::MetadataManager] {
+        let revoked = RoleRevokedEvent { role, account: admin.clone(), admin: new_admin.clone() };
+        expected.push_back(emitted(&env, &client, &revoked));
+    }
+    assert_eq!(env.events().all(), expected);
+    
+    client.renounce_admin();
+    let event = AdminRenouncedEvent { admin: new_admin };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+}
+
+/// Test: grant_role y revoke_role emiten su evento
+#[test]
+fn test_role_changes_emit_events() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let minter = Address::generate(&env);
+    
+    client.grant_role(&Role::Minter, &minter);
+    let event = RoleGrantedEvent { role: Role::Minter, account: minter.clone(), admin: admin.clone() };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+    
+    client.revoke_role(&Role::Minter, &minter);
+    let event = RoleRevokedEvent { role: Role::Minter, account: minter, admin };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+}
+
+/// Test: register_permit_key emite PermitKeyRegisteredEvent
+#[test]
+fn test_register_permit_key_emits_event() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let owner = Address::generate(&env);
+    let signer = permit_signer(1);
+    
+    register_signer(&env, &client, &owner, &signer);
+    
+    let event = PermitKeyRegisteredEvent {
+        owner,
+        public_key: BytesN::from_array(&env, &signer.verifying_key().to_bytes()),
+    };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+}
+
+// ============================================================
+// Allowance relativo (increase/decrease) e ilimitado
+// ============================================================
+
+/// Test: increase_allowance suma al valor vigente y fija la nueva expiración
+#[test]
+fn test_increase_allowance() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.approve(&alice, &bob, &100, &1000);
+    client.increase_allowance(&alice, &bob, &50, &2000);
+    
+    let detailed = ApproveEvent {
+        from: alice.clone(),
+        spender: bob.clone(),
+        old_allowance: 100,
+        new_allowance: 150,
+        expiration_ledger: 2000,
+    };
+    let standard = Sep41ApproveEvent {
+        from: alice.clone(),
+        spender: bob.clone(),
+        amount: 150,
+        expiration_ledger: 2000,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+    assert_eq!(client.allowance(&alice, &bob), 150);
+    
+    // Montos no positivos y expiraciones pasadas se rechazan
+    assert_eq!(client.try_increase_allowance(&alice, &bob, &0, &2000), Err(Ok(TokenError::InvalidAmount)));
+    set_sequence(&env, 100);
+    assert_eq!(client.try_increase_allowance(&alice, &bob, &1, &99), Err(Ok(TokenError::InvalidExpiration)));
+}
+
+/// Test: un allowance vencido cuenta como 0 al incrementarlo
+#[test]
+fn test_increase_expired_allowance() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.approve(&alice, &bob, &100, &10);
+    set_sequence(&env, 11);
+    
+    client.increase_allowance(&alice, &bob, &50, &100);
+    assert_eq!(client.allowance(&alice, &bob), 50);
+}
+
+/// Test: increase_allowance usa aritmética verificada
+#[test]
+fn test_increase_allowance_overflow() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.approve(&alice, &bob, &(i128::MAX - 10), &1000);
+    
+    assert_eq!(client.try_increase_allowance(&alice, &bob, &11, &1000), Err(Ok(TokenError::OverflowError)));
+    
+    // Llegar exactamente a i128::MAX lo vuelve ilimitado
+    client.increase_allowance(&alice, &bob, &10, &1000);
+    assert_eq!(client.allowance(&alice, &bob), UNLIMITED_ALLOWANCE);
+}
+
+/// Test: decrease_allowance conserva la expiración y borra la key en 0
+#[test]
+fn test_decrease_allowance() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.approve(&alice, &bob, &100, &1000);
+    client.decrease_allowance(&alice, &bob, &30);
+    
+    let stored = env.as_contract(&client.address, || storage::read_allowance(&env, &alice, &bob));
+    assert_eq!(stored.amount, 70);
+    assert_eq!(stored.expiration_ledger, 1000);
+    
+    // No puede bajar de 0
+    assert_eq!(client.try_decrease_allowance(&alice, &bob, &71), Err(Ok(TokenError::InsufficientAllowance)));
+    assert_eq!(client.try_decrease_allowance(&alice, &bob, &0), Err(Ok(TokenError::InvalidAmount)));
+    
+    client.decrease_allowance(&alice, &bob, &70);
+    assert_eq!(client.allowance(&alice, &bob), 0);
+    env.as_contract(&client.address, || {
+        let key = DataKey::Allowance(alice.clone(), bob.clone());
+        assert!(!env.storage().persistent().has(&key));
+    });
+}
+
+/// Test: un allowance de i128::MAX no se descuenta en transfer_from ni burn_from
+#[test]
+fn test_unlimited_allowance() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &bob, &UNLIMITED_ALLOWANCE, &1000);
+    
+    client.transfer_from(&bob, &alice, &charlie, &300);
+    let detailed = TransferFromEvent {
+        spender: bob.clone(),
+        from: alice.clone(),
+        to: charlie.clone(),
+        amount: 300,
+        fee: 0,
+        net_amount: 300,
+        from_new_balance: 700,
+        to_new_balance: 300,
+        new_allowance: UNLIMITED_ALLOWANCE,
+        memo: None,
+    };
+    let standard = Sep41TransferEvent { from: alice.clone(), to: charlie.clone(), amount: 300 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+    
+    client.burn_from(&bob, &alice, &200);
+    
+    assert_eq!(client.balance(&alice), 500);
+    assert_eq!(client.allowance(&alice, &bob), UNLIMITED_ALLOWANCE);
+    let stored = env.as_contract(&client.address, || storage::read_allowance(&env, &alice, &bob));
+    assert_eq!(stored.amount, UNLIMITED_ALLOWANCE);
+    assert_eq!(stored.expiration_ledger, 1000);
+    
+    // Sigue sujeto a la expiración
+    set_sequence(&env, 1001);
+    assert_eq!(client.try_transfer_from(&bob, &alice, &charlie, &1), Err(Ok(TokenError::InsufficientAllowance)));
+}
+
+// ============================================================
+// Holders y allowances enumerables
+// ============================================================
+
+/// Test: la lista de holders sigue a los balances que pasan por 0
+#[test]
+fn test_holders_follow_balances() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.mint(&admin, &alice, &1000);
+    client.mint(&admin, &bob, &500);
+    client.transfer(&alice, &charlie, &400);
+    assert_eq!(client.holder_count(), 3);
+    assert_eq!(client.holders(&0, &10), vec![&env, alice.clone(), bob.clone(), charlie.clone()]);
+    
+    // transfer de todo el balance: alice sale y charlie ocupa su lugar
+    client.transfer(&alice, &bob, &600);
+    assert_eq!(client.holders(&0, &10), vec![&env, charlie.clone(), bob.clone()]);
+    
+    // burn de todo el balance
+    client.burn(&bob, &1100);
+    assert_eq!(client.holder_count(), 1);
+    assert_eq!(client.holders(&0, &10), vec![&env, charlie.clone()]);
+    
+    // Volver a recibir lo agrega de nuevo al final
+    client.transfer(&charlie, &alice, &100);
+    assert_eq!(client.holders(&0, &10), vec![&env, charlie, alice]);
+}
+
+/// Test: holders() pagina y limita el tamaño de página
+#[test]
+fn test_holders_pagination() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let mut accounts = soroban_sdk::Vec::new(&env);
+    for _ in 0..MAX_PAGE_SIZE + 5 {
+        let account = Address::generate(&env);
+        client.mint(&admin, &account, &1);
+        accounts.push_back(account);
+    }
+    
+    assert_eq!(client.holder_count(), MAX_PAGE_SIZE + 5);
+    assert_eq!(client.holders(&0, &3), accounts.slice(0..3));
+    assert_eq!(client.holders(&MAX_PAGE_SIZE, &10), accounts.slice(MAX_PAGE_SIZE..MAX_PAGE_SIZE + 5));
+    assert_eq!(client.holders(&0, &u32::MAX).len(), MAX_PAGE_SIZE);
+    assert_eq!(client.holders(&(MAX_PAGE_SIZE + 5), &10).len(), 0);
+}
+
+/// Test: en un contrato migrado desde un esquema sin listas, las consultas fallan
+#[test]
+fn test_migrate_disables_lists() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    // Simular un holder y un allowance creados por un código sin listas
+    client.mint(&admin, &alice, &100);
+    client.approve(&alice, &bob, &50, &1000);
+    env.as_contract(&client.address, || {
+        env.storage().persistent().remove(&DataKey::Holder(0));
+        env.storage().persistent().remove(&DataKey::HolderIndex(alice.clone()));
+        env.storage().instance().remove(&DataKey::HolderCount);
+        env.storage().persistent().remove(&DataKey::Spender(alice.clone(), 0));
+        env.storage().persistent().remove(&DataKey::SpenderIndex(alice.clone(), bob.clone()));
+        env.storage().persistent().remove(&DataKey::SpenderCount(alice.clone()));
+        env.storage().instance().set(&DataKey::StorageVersion, &2u32);
+    });
+    client.migrate();
+    
+    assert_eq!(client.try_holder_count(), Err(Ok(TokenError::ListsIncomplete)));
+    assert_eq!(client.try_holders(&0, &10), Err(Ok(TokenError::ListsIncomplete)));
+    assert_eq!(client.try_spender_count(&alice), Err(Ok(TokenError::ListsIncomplete)));
+    assert_eq!(client.try_allowances_of(&alice, &0, &10), Err(Ok(TokenError::ListsIncomplete)));
+    
+    // Las cuentas sin posición siguen operando normalmente
+    client.transfer_from(&bob, &alice, &bob, &50);
+    client.transfer(&alice, &bob, &50);
+    assert_eq!(client.balance(&alice), 0);
+    assert_eq!(client.balance(&bob), 100);
+}
+
+/// Test: allowances_of se pagina por posición en la lista de spenders
+#[test]
+fn test_allowances_of_paged() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    let dave = Address::generate(&env);
+    
+    client.approve(&alice, &bob, &100, &1000);
+    client.approve(&alice, &charlie, &100, &50);
+    client.approve(&alice, &dave, &100, &1000);
+    assert_eq!(client.spender_count(&alice), 3);
+    assert_eq!(client.allowances_of(&alice, &0, &2), vec![&env, bob.clone(), charlie.clone()]);
+    assert_eq!(client.allowances_of(&alice, &2, &2), vec![&env, dave.clone()]);
+    
+    // Un allowance vencido ocupa su posición pero no se lista
+    set_sequence(&env, 51);
+    assert_eq!(client.spender_count(&alice), 3);
+    assert_eq!(client.allowances_of(&alice, &0, &2), vec![&env, bob.clone()]);
+    assert_eq!(client.allowances_of(&alice, &3, &2), soroban_sdk::Vec::<Address>::new(&env));
+}
+
+/// Test: allowances_of lista los spenders con allowance vigente
+#[test]
+fn test_allowances_of() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    let dave = Address::generate(&env);
+    client.mint(&admin, &alice, &1000);
+    
+    client.approve(&alice, &bob, &100, &1000);
+    client.approve(&alice, &charlie, &200, &1000);
+    client.approve(&alice, &dave, &300, &50);
+    assert_eq!(client.allowances_of(&alice, &0, &10), vec![&env, bob.clone(), charlie.clone(), dave.clone()]);
+    
+    // approve en 0 lo quita y el último ocupa su posición
+    client.approve(&alice, &bob, &0, &0);
+    assert_eq!(client.allowances_of(&alice, &0, &10), vec![&env, dave.clone(), charlie.clone()]);
+    
+    // transfer_from que consume todo el allowance también
+    client.transfer_from(&charlie, &alice, &bob, &200);
+    assert_eq!(client.allowances_of(&alice, &0, &10), vec![&env, dave.clone()]);
+    
+    // Un allowance vencido sigue en storage pero no se lista
+    set_sequence(&env, 51);
+    assert_eq!(client.allowances_of(&alice, &0, &10), soroban_sdk::Vec::<Address>::new(&env));
+    
+    // Reaprobarlo lo vuelve a listar; decrease hasta 0 lo quita y borra la lista
+    client.approve(&alice, &dave, &300, &1000);
+    client.decrease_allowance(&alice, &dave, &300);
+    assert_eq!(client.allowances_of(&alice, &0, &10), soroban_sdk::Vec::<Address>::new(&env));
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::SpenderCount(alice.clone())));
+    });
+}
+
+// ============================================================
+// Memos y cuentas que exigen memo
+// ============================================================
+
+/// Test: transfer_with_memo lleva el memo en TransferEvent
+#[test]
+fn test_transfer_with_memo() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.mint(&admin, &alice, &1000);
+    let memo = String::from_str(&env, "factura-2024-0042");
+    
+    client.transfer_with_memo(&alice, &bob, &300, &memo);
+    
+    let detailed = TransferEvent {
+        from: alice.clone(),
+        to: bob.clone(),
+        amount: 300,
+        fee: 0,
+        net_amount: 300,
+        from_new_balance: 700,
+        to_new_balance: 300,
+        memo: Some(memo),
+    };
+    let standard = Sep41TransferEvent { from: alice, to: bob, amount: 300 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: mint_with_memo lleva el memo en MintEvent
+#[test]
+fn test_mint_with_memo() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let memo = String::from_str(&env, "deposito-7781");
+    
+    client.mint_with_memo(&admin, &alice, &1000, &memo);
+    
+    let detailed = MintEvent {
+        to: alice.clone(),
+        amount: 1000,
+        new_balance: 1000,
+        new_supply: 1000,
+        memo: Some(memo),
+    };
+    let standard = Sep41MintEvent { to: alice, amount: 1000 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: memos vacíos o más largos que MAX_MEMO_LENGTH se rechazan
+#[test]
+fn test_memo_length_limits() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.mint(&admin, &alice, &1000);
+    
+    let empty = String::from_str(&env, "");
+    let too_long = String::from_str(&env, &"x".repeat(MAX_MEMO_LENGTH as usize + 1));
+    let max = String::from_str(&env, &"x".repeat(MAX_MEMO_LENGTH as usize));
+    
+    assert_eq!(client.try_transfer_with_memo(&alice, &bob, &1, &empty), Err(Ok(TokenError::InvalidMemo)));
+    assert_eq!(client.try_transfer_with_memo(&alice, &bob, &1, &too_long), Err(Ok(TokenError::InvalidMemo)));
+    assert_eq!(client.try_mint_with_memo(&admin, &bob, &1, &too_long), Err(Ok(TokenError::InvalidMemo)));
+    
+    client.transfer_with_memo(&alice, &bob, &1, &max);
+    assert_eq!(client.balance(&bob), 1);
+}
+
+/// Test: una cuenta que exige memo solo recibe transferencias con memo
+#[test]
+fn test_memo_required() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    // Cuenta de depósitos de un exchange; el id muxed identifica al cliente
+    let exchange_muxed = MuxedAddress::generate(&env);
+    let exchange = exchange_muxed.address();
+    let spender = Address::generate(&env);
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &spender, &500, &1000);
+    
+    client.set_memo_required(&exchange, &true);
+    let event = MemoRequiredEvent { account: exchange.clone(), required: true };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+    assert!(client.memo_required(&exchange));
+    
+    // Sin memo falla en transfer, transfer_from y transfer_batch
+    assert_eq!(client.try_transfer(&alice, &exchange, &100), Err(Ok(TokenError::MemoRequired)));
+    assert_eq!(
+        client.try_transfer_from(&spender, &alice, &exchange, &100),
+        Err(Ok(TokenError::MemoRequired))
+    );
+    assert_eq!(
+        client.try_transfer_batch(&alice, &vec![&env, (exchange.clone(), 100)]),
+        Err(Ok(TokenError::MemoRequired))
+    );
+    
+    // Con memo, o con el id de una dirección muxed, pasa
+    client.transfer_with_memo(&alice, &exchange, &100, &String::from_str(&env, "user-1234"));
+    client.transfer(&alice, &exchange_muxed, &100);
+    assert_eq!(client.balance(&exchange), 200);
+    
+    // Desactivarlo borra la key
+    client.set_memo_required(&exchange, &false);
+    client.transfer(&alice, &exchange, &100);
+    assert!(!client.memo_required(&exchange));
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::MemoRequired(exchange.clone())));
+    });
+}
+
+/// Test: transfer_from y transfer_batch llegan con memo a una cuenta que lo exige
+#[test]
+fn test_memo_required_transfer_from_and_batch() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let exchange = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &spender, &500, &1000);
+    client.set_memo_required(&exchange, &true);
+    let memo = String::from_str(&env, "user-1234");
+    
+    // transfer_from con memo: el memo viaja en TransferFromEvent
+    client.transfer_from_with_memo(&spender, &alice, &exchange, &100, &memo);
+    let event = TransferFromEvent {
+        spender: spender.clone(),
+        from: alice.clone(),
+        to: exchange.clone(),
+        amount: 100,
+        fee: 0,
+        net_amount: 100,
+        from_new_balance: 900,
+        to_new_balance: 100,
+        new_allowance: 400,
+        memo: Some(memo.clone()),
+    };
+    assert!(env.events().all().contains(emitted(&env, &client, &event)));
+    
+    // Lote con un memo por destinatario
+    client.transfer_batch_with_memo(&alice, &vec![
+        &env,
+        (exchange.clone(), 200, memo.clone()),
+        (bob.clone(), 50, String::from_str(&env, "factura-7")),
+    ]);
+    let event = TransferEvent {
+        from: alice.clone(),
+        to: exchange.clone(),
+        amount: 200,
+        fee: 0,
+        net_amount: 200,
+        from_new_balance: 700,
+        to_new_balance: 300,
+        memo: Some(memo.clone()),
+    };
+    assert!(env.events().all().contains(emitted(&env, &client, &event)));
+    assert_eq!(client.balance(&exchange), 300);
+    assert_eq!(client.balance(&bob), 50);
+    assert_eq!(client.balance(&alice), 650);
+    
+    // Los memos se validan igual que en transfer_with_memo
+    assert_eq!(
+        client.try_transfer_from_with_memo(&spender, &alice, &exchange, &100, &String::from_str(&env, "")),
+        Err(Ok(TokenError::InvalidMemo))
+    );
+    assert_eq!(
+        client.try_transfer_batch_with_memo(&alice, &vec![&env, (bob.clone(), 10, String::from_str(&env, ""))]),
+        Err(Ok(TokenError::InvalidMemo))
+    );
+}
+
+// ============================================================
+// transfer_and_call y contratos receptores
+// ============================================================
+
+/// Receptor de ejemplo: un vault que acredita cada depósito al remitente
+#[contract]
+pub struct VaultReceiver;
+
+#[contractimpl]
+impl VaultReceiver {
+    pub fn on_token_received(
+        env: Env,
+        token: Address,
+        from: Address,
+        amount: i128,
+        data: Bytes
+    ) -> bool {
+        let key = (token, from);
+        let deposited: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(deposited + amount));
+        env.storage().instance().set(&Symbol::new(&env, "last_data"), &data);
+        true
+    }
+    
+    pub fn deposited(env: Env, token: Address, from: Address) -> i128 {
+        env.storage().persistent().get(&(token, from)).unwrap_or(0)
+    }
+    
+    pub fn last_data(env: Env) -> Option<Bytes> {
+        env.storage().instance().get(&Symbol::new(&env, "last_data"))
+    }
+}
+
+/// Receptor de ejemplo que no acepta tokens
+#[contract]
+pub struct RejectingReceiver;
+
+#[contractimpl]
+impl RejectingReceiver {
+    pub fn on_token_received(
+        _env: Env,
+        _token: Address,
+        _from: Address,
+        _amount: i128,
+        _data: Bytes
+    ) -> bool {
+        false
+    }
+}
+
+/// Receptor de ejemplo cuyo hook falla
+#[contract]
+pub struct PanickingReceiver;
+
+#[contractimpl]
+impl PanickingReceiver {
+    pub fn on_token_received(
+        _env: Env,
+        _token: Address,
+        _from: Address,
+        _amount: i128,
+        _data: Bytes
+    ) -> bool {
+        panic!("venta cerrada")
+    }
+}
+
+/// Receptor de ejemplo que intenta reenviar los tokens con otro transfer_and_call
+#[contract]
+pub struct ReentrantReceiver;
+
+#[contractimpl]
+impl ReentrantReceiver {
+    pub fn on_token_received(
+        env: Env,
+        token: Address,
+        _from: Address,
+        amount: i128,
+        data: Bytes
+    ) -> bool {
+        let receiver = env.current_contract_address();
+        TokenBDBClient::new(&env, &token).transfer_and_call(&receiver, &receiver, &amount, &data);
+        true
+    }
+}
+
+/// Test: transfer_and_call deposita en el vault y le pasa `data`
+#[test]
+fn test_transfer_and_call_to_vault() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let vault_id = env.register(VaultReceiver, ());
+    let vault = VaultReceiverClient::new(&env, &vault_id);
+    let alice = Address::generate(&env);
+    client.mint(&admin, &alice, &1000);
+    let data = Bytes::from_slice(&env, b"deposito:plazo-30");
+    
+    client.transfer_and_call(&alice, &vault_id, &400, &data);
+    
+    let detailed = TransferEvent {
+        from: alice.clone(),
+        to: vault_id.clone(),
+        amount: 400,
+        fee: 0,
+        net_amount: 400,
+        from_new_balance: 600,
+        to_new_balance: 400,
+        memo: None,
+    };
+    let standard = Sep41TransferEvent { from: alice.clone(), to: vault_id.clone(), amount: 400 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+    assert_eq!(client.balance(&alice), 600);
+    assert_eq!(client.balance(&vault_id), 400);
+    assert_eq!(vault.deposited(&client.address, &alice), 400);
+    assert_eq!(vault.last_data(), Some(data));
+    
+    // El guard se libera al terminar
+    env.as_contract(&client.address, || assert!(!storage::is_locked(&env)));
+}
+
+/// Test: si el receptor rechaza o falla, la transferencia se revierte
+#[test]
+fn test_transfer_and_call_rejected_rolls_back() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    client.mint(&admin, &alice, &1000);
+    let data = Bytes::new(&env);
+    
+    let rejecting = env.register(RejectingReceiver, ());
+    let panicking = env.register(PanickingReceiver, ());
+    for receiver in [rejecting, panicking] {
+        assert_eq!(
+            client.try_transfer_and_call(&alice, &receiver, &100, &data),
+            Err(Ok(TokenError::TransferRejected))
+        );
+        assert_eq!(client.balance(&receiver), 0);
+    }
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.holder_count(), 1);
+}
+
+/// Test: un receptor no puede volver a entrar en el token desde el hook
+#[test]
+fn test_transfer_and_call_reentrancy() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let vault = env.register(VaultReceiver, ());
+    client.mint(&admin, &alice, &1000);
+    let data = Bytes::new(&env);
+    
+    // El host de Soroban prohíbe la reentrada: el hook falla y se revierte
+    let reentrant = env.register(ReentrantReceiver, ());
+    assert_eq!(
+        client.try_transfer_and_call(&alice, &reentrant, &100, &data),
+        Err(Ok(TokenError::TransferRejected))
+    );
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.balance(&reentrant), 0);
+    env.as_contract(&client.address, || assert!(!storage::is_locked(&env)));
+    
+    // Con el guard activo, transfer_and_call se rechaza de entrada
+    env.as_contract(&client.address, || storage::write_locked(&env, true));
+    assert_eq!(
+        client.try_transfer_and_call(&alice, &vault, &100, &data),
+        Err(Ok(TokenError::Reentrancy))
+    );
+    assert_eq!(client.balance(&alice), 1000);
+    env.as_contract(&client.address, || storage::write_locked(&env, false));
+    
+    // Liberado el guard, la siguiente llamada pasa
+    client.transfer_and_call(&alice, &vault, &100, &data);
+    assert_eq!(client.balance(&vault), 100);
+}
+
+/// Test: un receptor que devuelve false no deja balances, historial ni eventos
+#[test]
+fn test_transfer_and_call_false_reverts_everything() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    set_sequence(&env, 10);
+    client.mint(&admin, &alice, &1000);
+    client.delegate(&alice, &alice);
+    let rejecting = env.register(RejectingReceiver, ());
+    set_sequence(&env, 20);
+    
+    assert_eq!(
+        client.try_transfer_and_call(&alice, &rejecting, &100, &Bytes::new(&env)),
+        Err(Ok(TokenError::TransferRejected))
+    );
+    
+    // Ni TransferEvent ni el transfer estándar quedan publicados
+    assert_eq!(env.events().all().len(), 0);
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.balance(&rejecting), 0);
+    assert_eq!(client.get_votes(&alice), 1000);
+    assert_eq!(client.holders(&0, &10), vec![&env, alice.clone()]);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::Balance(rejecting.clone())));
+        assert!(!env.storage().persistent().has(&DataKey::BalanceHistory(rejecting.clone())));
+    });
+    set_sequence(&env, 21);
+    assert_eq!(client.balance_at(&alice, &20), 1000);
+}
+
+/// Test: hacia una cuenta, transfer_and_call es un transfer normal
+#[test]
+fn test_transfer_and_call_to_account() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.mint(&admin, &alice, &1000);
+    
+    client.transfer_and_call(&alice, &bob, &250, &Bytes::new(&env));
+    
+    assert_eq!(client.balance(&alice), 750);
+    assert_eq!(client.balance(&bob), 250);
+    
+    // Las validaciones de transfer siguen aplicando
+    assert_eq!(
+        client.try_transfer_and_call(&alice, &bob, &0, &Bytes::new(&env)),
+        Err(Ok(TokenError::InvalidAmount))
+    );
+}
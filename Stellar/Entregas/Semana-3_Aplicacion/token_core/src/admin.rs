@@ -0,0 +1,160 @@
+// src/admin.rs
+use soroban_sdk::{Address, BytesN, Env};
+
+use crate::errors::TokenError;
+use crate::events::{
+    AdminProposedEvent, AdminRenouncedEvent, AdminTransferredEvent, MigratedEvent,
+    SetAuthorizedEvent, UpgradedEvent,
+};
+use crate::storage::{self, DataKey};
+use crate::{require_admin, require_initialized, STORAGE_VERSION};
+
+// Operaciones del admin: transferencia en dos pasos, renuncia,
+// congelamiento de cuentas y upgrades. Todas exigen la firma del admin.
+
+/// Propone `new_admin`; reemplaza la propuesta anterior
+pub fn propose_admin(env: &Env, new_admin: Address) -> Result<(), TokenError> {
+    // 1. Solo el admin actual puede proponer
+    let admin = require_admin(env)?;
+    
+    // 2. Guardar la propuesta (reemplaza la anterior)
+    env.storage().instance().set(&DataKey::PendingAdmin, &new_admin);
+    
+    // 3. Emitir evento
+    AdminProposedEvent {
+        admin,
+        pending_admin: new_admin,
+    }.publish(env);
+    
+    Ok(())
+}
+
+/// El admin propuesto acepta (requiere su autorización)
+pub fn accept_admin(env: &Env) -> Result<(), TokenError> {
+    // 1. Verificar inicialización
+    require_initialized(env)?;
+    
+    // 2. Debe existir una propuesta pendiente
+    let new_admin: Address = env.storage().instance()
+        .get(&DataKey::PendingAdmin)
+        .ok_or(TokenError::NoPendingAdmin)?;
+    
+    // 3. El admin propuesto firma la aceptación
+    new_admin.require_auth();
+    
+    let previous_admin: Address = env.storage().instance()
+        .get(&DataKey::Admin)
+        .ok_or(TokenError::NotInitialized)?;
+    
+    // 4. Actualizar admin y limpiar la propuesta
+    env.storage().instance().set(&DataKey::Admin, &new_admin);
+    env.storage().instance().remove(&DataKey::PendingAdmin);
+    
+    // 5. Emitir evento
+    AdminTransferredEvent {
+        previous_admin,
+        new_admin,
+    }.publish(env);
+    
+    Ok(())
+}
+
+/// Renuncia permanente al admin
+pub fn renounce_admin(env: &Env) -> Result<(), TokenError> {
+    // 1. Solo el admin actual puede renunciar
+    let admin = require_admin(env)?;
+    
+    // 2. Borrar admin y propuesta pendiente; el flag es permanente
+    env.storage().instance().remove(&DataKey::Admin);
+    env.storage().instance().remove(&DataKey::PendingAdmin);
+    env.storage().instance().set(&DataKey::AdminRenounced, &true);
+    
+    // 3. Emitir evento
+    AdminRenouncedEvent { admin }.publish(env);
+    
+    Ok(())
+}
+
+pub fn pending_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::PendingAdmin)
+}
+
+pub fn admin_renounced(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::AdminRenounced)
+}
+
+/// Congela (`authorize = false`) o descongela `id`
+pub fn set_authorized(env: &Env, id: Address, authorize: bool) -> Result<(), TokenError> {
+    // 1. Solo el admin puede congelar cuentas
+    let admin = require_admin(env)?;
+    
+    // 2. Actualizar el flag (se elimina la key al descongelar)
+    storage::write_authorized(env, &id, authorize);
+    
+    // 3. Emitir evento para herramientas de compliance
+    SetAuthorizedEvent {
+        admin,
+        id,
+        authorize,
+    }.publish(env);
+    
+    Ok(())
+}
+
+pub fn authorized(env: &Env, id: &Address) -> bool {
+    storage::is_authorized(env, id)
+}
+
+/// Reemplaza el wasm del contrato (el storage se mantiene intacto)
+pub fn upgrade(env: &Env, new_wasm_hash: BytesN<32>) -> Result<(), TokenError> {
+    // 1. Solo el admin puede cambiar el código
+    let admin = require_admin(env)?;
+    
+    // 2. Reemplazar el wasm
+    env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+    
+    // 3. Emitir evento
+    UpgradedEvent {
+        admin,
+        new_wasm_hash,
+    }.publish(env);
+    
+    Ok(())
+}
+
+/// Lleva el storage desde su versión guardada hasta STORAGE_VERSION
+pub fn migrate(env: &Env) -> Result<(), TokenError> {
+    // 1. Solo el admin puede migrar
+    let admin = require_admin(env)?;
+    
+    // 2. Verificar que el esquema guardado sea anterior al del código
+    let from_version = storage::read_storage_version(env);
+    if from_version >= STORAGE_VERSION {
+        return Err(TokenError::MigrationNotNeeded);
+    }
+    
+    // 3. Aplicar los pasos de migración pendientes en orden
+    if from_version < 2 {
+        storage::migrate_metadata_v1(env);
+    }
+    if from_version < 3 {
+        storage::start_history(env);
+        storage::mark_lists_incomplete(env);
+    }
+    
+    // 4. Guardar la nueva versión del esquema
+    env.storage().instance().set(&DataKey::StorageVersion, &STORAGE_VERSION);
+    
+    // 5. Emitir evento
+    MigratedEvent {
+        admin,
+        from_version,
+        to_version: STORAGE_VERSION,
+    }.publish(env);
+    
+    Ok(())
+}
+
+pub fn storage_version(env: &Env) -> u32 {
+    storage::read_storage_version(env)
+}
@@ -0,0 +1,826 @@
+// src/base.rs
+use soroban_sdk::{Address, Env, Map, MuxedAddress, String, Vec};
+
+use crate::errors::TokenError;
+use crate::events::{
+    ApproveEvent, MemoRequiredEvent, MetadataUpdatedEvent, Sep41ApproveEvent, Sep41TransferEvent,
+    Sep41TransferMuxedEvent, TransferEvent, TransferFromEvent,
+};
+use crate::storage::{self, DataKey, PauseTarget, TokenMetadata};
+use crate::{
+    require_authorized, require_initialized, require_past_ledger, TokenHooks, MAX_BATCH_SIZE,
+    MAX_DECIMALS, MAX_DESCRIPTION_LENGTH, MAX_MEMO_LENGTH, MAX_METADATA_ENTRIES,
+    MAX_NAME_LENGTH, MAX_PAGE_SIZE, MAX_SYMBOL_LENGTH, MAX_URL_LENGTH, STORAGE_VERSION, UNLIMITED_ALLOWANCE,
+};
+
+// Operaciones SEP-41 y consultas comunes a todos los tokens del núcleo
+
+#[cfg(feature = "fee")]
+use crate::fee::{collect_transfer_fee, transfer_fee};
+#[cfg(feature = "pausable")]
+use crate::pausable::require_not_paused;
+#[cfg(feature = "periodic")]
+use crate::periodic::{periodic_allowance, spend_periodic_allowance};
+
+/// Sin la extensión `pausable` nada se puede pausar
+#[cfg(not(feature = "pausable"))]
+fn require_not_paused(_env: &Env, _target: PauseTarget) -> Result<(), TokenError> {
+    Ok(())
+}
+
+/// Sin la extensión `fee` las transferencias no pagan fee
+#[cfg(not(feature = "fee"))]
+fn transfer_fee(_env: &Env, _from: &Address, _to: &Address, _amount: i128) -> Result<i128, TokenError> {
+    Ok(0)
+}
+
+/// Valida los límites de longitud de los metadatos
+pub fn validate_metadata(metadata: &TokenMetadata) -> Result<(), TokenError> {
+    if metadata.name.is_empty() || metadata.name.len() > MAX_NAME_LENGTH {
+        return Err(TokenError::InvalidMetadata);
+    }
+    if metadata.symbol.is_empty() || metadata.symbol.len() > MAX_SYMBOL_LENGTH {
+        return Err(TokenError::InvalidMetadata);
+    }
+    if metadata.description.len() > MAX_DESCRIPTION_LENGTH
+        || metadata.icon_url.len() > MAX_URL_LENGTH
+        || metadata.home_domain.len() > MAX_URL_LENGTH
+    {
+        return Err(TokenError::InvalidMetadata);
+    }
+    
+    if metadata.extra.len() > MAX_METADATA_ENTRIES {
+        return Err(TokenError::InvalidMetadata);
+    }
+    for (key, value) in metadata.extra.iter() {
+        if key.is_empty() || key.len() > MAX_SYMBOL_LENGTH || value.len() > MAX_URL_LENGTH {
+            return Err(TokenError::InvalidMetadata);
+        }
+    }
+    
+    Ok(())
+}
+
+/// Inicializa el estado común: admin, metadatos, supply y versión
+/// 
+/// El contrato agrega lo propio (roles, tope, clawback) y emite su
+/// evento de inicialización.
+pub fn initialize(
+    env: &Env,
+    admin: &Address,
+    name: String,
+    symbol: String,
+    decimals: u32
+) -> Result<(), TokenError> {
+    // 1. Verificar que no esté inicializado
+    if env.storage().instance().has(&DataKey::Initialized) {
+        return Err(TokenError::AlreadyInitialized);
+    }
+    
+    // 2. Validar decimales (máximo 18 como Ethereum)
+    if decimals > MAX_DECIMALS {
+        return Err(TokenError::InvalidDecimals);
+    }
+    
+    // 3. Validar metadatos (name y symbol no vacíos)
+    if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+        return Err(TokenError::InvalidMetadata);
+    }
+    
+    if symbol.is_empty() || symbol.len() > MAX_SYMBOL_LENGTH {
+        return Err(TokenError::InvalidMetadata);
+    }
+    
+    // 4. Guardar metadata en instance storage
+    env.storage().instance().set(&DataKey::Admin, admin);
+    storage::write_metadata(env, &TokenMetadata {
+        name,
+        symbol,
+        decimals,
+        description: String::from_str(env, ""),
+        icon_url: String::from_str(env, ""),
+        home_domain: String::from_str(env, ""),
+        extra: Map::new(env),
+    });
+    env.storage().instance().set(&DataKey::TotalSupply, &0i128);
+    env.storage().instance().set(&DataKey::Initialized, &true);
+    env.storage().instance().set(&DataKey::StorageVersion, &STORAGE_VERSION);
+    
+    // 5. Extender TTL del storage de instance (30 días)
+    env.storage().instance().extend_ttl(100_000, 200_000);
+    
+    Ok(())
+}
+
+/// Reduce el balance de `from` y el total supply en `amount`
+/// 
+/// Compartido por burn(), burn_from() y el clawback de WorxToken: verifica
+/// el balance y actualiza el supply con checked_sub. No verifica
+/// autorizaciones. Devuelve (nuevo balance, nuevo supply).
+pub fn burn_balance<H: TokenHooks>(
+    env: &Env,
+    from: &Address,
+    amount: i128
+) -> Result<(i128, i128), TokenError> {
+    // 1. Verificar balance
+    let balance = storage::read_balance(env, from);
+    if balance < amount {
+        return Err(TokenError::InsufficientBalance);
+    }
+    
+    // 2. Calcular nuevo supply
+    let new_total = storage::read_total_supply(env).checked_sub(amount)
+        .ok_or(TokenError::OverflowError)?;
+    
+    // 3. Actualizar balance y total supply (con sus checkpoints)
+    let new_balance = balance - amount;
+    storage::write_balance(env, from, new_balance)?;
+    storage::write_total_supply(env, new_total);
+    H::on_balance_moved(env, Some(from), None, amount);
+    
+    Ok((new_balance, new_total))
+}
+
+/// Valida monto, destinatario y cuentas congeladas de una transferencia
+fn validate_transfer(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    amount: i128
+) -> Result<(), TokenError> {
+    if amount <= 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    
+    // No permitir transferencia a sí mismo (gas-efficient)
+    if from == to {
+        return Err(TokenError::InvalidRecipient);
+    }
+    
+    // Verificar que ninguna de las cuentas esté congelada
+    require_authorized(env, from)?;
+    require_authorized(env, to)
+}
+
+/// Valida el largo del memo (1..=MAX_MEMO_LENGTH)
+pub fn validate_memo(memo: &Option<String>) -> Result<(), TokenError> {
+    match memo {
+        Some(memo) if memo.is_empty() || memo.len() > MAX_MEMO_LENGTH => {
+            Err(TokenError::InvalidMemo)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Falla con MemoRequired si `to` exige memo y la transferencia no lo trae
+/// 
+/// El id de una dirección muxed cuenta como memo
+fn require_memo(env: &Env, to: &Address, has_memo: bool) -> Result<(), TokenError> {
+    if !has_memo && storage::is_memo_required(env, to) {
+        return Err(TokenError::MemoRequired);
+    }
+    Ok(())
+}
+
+/// Mueve `amount` de `from` a `to` descontando el fee (si hay)
+/// 
+/// Se llama después de validate_transfer(); no verifica autorizaciones
+/// ni pausas. Devuelve (fee, nuevo balance de `from`, nuevo balance
+/// de `to`).
+fn move_balance<H: TokenHooks>(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    amount: i128
+) -> Result<(i128, i128, i128), TokenError> {
+    // 1. Verificar balance
+    let from_balance = storage::read_balance(env, from);
+    if from_balance < amount {
+        return Err(TokenError::InsufficientBalance);
+    }
+    
+    // 2. Calcular fee y nuevos balances con verificación de overflow
+    let fee = transfer_fee(env, from, to, amount)?;
+    let net_amount = amount - fee;
+    let new_from_balance = from_balance - amount;
+    let new_to_balance = storage::read_balance(env, to).checked_add(net_amount)
+        .ok_or(TokenError::OverflowError)?;
+    
+    // 3. Actualizar balances (con sus checkpoints)
+    storage::write_balance(env, from, new_from_balance)?;
+    storage::write_balance(env, to, new_to_balance)?;
+    H::on_balance_moved(env, Some(from), Some(to), net_amount);
+    
+    // 4. Cobrar el fee (tesorería o quema)
+    #[cfg(feature = "fee")]
+    collect_transfer_fee::<H>(env, from, fee)?;
+    
+    Ok((fee, new_from_balance, new_to_balance))
+}
+
+/// Publica el evento `transfer` estándar por el monto acreditado a `to`
+/// 
+/// Con destino muxed la data lleva `to_muxed_id` (CAP-67)
+fn publish_sep41_transfer(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    to_muxed_id: Option<u64>,
+    amount: i128
+) {
+    match to_muxed_id {
+        Some(to_muxed_id) => Sep41TransferMuxedEvent {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+            to_muxed_id,
+        }.publish(env),
+        None => Sep41TransferEvent {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+        }.publish(env),
+    }
+}
+
+/// transfer() de SEP-41: requiere autorización de `from`
+/// 
+/// El balance se acredita a la cuenta base de `to`; su id muxed solo
+/// viaja en el evento estándar.
+pub fn transfer<H: TokenHooks>(
+    env: &Env,
+    from: Address,
+    to: MuxedAddress,
+    amount: i128
+) -> Result<(), TokenError> {
+    transfer_with_memo::<H>(env, from, to, amount, None)
+}
+
+/// transfer() con una referencia de pago que viaja en TransferEvent
+/// 
+/// Es la única forma de transferir a una cuenta que exige memo, salvo
+/// usar una dirección muxed.
+pub fn transfer_with_memo<H: TokenHooks>(
+    env: &Env,
+    from: Address,
+    to: MuxedAddress,
+    amount: i128,
+    memo: Option<String>
+) -> Result<(), TokenError> {
+    let to_muxed_id = to.id();
+    let to = to.address();
+    
+    // 1. Verificar inicialización y que la operación no esté pausada
+    require_initialized(env)?;
+    require_not_paused(env, PauseTarget::Transfer)?;
+    
+    // 2. Verificar autorización del sender
+    from.require_auth();
+    
+    // 3. Validaciones y movimiento de balances
+    validate_transfer(env, &from, &to, amount)?;
+    validate_memo(&memo)?;
+    require_memo(env, &to, memo.is_some() || to_muxed_id.is_some())?;
+    let (fee, from_new_balance, to_new_balance) = move_balance::<H>(env, &from, &to, amount)?;
+    
+    // 4. Emitir evento con balances post-transferencia y el estándar
+    TransferEvent {
+        from: from.clone(),
+        to: to.clone(),
+        amount,
+        fee,
+        net_amount: amount - fee,
+        from_new_balance,
+        to_new_balance,
+        memo,
+    }.publish(env);
+    publish_sep41_transfer(env, &from, &to, to_muxed_id, amount - fee);
+    
+    Ok(())
+}
+
+/// transfer_from() de SEP-41: requiere autorización de `spender`
+pub fn transfer_from<H: TokenHooks>(
+    env: &Env,
+    spender: Address,
+    from: Address,
+    to: Address,
+    amount: i128
+) -> Result<(), TokenError> {
+    transfer_from_with_memo::<H>(env, spender, from, to, amount, None)
+}
+
+/// transfer_from() con una referencia de pago que viaja en TransferFromEvent
+/// 
+/// Permite que un spender pague a una cuenta que exige memo.
+pub fn transfer_from_with_memo<H: TokenHooks>(
+    env: &Env,
+    spender: Address,
+    from: Address,
+    to: Address,
+    amount: i128,
+    memo: Option<String>
+) -> Result<(), TokenError> {
+    // 1. Verificar inicialización y que la operación no esté pausada
+    require_initialized(env)?;
+    require_not_paused(env, PauseTarget::Transfer)?;
+    
+    // 2. Verificar autorización del spender
+    spender.require_auth();
+    
+    // 3. Validaciones
+    validate_transfer(env, &from, &to, amount)?;
+    validate_memo(&memo)?;
+    require_memo(env, &to, memo.is_some())?;
+    
+    // 4. Consumir allowance (los ilimitados no se descuentan)
+    let new_allowance = spend_allowance(env, &from, &spender, amount)?;
+    
+    // 5. Mover balances
+    let (fee, from_new_balance, to_new_balance) = move_balance::<H>(env, &from, &to, amount)?;
+    
+    // 6. Emitir evento detallado y el estándar
+    TransferFromEvent {
+        spender,
+        from: from.clone(),
+        to: to.clone(),
+        amount,
+        fee,
+        net_amount: amount - fee,
+        from_new_balance,
+        to_new_balance,
+        new_allowance,
+        memo,
+    }.publish(env);
+    publish_sep41_transfer(env, &from, &to, None, amount - fee);
+    
+    Ok(())
+}
+
+/// approve() de SEP-41: requiere autorización de `from`
+pub fn approve(
+    env: &Env,
+    from: Address,
+    spender: Address,
+    amount: i128,
+    expiration_ledger: u32
+) -> Result<(), TokenError> {
+    // 1. Verificar inicialización
+    require_initialized(env)?;
+    
+    // 2. Verificar autorización del owner
+    from.require_auth();
+    
+    // 3. Validación: amount debe ser >= 0 (permitir 0 para revocar)
+    if amount < 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    
+    // 4. Un allowance no nulo no puede nacer expirado (igual que el SAC)
+    if amount > 0 && expiration_ledger < env.ledger().sequence() {
+        return Err(TokenError::InvalidExpiration);
+    }
+    
+    // 5. Actualizar allowance y emitir evento
+    set_allowance(env, from, spender, amount, expiration_ledger)?;
+    
+    Ok(())
+}
+
+/// Activa o desactiva el requisito de memo de `id` (requiere su autorización)
+/// 
+/// Pensado para exchanges y custodios: las transferencias sin memo a una
+/// cuenta que lo exige fallan con MemoRequired (SEP-29).
+pub fn set_memo_required(env: &Env, id: Address, required: bool) -> Result<(), TokenError> {
+    // 1. Verificar inicialización
+    require_initialized(env)?;
+    
+    // 2. Solo la propia cuenta cambia su requisito
+    id.require_auth();
+    
+    // 3. Actualizar flag y emitir evento
+    storage::write_memo_required(env, &id, required);
+    MemoRequiredEvent { account: id, required }.publish(env);
+    
+    Ok(())
+}
+
+pub fn memo_required(env: &Env, id: &Address) -> bool {
+    storage::is_memo_required(env, id)
+}
+
+/// Suma `amount` al allowance vigente: requiere autorización de `from`
+/// 
+/// Evita la carrera de approve(): el spender no puede gastar el valor
+/// anterior y además el nuevo. `expiration_ledger` reemplaza la
+/// expiración actual (un allowance vencido cuenta como 0).
+pub fn increase_allowance(
+    env: &Env,
+    from: Address,
+    spender: Address,
+    amount: i128,
+    expiration_ledger: u32
+) -> Result<(), TokenError> {
+    // 1. Verificar inicialización
+    require_initialized(env)?;
+    
+    // 2. Verificar autorización del owner
+    from.require_auth();
+    
+    // 3. Validaciones
+    if amount <= 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    if expiration_ledger < env.ledger().sequence() {
+        return Err(TokenError::InvalidExpiration);
+    }
+    
+    // 4. Calcular el nuevo allowance (llegar a i128::MAX lo vuelve ilimitado)
+    let new_allowance = allowance(env, &from, &spender).checked_add(amount)
+        .ok_or(TokenError::OverflowError)?;
+    
+    // 5. Actualizar allowance y emitir evento
+    set_allowance(env, from, spender, new_allowance, expiration_ledger)?;
+    
+    Ok(())
+}
+
+/// Resta `amount` del allowance vigente: requiere autorización de `from`
+/// 
+/// Conserva la expiración actual; falla con InsufficientAllowance si
+/// el allowance es menor a `amount`.
+pub fn decrease_allowance(
+    env: &Env,
+    from: Address,
+    spender: Address,
+    amount: i128
+) -> Result<(), TokenError> {
+    // 1. Verificar inicialización
+    require_initialized(env)?;
+    
+    // 2. Verificar autorización del owner
+    from.require_auth();
+    
+    // 3. Validación
+    if amount <= 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    
+    // 4. Calcular el nuevo allowance (0 elimina la key)
+    let current = storage::read_allowance(env, &from, &spender);
+    if current.amount < amount {
+        return Err(TokenError::InsufficientAllowance);
+    }
+    let new_allowance = current.amount - amount;
+    
+    // 5. Actualizar allowance y emitir evento
+    set_allowance(env, from, spender, new_allowance, current.expiration_ledger)?;
+    
+    Ok(())
+}
+
+/// Descuenta `amount` del allowance de `spender` y devuelve el restante
+/// 
+/// Lo usan transfer_from() y burn_from(). Un allowance ilimitado
+/// (UNLIMITED_ALLOWANCE) no se descuenta ni se vuelve a escribir.
+/// Con la extensión `periodic`, un allowance periódico vigente tiene
+/// prioridad sobre el común.
+pub fn spend_allowance(
+    env: &Env,
+    from: &Address,
+    spender: &Address,
+    amount: i128
+) -> Result<i128, TokenError> {
+    #[cfg(feature = "periodic")]
+    if let Some(remaining) = spend_periodic_allowance(env, from, spender, amount)? {
+        return Ok(remaining);
+    }
+    
+    // Los allowances expirados valen 0
+    let allowance = storage::read_allowance(env, from, spender);
+    if allowance.amount < amount {
+        return Err(TokenError::InsufficientAllowance);
+    }
+    if allowance.amount == UNLIMITED_ALLOWANCE {
+        return Ok(UNLIMITED_ALLOWANCE);
+    }
+    
+    // El allowance restante conserva su expiración original
+    let new_allowance = allowance.amount - amount;
+    storage::write_allowance(env, from, spender, new_allowance, allowance.expiration_ledger)?;
+    
+    Ok(new_allowance)
+}
+
+/// Escribe el allowance y emite ApproveEvent, sin verificar autorización
+/// 
+/// La usan approve() y los mecanismos que autorizan por otra vía
+/// (ej: permits firmados de TokenBDB).
+pub fn set_allowance(
+    env: &Env,
+    from: Address,
+    spender: Address,
+    amount: i128,
+    expiration_ledger: u32
+) -> Result<(), TokenError> {
+    // 1. Obtener allowance anterior para el evento
+    let old_allowance = allowance(env, &from, &spender);
+    
+    // 2. Actualizar allowance (se elimina la key si amount = 0)
+    storage::write_allowance(env, &from, &spender, amount, expiration_ledger)?;
+    
+    // 3. Evento con allowance anterior, nuevo y expiración, y el estándar
+    ApproveEvent {
+        from: from.clone(),
+        spender: spender.clone(),
+        old_allowance,
+        new_allowance: amount,
+        expiration_ledger,
+    }.publish(env);
+    Sep41ApproveEvent {
+        from,
+        spender,
+        amount,
+        expiration_ledger,
+    }.publish(env);
+    
+    Ok(())
+}
+
+/// Transfiere a varios destinatarios con una sola autorización de `from`
+/// 
+/// Todo o nada: si un destinatario falla la validación, no se mueve nada.
+/// Cada destinatario recibe el neto de fee y su propio TransferEvent.
+pub fn transfer_batch<H: TokenHooks>(
+    env: &Env,
+    from: Address,
+    recipients: Vec<(Address, i128)>
+) -> Result<(), TokenError> {
+    let mut batch = Vec::new(env);
+    for (to, amount) in recipients.iter() {
+        batch.push_back((to, amount, None));
+    }
+    move_batch::<H>(env, from, batch)
+}
+
+/// transfer_batch() con un memo por destinatario
+/// 
+/// Permite incluir en el lote cuentas que exigen memo.
+pub fn transfer_batch_with_memo<H: TokenHooks>(
+    env: &Env,
+    from: Address,
+    recipients: Vec<(Address, i128, String)>
+) -> Result<(), TokenError> {
+    let mut batch = Vec::new(env);
+    for (to, amount, memo) in recipients.iter() {
+        batch.push_back((to, amount, Some(memo)));
+    }
+    move_batch::<H>(env, from, batch)
+}
+
+/// Lote de transferencias con memo opcional por destinatario
+fn move_batch<H: TokenHooks>(
+    env: &Env,
+    from: Address,
+    recipients: Vec<(Address, i128, Option<String>)>
+) -> Result<(), TokenError> {
+    // 1. Verificar inicialización y que la operación no esté pausada
+    require_initialized(env)?;
+    require_not_paused(env, PauseTarget::Transfer)?;
+    
+    // 2. Una sola autorización del sender para todo el lote
+    from.require_auth();
+    
+    // 3. Validar tamaño del lote
+    if recipients.is_empty() || recipients.len() > MAX_BATCH_SIZE {
+        return Err(TokenError::InvalidBatchSize);
+    }
+    require_authorized(env, &from)?;
+    
+    // 4. Validar cada destinatario y calcular el total
+    let mut total: i128 = 0;
+    for (to, amount, memo) in recipients.iter() {
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        if to == from {
+            return Err(TokenError::InvalidRecipient);
+        }
+        require_authorized(env, &to)?;
+        validate_memo(&memo)?;
+        require_memo(env, &to, memo.is_some())?;
+        total = total.checked_add(amount)
+            .ok_or(TokenError::OverflowError)?;
+    }
+    
+    // 5. Verificar el balance contra el total antes de mover fondos
+    let from_balance = storage::read_balance(env, &from);
+    if from_balance < total {
+        return Err(TokenError::InsufficientBalance);
+    }
+    
+    // 6. Acreditar a cada destinatario (neto de fee) y emitir su evento
+    let mut from_new_balance = from_balance;
+    let mut total_fee: i128 = 0;
+    for (to, amount, memo) in recipients.iter() {
+        from_new_balance -= amount;
+        let fee = transfer_fee(env, &from, &to, amount)?;
+        let net_amount = amount - fee;
+        total_fee += fee;
+        let to_new_balance = storage::read_balance(env, &to).checked_add(net_amount)
+            .ok_or(TokenError::OverflowError)?;
+        
+        storage::write_balance(env, &to, to_new_balance)?;
+        H::on_balance_moved(env, None, Some(&to), net_amount);
+        
+        TransferEvent {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+            fee,
+            net_amount,
+            from_new_balance,
+            to_new_balance,
+            memo,
+        }.publish(env);
+        publish_sep41_transfer(env, &from, &to, None, net_amount);
+    }
+    
+    // 7. Debitar al sender una sola vez
+    storage::write_balance(env, &from, from_new_balance)?;
+    H::on_balance_moved(env, Some(&from), None, total - total_fee);
+    
+    // 8. Cobrar los fees del lote en una sola escritura
+    #[cfg(feature = "fee")]
+    collect_transfer_fee::<H>(env, &from, total_fee)?;
+    
+    Ok(())
+}
+
+/// Reemplaza los metadatos, sin verificar autorización
+/// 
+/// El contrato decide quién puede llamarla (admin o rol) y pasa esa
+/// cuenta como `caller`. Los decimales no se pueden cambiar porque
+/// alterarían todos los balances.
+pub fn update_metadata(
+    env: &Env,
+    caller: Address,
+    metadata: TokenMetadata
+) -> Result<(), TokenError> {
+    // 1. Validar longitudes; los decimales son inmutables
+    let current = self::metadata(env)?;
+    if metadata.decimals != current.decimals {
+        return Err(TokenError::InvalidDecimals);
+    }
+    validate_metadata(&metadata)?;
+    
+    // 2. Guardar metadatos
+    storage::write_metadata(env, &metadata);
+    env.storage().instance().extend_ttl(100_000, 200_000);
+    
+    // 3. Emitir evento con los metadatos nuevos
+    MetadataUpdatedEvent {
+        caller,
+        metadata,
+    }.publish(env);
+    
+    Ok(())
+}
+
+// Métodos de consulta
+
+pub fn balance(env: &Env, id: &Address) -> i128 {
+    storage::read_balance(env, id)
+}
+
+pub fn balances(env: &Env, ids: Vec<Address>) -> Vec<i128> {
+    let mut result = Vec::new(env);
+    for id in ids.iter() {
+        result.push_back(balance(env, &id));
+    }
+    result
+}
+
+pub fn allowance(env: &Env, from: &Address, spender: &Address) -> i128 {
+    storage::read_allowance(env, from, spender).amount
+}
+
+/// Cantidad de spenders con un allowance guardado sobre los tokens de `from`
+/// 
+/// Posiciones de la lista que recorre allowances_of(), incluidos los
+/// allowances vencidos que siguen en storage
+pub fn spender_count(env: &Env, from: &Address) -> Result<u32, TokenError> {
+    require_lists_complete(env)?;
+    Ok(storage::read_spender_count(env, from))
+}
+
+/// Spenders con allowance vigente (> 0 y sin vencer) sobre los tokens de `from`
+/// 
+/// Recorre las posiciones `start..start + limit` de la lista (hasta
+/// MAX_PAGE_SIZE) y omite los allowances vencidos, así que una página puede
+/// traer menos de `limit`. Con la extensión `periodic` incluye también a
+/// los spenders con un allowance periódico sin vencer, aunque ya hayan
+/// gastado el período
+pub fn allowances_of(
+    env: &Env,
+    from: &Address,
+    start: u32,
+    limit: u32
+) -> Result<Vec<Address>, TokenError> {
+    require_lists_complete(env)?;
+    
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE))
+        .min(storage::read_spender_count(env, from));
+    let mut result = Vec::new(env);
+    for index in start..end {
+        if let Some(spender) = storage::read_spender(env, from, index) {
+            if has_allowance(env, from, &spender) {
+                result.push_back(spender);
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Indica si `spender` tiene algún allowance vigente sobre los tokens de `from`
+fn has_allowance(env: &Env, from: &Address, spender: &Address) -> bool {
+    #[cfg(feature = "periodic")]
+    if periodic_allowance(env, from, spender).is_some() {
+        return true;
+    }
+    allowance(env, from, spender) > 0
+}
+
+/// Falla con ListsIncomplete si el contrato se migró desde un esquema
+/// que no llevaba las listas de holders y spenders
+fn require_lists_complete(env: &Env) -> Result<(), TokenError> {
+    if !storage::lists_complete(env) {
+        return Err(TokenError::ListsIncomplete);
+    }
+    Ok(())
+}
+
+pub fn holder_count(env: &Env) -> Result<u32, TokenError> {
+    require_lists_complete(env)?;
+    Ok(storage::read_holder_count(env))
+}
+
+/// Página de holders desde la posición `start` (hasta MAX_PAGE_SIZE)
+/// 
+/// El orden no es estable: cuando un holder queda en 0 el último de la
+/// lista ocupa su posición.
+pub fn holders(env: &Env, start: u32, limit: u32) -> Result<Vec<Address>, TokenError> {
+    require_lists_complete(env)?;
+    
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE))
+        .min(storage::read_holder_count(env));
+    let mut result = Vec::new(env);
+    for index in start..end {
+        if let Some(holder) = storage::read_holder(env, index) {
+            result.push_back(holder);
+        }
+    }
+    Ok(result)
+}
+
+pub fn total_supply(env: &Env) -> i128 {
+    storage::read_total_supply(env)
+}
+
+pub fn admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn metadata(env: &Env) -> Result<TokenMetadata, TokenError> {
+    storage::read_metadata(env).ok_or(TokenError::NotInitialized)
+}
+
+/// Nombre del token ("" si no fue inicializado)
+pub fn name(env: &Env) -> String {
+    storage::read_metadata(env)
+        .map_or(String::from_str(env, ""), |metadata| metadata.name)
+}
+
+/// Símbolo del token ("" si no fue inicializado)
+pub fn symbol(env: &Env) -> String {
+    storage::read_metadata(env)
+        .map_or(String::from_str(env, ""), |metadata| metadata.symbol)
+}
+
+/// Decimales del token (0 si no fue inicializado)
+pub fn decimals(env: &Env) -> u32 {
+    storage::read_metadata(env)
+        .map_or(0, |metadata| metadata.decimals)
+}
+
+/// Balance de `id` al cierre de `ledger` (debe ser un ledger pasado)
+pub fn balance_at(env: &Env, id: &Address, ledger: u32) -> Result<i128, TokenError> {
+    require_past_ledger(env, ledger)?;
+    storage::read_balance_at(env, id, ledger)
+}
+
+/// Total supply al cierre de `ledger` (debe ser un ledger pasado)
+pub fn total_supply_at(env: &Env, ledger: u32) -> Result<i128, TokenError> {
+    require_past_ledger(env, ledger)?;
+    storage::read_supply_at(env, ledger)
+}
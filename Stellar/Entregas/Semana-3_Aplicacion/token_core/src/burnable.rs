@@ -0,0 +1,94 @@
+// src/burnable.rs
+use soroban_sdk::{Address, Env};
+
+use crate::base::{burn_balance, spend_allowance};
+use crate::errors::TokenError;
+use crate::events::{BurnEvent, BurnFromEvent, Sep41BurnEvent};
+use crate::storage;
+use crate::{require_authorized, require_initialized, TokenHooks};
+
+// Extensión `burnable`: quema de tokens por el holder o con allowance
+
+/// Verifica inicialización y que el burn no esté pausado
+fn require_burn_enabled(env: &Env) -> Result<(), TokenError> {
+    require_initialized(env)?;
+    
+    #[cfg(feature = "pausable")]
+    crate::pausable::require_not_paused(env, storage::PauseTarget::Burn)?;
+    
+    Ok(())
+}
+
+/// Quema `amount` tokens de `from` (requiere su autorización)
+pub fn burn<H: TokenHooks>(env: &Env, from: Address, amount: i128) -> Result<(), TokenError> {
+    // 1. Verificar inicialización y que la operación no esté pausada
+    require_burn_enabled(env)?;
+    
+    // 2. Requiere autorización del dueño de los tokens
+    from.require_auth();
+    
+    // 3. Validaciones
+    if amount <= 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    
+    // Verificar que la cuenta no esté congelada
+    require_authorized(env, &from)?;
+    
+    // 4. Actualizar balance y total supply
+    let (new_balance, new_total) = burn_balance::<H>(env, &from, amount)?;
+    
+    // 5. Emitir evento detallado y el estándar
+    BurnEvent {
+        from: from.clone(),
+        amount,
+        new_balance,
+        new_supply: new_total,
+    }.publish(env);
+    Sep41BurnEvent { from, amount }.publish(env);
+    
+    Ok(())
+}
+
+/// Quema `amount` tokens de `from` usando el allowance de `spender`
+/// 
+/// Requiere autorización del spender
+pub fn burn_from<H: TokenHooks>(
+    env: &Env,
+    spender: Address,
+    from: Address,
+    amount: i128
+) -> Result<(), TokenError> {
+    // 1. Verificar inicialización y que la operación no esté pausada
+    require_burn_enabled(env)?;
+    
+    // 2. Requiere autorización del spender
+    spender.require_auth();
+    
+    // 3. Validaciones
+    if amount <= 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    
+    // Verificar que la cuenta no esté congelada
+    require_authorized(env, &from)?;
+    
+    // 4. Consumir allowance (los ilimitados no se descuentan)
+    let new_allowance = spend_allowance(env, &from, &spender, amount)?;
+    
+    // 5. Actualizar balance y total supply
+    let (new_balance, new_total) = burn_balance::<H>(env, &from, amount)?;
+    
+    // 6. Emitir evento detallado y el estándar
+    BurnFromEvent {
+        spender,
+        from: from.clone(),
+        amount,
+        new_balance,
+        new_supply: new_total,
+        new_allowance,
+    }.publish(env);
+    Sep41BurnEvent { from, amount }.publish(env);
+    
+    Ok(())
+}
@@ -0,0 +1,70 @@
+// src/capped.rs
+use soroban_sdk::Env;
+
+use crate::errors::TokenError;
+use crate::events::MaxSupplyUpdatedEvent;
+use crate::storage::{self, DataKey};
+use crate::require_admin;
+
+// Extensión `capped`: tope de supply verificable on-chain
+// El tope se fija en initialize() y solo se puede bajar, nunca subir.
+
+/// Guarda el tope inicial (None = sin tope); lo llama initialize()
+pub fn initialize(env: &Env, max_supply: Option<i128>) -> Result<(), TokenError> {
+    // Si se configura debe ser > 0
+    if let Some(max_supply) = max_supply {
+        if max_supply <= 0 {
+            return Err(TokenError::InvalidSupplyCap);
+        }
+        env.storage().instance().set(&DataKey::MaxSupply, &max_supply);
+    }
+    
+    Ok(())
+}
+
+/// Falla con SupplyCapExceeded si `new_total` supera el tope
+pub fn require_within_cap(env: &Env, new_total: i128) -> Result<(), TokenError> {
+    if let Some(max_supply) = storage::read_max_supply(env) {
+        if new_total > max_supply {
+            return Err(TokenError::SupplyCapExceeded);
+        }
+    }
+    
+    Ok(())
+}
+
+pub fn max_supply(env: &Env) -> Option<i128> {
+    storage::read_max_supply(env)
+}
+
+/// Baja el tope (solo admin); nunca por debajo del supply en circulación
+pub fn lower_max_supply(env: &Env, new_max_supply: i128) -> Result<(), TokenError> {
+    // 1. Solo el admin puede cambiar el tope
+    let admin = require_admin(env)?;
+    
+    // 2. El tope solo puede bajar
+    let old_max_supply = storage::read_max_supply(env);
+    if let Some(current) = old_max_supply {
+        if new_max_supply >= current {
+            return Err(TokenError::InvalidSupplyCap);
+        }
+    }
+    
+    // 3. Nunca por debajo del supply en circulación
+    let total = storage::read_total_supply(env);
+    if new_max_supply <= 0 || new_max_supply < total {
+        return Err(TokenError::InvalidSupplyCap);
+    }
+    
+    // 4. Guardar el nuevo tope
+    env.storage().instance().set(&DataKey::MaxSupply, &new_max_supply);
+    
+    // 5. Emitir evento
+    MaxSupplyUpdatedEvent {
+        admin,
+        old_max_supply,
+        new_max_supply,
+    }.publish(env);
+    
+    Ok(())
+}
@@ -0,0 +1,158 @@
+// src/errors.rs
+use soroban_sdk::contracterror;
+
+/// Enum de errores personalizados para el token
+/// 
+/// Compartido por todos los tokens del núcleo: cada código significa lo
+/// mismo en TokenBDB y en WorxToken, aunque algunos solo los use uno.
+/// 
+/// Cada error tiene un código único para debugging en el ledger
+/// Los códigos empiezan en 1 (0 está reservado para "sin error")
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TokenError {
+    /// El contrato ya fue inicializado
+    /// Se lanza si se intenta llamar initialize() dos veces
+    AlreadyInitialized = 1,
+    
+    /// Amount debe ser mayor a 0
+    /// Transferencias, mint, burn, etc. no aceptan 0
+    InvalidAmount = 2,
+    
+    /// Balance insuficiente para la operación
+    /// El usuario no tiene suficientes tokens
+    InsufficientBalance = 3,
+    
+    /// Allowance insuficiente para transfer_from
+    /// El spender no tiene permiso suficiente
+    InsufficientAllowance = 4,
+    
+    /// El contrato no ha sido inicializado
+    /// Todas las operaciones requieren initialize() primero
+    NotInitialized = 5,
+    
+    /// Decimales inválidos (máximo 18)
+    /// Por convención, Stellar usa 7, Ethereum 18
+    InvalidDecimals = 6,
+    
+    /// Overflow en operación aritmética
+    /// checked_add/checked_sub detectó overflow
+    OverflowError = 7,
+    
+    /// Transferencia a sí mismo no permitida
+    /// from == to (optimización de gas)
+    InvalidRecipient = 8,
+    
+    /// Nombre o símbolo inválido (vacío o muy largo)
+    /// Validación de metadatos en initialize()
+    InvalidMetadata = 9,
+    
+    /// Ledger de expiración inválido
+    /// approve() con amount > 0 y expiration_ledger ya pasado
+    InvalidExpiration = 10,
+    
+    /// No hay un admin propuesto pendiente
+    /// accept_admin() sin propose_admin() previo
+    NoPendingAdmin = 11,
+    
+    /// El admin renunció de forma permanente
+    /// mint() y las funciones de admin quedan deshabilitadas
+    AdminRenounced = 12,
+    
+    /// La cuenta no tiene el rol requerido
+    /// Ej: mint() llamado por una cuenta sin rol Minter
+    MissingRole = 13,
+    
+    /// La cuenta ya tiene el rol
+    /// grant_role() sobre un rol ya otorgado
+    RoleAlreadyGranted = 14,
+    
+    /// La cuenta no tiene el rol a revocar
+    /// revoke_role() sobre un rol no otorgado
+    RoleNotGranted = 15,
+    
+    /// La operación está pausada
+    /// transfer/mint/burn durante una pausa (circuit breaker)
+    Paused = 16,
+    
+    /// La cuenta está congelada
+    /// set_authorized(id, false): no puede enviar ni recibir tokens
+    AccountFrozen = 17,
+    
+    /// El token no permite clawback
+    /// Se inicializó con clawback_enabled = false
+    ClawbackDisabled = 18,
+    
+    /// El mint supera el tope de supply
+    /// total_supply + amount > max_supply
+    SupplyCapExceeded = 19,
+    
+    /// Tope de supply inválido
+    /// <= 0, mayor al tope actual o menor al supply en circulación
+    InvalidSupplyCap = 20,
+    
+    /// El storage ya está en la versión de esquema del código
+    /// migrate() solo corre una vez por cada cambio de esquema
+    MigrationNotNeeded = 21,
+    
+    /// Lote vacío o con más destinatarios que MAX_BATCH_SIZE
+    /// transfer_batch() / mint_batch()
+    InvalidBatchSize = 22,
+    
+    /// El historial de checkpoints no cubre ese ledger
+    /// Es anterior a la migración que inició el historial (contratos
+    /// actualizados desde un esquema sin checkpoints)
+    CheckpointUnavailable = 23,
+    
+    /// El ledger consultado todavía no cerró
+    /// balance_at() / total_supply_at() exigen ledger < ledger actual
+    LedgerNotFinalized = 24,
+    
+    /// La cuenta no registró una clave pública para permits
+    /// Debe llamar register_permit_key() primero
+    PermitKeyNotRegistered = 25,
+    
+    /// El permit ya venció
+    /// expiration_ledger < ledger actual
+    PermitExpired = 26,
+    
+    /// El nonce no es el próximo del owner
+    /// Permit ya usado (replay) o fuera de orden
+    InvalidNonce = 27,
+    
+    /// Fee de transferencia inválido
+    /// fee_bps mayor a MAX_FEE_BPS
+    InvalidFee = 28,
+    
+    /// Período de allowance inválido
+    /// approve_periodic() con period_ledgers = 0
+    InvalidPeriod = 29,
+    
+    /// El destinatario exige memo y la transferencia no lo trae
+    /// Usar transfer_with_memo() o una dirección muxed
+    MemoRequired = 30,
+    
+    /// Memo vacío o más largo que MAX_MEMO_LENGTH
+    InvalidMemo = 31,
+    
+    /// El contrato destinatario rechazó los tokens
+    /// on_token_received() devolvió false o falló; la transferencia se revierte
+    TransferRejected = 32,
+    
+    /// Las listas de holders y spenders no están completas
+    /// Contrato migrado desde un esquema < 3: indexar los eventos off-chain
+    ListsIncomplete = 33,
+    
+    /// Lista de holders o spenders inconsistente
+    /// Una cuenta tiene posición en la lista pero el contador está en 0
+    ListCorrupted = 34,
+    
+    /// El monto no alcanza a cubrir el fee de la transferencia
+    /// El fee redondeado hacia arriba se llevaría todo el monto (neto 0)
+    AmountTooSmall = 35,
+    
+    /// Llamada reentrante durante el hook de un receptor
+    /// transfer_and_call() mientras otra está esperando a on_token_received()
+    Reentrancy = 36,
+}
@@ -0,0 +1,244 @@
+// src/events.rs
+use soroban_sdk::{contractevent, Address, BytesN, String};
+
+use crate::storage::{PauseTarget, TokenMetadata};
+#[cfg(feature = "fee")]
+use crate::storage::FeeDestination;
+
+// EVENTOS compartidos por todos los tokens del núcleo
+// Los eventos propios de cada contrato (init, roles, votos, clawback)
+// se declaran en el contrato.
+
+/// `memo` solo viene en mint_with_memo()
+#[contractevent]
+pub struct MintEvent {
+    pub to: Address,
+    pub amount: i128,
+    pub new_balance: i128,
+    pub new_supply: i128,
+    pub memo: Option<String>,
+}
+
+#[contractevent]
+pub struct BurnEvent {
+    pub from: Address,
+    pub amount: i128,
+    pub new_balance: i128,
+    pub new_supply: i128,
+}
+
+/// `fee` es 0 y `net_amount` igual a `amount` en tokens sin fee
+/// 
+/// `memo` solo viene en transfer_with_memo()
+#[contractevent]
+pub struct TransferEvent {
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub fee: i128,
+    pub net_amount: i128,
+    pub from_new_balance: i128,
+    pub to_new_balance: i128,
+    pub memo: Option<String>,
+}
+
+#[contractevent]
+pub struct TransferFromEvent {
+    pub spender: Address,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub fee: i128,
+    pub net_amount: i128,
+    pub from_new_balance: i128,
+    pub to_new_balance: i128,
+    pub new_allowance: i128,
+    pub memo: Option<String>,
+}
+
+#[contractevent]
+pub struct BurnFromEvent {
+    pub spender: Address,
+    pub from: Address,
+    pub amount: i128,
+    pub new_balance: i128,
+    pub new_supply: i128,
+    pub new_allowance: i128,
+}
+
+#[contractevent]
+pub struct ApproveEvent {
+    pub from: Address,
+    pub spender: Address,
+    pub old_allowance: i128,
+    pub new_allowance: i128,
+    pub expiration_ledger: u32,
+}
+
+#[contractevent]
+pub struct AdminProposedEvent {
+    pub admin: Address,
+    pub pending_admin: Address,
+}
+
+#[contractevent]
+pub struct AdminTransferredEvent {
+    pub previous_admin: Address,
+    pub new_admin: Address,
+}
+
+#[contractevent]
+pub struct AdminRenouncedEvent {
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct PausedEvent {
+    pub target: PauseTarget,
+    pub caller: Address,
+}
+
+#[contractevent]
+pub struct UnpausedEvent {
+    pub target: PauseTarget,
+    pub caller: Address,
+}
+
+#[contractevent]
+pub struct SetAuthorizedEvent {
+    pub admin: Address,
+    pub id: Address,
+    pub authorize: bool,
+}
+
+#[contractevent]
+pub struct MaxSupplyUpdatedEvent {
+    pub admin: Address,
+    pub old_max_supply: Option<i128>,
+    pub new_max_supply: i128,
+}
+
+#[contractevent]
+pub struct UpgradedEvent {
+    pub admin: Address,
+    pub new_wasm_hash: BytesN<32>,
+}
+
+/// `caller` es quien autorizó el cambio (admin o rol, según el contrato)
+#[contractevent]
+pub struct MetadataUpdatedEvent {
+    pub caller: Address,
+    pub metadata: TokenMetadata,
+}
+
+#[contractevent]
+pub struct MigratedEvent {
+    pub admin: Address,
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+#[contractevent]
+pub struct MemoRequiredEvent {
+    pub account: Address,
+    pub required: bool,
+}
+
+// EVENTOS ESTÁNDAR SEP-41 (CAP-67)
+// Mismos topics y data que el Stellar Asset Contract, para que wallets,
+// exploradores e indexers lean el token sin conocer los structs de arriba.
+// Se publican siempre junto al evento detallado de la misma operación y
+// reflejan el movimiento real de balances (el neto y el fee por separado).
+
+/// topics `["transfer", from, to]`, data `amount`
+#[contractevent(topics = ["transfer"], data_format = "single-value")]
+pub struct Sep41TransferEvent {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
+
+/// Transfer a una dirección muxed: data `{ amount, to_muxed_id }`
+#[contractevent(topics = ["transfer"])]
+pub struct Sep41TransferMuxedEvent {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+    pub to_muxed_id: u64,
+}
+
+/// topics `["mint", to]`, data `amount`
+#[contractevent(topics = ["mint"], data_format = "single-value")]
+pub struct Sep41MintEvent {
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
+
+/// topics `["burn", from]`, data `amount`
+#[contractevent(topics = ["burn"], data_format = "single-value")]
+pub struct Sep41BurnEvent {
+    #[topic]
+    pub from: Address,
+    pub amount: i128,
+}
+
+/// topics `["approve", from, spender]`, data `[amount, expiration_ledger]`
+#[contractevent(topics = ["approve"], data_format = "vec")]
+pub struct Sep41ApproveEvent {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// topics `["clawback", from]`, data `amount`
+#[contractevent(topics = ["clawback"], data_format = "single-value")]
+pub struct Sep41ClawbackEvent {
+    #[topic]
+    pub from: Address,
+    pub amount: i128,
+}
+
+#[cfg(feature = "fee")]
+#[contractevent]
+pub struct TransferFeeUpdatedEvent {
+    pub admin: Address,
+    pub fee_bps: u32,
+    pub destination: FeeDestination,
+}
+
+#[cfg(feature = "fee")]
+#[contractevent]
+pub struct FeeExemptionEvent {
+    pub admin: Address,
+    pub account: Address,
+    pub exempt: bool,
+}
+
+#[cfg(feature = "fee")]
+#[contractevent]
+pub struct FeeCollectedEvent {
+    pub from: Address,
+    pub fee: i128,
+    /// None si el fee se quemó
+    pub treasury: Option<Address>,
+    pub new_supply: i128,
+}
+
+/// `limit` = 0 revoca el allowance periódico
+#[cfg(feature = "periodic")]
+#[contractevent]
+pub struct PeriodicApproveEvent {
+    pub from: Address,
+    pub spender: Address,
+    pub limit: i128,
+    pub period_ledgers: u32,
+    pub expiration_ledger: u32,
+}
@@ -0,0 +1,196 @@
+// src/fee.rs
+use soroban_sdk::{Address, Env};
+
+use crate::errors::TokenError;
+use crate::events::{
+    FeeCollectedEvent, FeeExemptionEvent, Sep41BurnEvent, Sep41TransferEvent,
+    TransferFeeUpdatedEvent,
+};
+use crate::storage::{self, FeeConfig, FeeDestination, TransferQuote};
+use crate::{require_admin, TokenHooks};
+
+// Extensión `fee`: fee sobre transferencias
+// 
+// transfer(), transfer_from() y transfer_batch() descuentan un fee en
+// basis points del monto enviado: el destinatario recibe el neto. El fee
+// va a la tesorería o se quema. Las cuentas exentas (y la tesorería) no
+// pagan fee ni como origen ni como destino.
+
+/// Fee máximo por transferencia: 10% (en basis points)
+pub const MAX_FEE_BPS: u32 = 1_000;
+/// Basis points que representan el 100%
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Fee de `amount` según la configuración (sin considerar exenciones)
+/// 
+/// Redondea hacia arriba: con fee_bps > 0 toda transferencia paga al menos
+/// 1 stroop, así que partir una transferencia en montos chicos no evita el fee
+fn fee_for(config: &FeeConfig, amount: i128) -> Result<i128, TokenError> {
+    let fee = amount.checked_mul(config.fee_bps as i128)
+        .and_then(|fee| fee.checked_add(BPS_DENOMINATOR - 1))
+        .ok_or(TokenError::OverflowError)?;
+    Ok(fee / BPS_DENOMINATOR)
+}
+
+/// Fee de transferir `amount` de `from` a `to`
+/// 
+/// 0 si no hay fee configurado o si alguna cuenta está exenta. Falla con
+/// AmountTooSmall si el fee se llevaría todo el monto: el destinatario
+/// siempre recibe un neto positivo
+pub fn transfer_fee(env: &Env, from: &Address, to: &Address, amount: i128) -> Result<i128, TokenError> {
+    let config = match storage::read_fee_config(env) {
+        Some(config) => config,
+        None => return Ok(0),
+    };
+    
+    let exempt = |id: &Address| {
+        storage::is_fee_exempt(env, id)
+            || config.destination == FeeDestination::Treasury(id.clone())
+    };
+    if exempt(from) || exempt(to) {
+        return Ok(0);
+    }
+    
+    let fee = fee_for(&config, amount)?;
+    if fee >= amount {
+        return Err(TokenError::AmountTooSmall);
+    }
+    
+    Ok(fee)
+}
+
+/// Envía el fee ya descontado a `from` a la tesorería o lo quema
+/// 
+/// Se llama después de escribir los balances de la transferencia
+pub fn collect_transfer_fee<H: TokenHooks>(
+    env: &Env,
+    from: &Address,
+    fee: i128
+) -> Result<(), TokenError> {
+    if fee == 0 {
+        return Ok(());
+    }
+    
+    let config = storage::read_fee_config(env)
+        .ok_or(TokenError::NotInitialized)?;
+    let total = storage::read_total_supply(env);
+    
+    let treasury = match config.destination {
+        // 1a. Acreditar a la tesorería
+        FeeDestination::Treasury(treasury) => {
+            let new_balance = storage::read_balance(env, &treasury).checked_add(fee)
+                .ok_or(TokenError::OverflowError)?;
+            storage::write_balance(env, &treasury, new_balance)?;
+            Some(treasury)
+        }
+        // 1b. Quemar: el fee sale del total supply
+        FeeDestination::Burn => {
+            storage::write_total_supply(env, total - fee);
+            None
+        }
+    };
+    H::on_balance_moved(env, Some(from), treasury.as_ref(), fee);
+    
+    // 2. Emitir evento detallado y el estándar (transfer a la tesorería o burn)
+    FeeCollectedEvent {
+        from: from.clone(),
+        fee,
+        new_supply: if treasury.is_some() { total } else { total - fee },
+        treasury: treasury.clone(),
+    }.publish(env);
+    match treasury {
+        Some(treasury) => Sep41TransferEvent {
+            from: from.clone(),
+            to: treasury,
+            amount: fee,
+        }.publish(env),
+        None => Sep41BurnEvent {
+            from: from.clone(),
+            amount: fee,
+        }.publish(env),
+    }
+    
+    Ok(())
+}
+
+/// Configura el fee (solo admin); 0 lo desactiva
+/// 
+/// Falla con InvalidFee si supera MAX_FEE_BPS
+pub fn set_transfer_fee(
+    env: &Env,
+    fee_bps: u32,
+    destination: FeeDestination
+) -> Result<(), TokenError> {
+    // 1. Solo el admin configura el fee
+    let admin = require_admin(env)?;
+    
+    // 2. Validar el tope
+    if fee_bps > MAX_FEE_BPS {
+        return Err(TokenError::InvalidFee);
+    }
+    
+    // 3. Guardar configuración
+    storage::write_fee_config(env, &FeeConfig {
+        fee_bps,
+        destination: destination.clone(),
+    });
+    
+    // 4. Emitir evento
+    TransferFeeUpdatedEvent {
+        admin,
+        fee_bps,
+        destination,
+    }.publish(env);
+    
+    Ok(())
+}
+
+/// Marca o desmarca `account` como exenta del fee (solo admin)
+pub fn set_fee_exempt(env: &Env, account: Address, exempt: bool) -> Result<(), TokenError> {
+    // 1. Solo el admin gestiona exenciones
+    let admin = require_admin(env)?;
+    
+    // 2. Actualizar el flag (se elimina la key al quitar la exención)
+    storage::write_fee_exempt(env, &account, exempt);
+    
+    // 3. Emitir evento
+    FeeExemptionEvent {
+        admin,
+        account,
+        exempt,
+    }.publish(env);
+    
+    Ok(())
+}
+
+/// Configuración actual del fee (None si nunca se configuró)
+pub fn fee_config(env: &Env) -> Option<FeeConfig> {
+    storage::read_fee_config(env)
+}
+
+/// Indica si `account` está exenta del fee
+pub fn is_fee_exempt(env: &Env, account: &Address) -> bool {
+    storage::is_fee_exempt(env, account)
+}
+
+/// Fee y neto de transferir `amount` de `from` a `to`
+/// 
+/// Aplica las mismas exenciones que transfer() (cuentas exentas y tesorería)
+pub fn quote_transfer(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    amount: i128
+) -> Result<TransferQuote, TokenError> {
+    if amount <= 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    
+    let fee = transfer_fee(env, from, to, amount)?;
+    
+    Ok(TransferQuote {
+        amount,
+        fee,
+        net_amount: amount - fee,
+    })
+}
@@ -0,0 +1,139 @@
+// src/lib.rs
+#![no_std]
+
+// Núcleo compartido de los tokens SEP-41 del proyecto (TokenBDB, WorxToken)
+//
+// No es un contrato: cada token declara su #[contract] y sus traits, y
+// delega en estas funciones. Las extensiones opcionales se eligen con
+// features de cargo:
+// - mintable: mint() y mint_batch()
+// - burnable: burn() y burn_from()
+// - pausable: pausas por operación y modo "redeem only"
+// - capped:   tope de supply (implica mintable)
+// - fee:      fee sobre transferencias hacia tesorería o quema
+// - periodic: allowances con un tope por período que se renueva solo
+
+use soroban_sdk::{Address, Env};
+
+pub mod admin;
+pub mod base;
+#[cfg(feature = "burnable")]
+pub mod burnable;
+#[cfg(feature = "capped")]
+pub mod capped;
+pub mod errors;
+pub mod events;
+#[cfg(feature = "fee")]
+pub mod fee;
+#[cfg(feature = "mintable")]
+pub mod mintable;
+#[cfg(feature = "pausable")]
+pub mod pausable;
+#[cfg(feature = "periodic")]
+pub mod periodic;
+pub mod storage;
+
+pub use errors::TokenError;
+
+/// Constantes de configuración
+pub const MAX_DECIMALS: u32 = 18;
+pub const MAX_NAME_LENGTH: u32 = 100;
+pub const MAX_SYMBOL_LENGTH: u32 = 32;
+pub const MAX_DESCRIPTION_LENGTH: u32 = 500;
+pub const MAX_URL_LENGTH: u32 = 256;
+/// Máximo de entradas en `TokenMetadata::extra`
+pub const MAX_METADATA_ENTRIES: u32 = 10;
+/// Máximo de destinatarios por operación en lote
+/// 
+/// Cada destinatario son dos ledger entries escritas (su balance y su
+/// historial de checkpoints), dos más si es un holder nuevo (su lugar en
+/// la lista de holders) y las que agregue el hook del contrato (ej: los
+/// votos de su delegado); el límite deja margen respecto al máximo de
+/// escrituras por transacción de la red.
+pub const MAX_BATCH_SIZE: u32 = 10;
+/// Máximo de caracteres del memo de transfer_with_memo() / mint_with_memo()
+pub const MAX_MEMO_LENGTH: u32 = 64;
+/// Máximo de holders por página en holders()
+pub const MAX_PAGE_SIZE: u32 = 50;
+/// Allowance ilimitado
+/// 
+/// transfer_from() y burn_from() no lo descuentan ni reescriben su key
+pub const UNLIMITED_ALLOWANCE: i128 = i128::MAX;
+
+/// Versión del esquema de storage que espera este código
+/// 
+/// Incrementarla solo cuando cambie el layout de DataKey o de los
+/// valores guardados, y agregar el paso correspondiente en migrate().
+/// 
+/// - v2: metadatos en `DataKey::Metadata`
+/// - v3: historiales de checkpoints con `DataKey::HistoryStart` y listas de
+///   holders y spenders (`DataKey::ListsIncomplete` si se migró)
+pub const STORAGE_VERSION: u32 = 3;
+
+/// Puntos de extensión del contrato sobre las operaciones del núcleo
+/// 
+/// Las funciones que mueven balances son genéricas sobre el hook del
+/// contrato. Un contrato sin lógica extra implementa el trait vacío.
+pub trait TokenHooks {
+    /// Se llama después de cada cambio de balance, ya escrito en storage
+    /// 
+    /// None representa un mint (sin origen) o un burn (sin destino).
+    /// TokenBDB lo usa para mover poder de voto entre delegados.
+    fn on_balance_moved(
+        _env: &Env,
+        _from: Option<&Address>,
+        _to: Option<&Address>,
+        _amount: i128
+    ) {
+    }
+}
+
+/// Falla con NotInitialized si initialize() no se llamó
+pub fn require_initialized(env: &Env) -> Result<(), TokenError> {
+    if !env.storage().instance().has(&storage::DataKey::Initialized) {
+        return Err(TokenError::NotInitialized);
+    }
+    
+    Ok(())
+}
+
+/// Lee el admin actual y exige su autorización
+/// 
+/// Falla con AdminRenounced si el admin ya renunció
+pub fn require_admin(env: &Env) -> Result<Address, TokenError> {
+    require_initialized(env)?;
+    
+    if env.storage().instance().has(&storage::DataKey::AdminRenounced) {
+        return Err(TokenError::AdminRenounced);
+    }
+    
+    let admin: Address = env.storage().instance()
+        .get(&storage::DataKey::Admin)
+        .ok_or(TokenError::NotInitialized)?;
+    admin.require_auth();
+    
+    Ok(admin)
+}
+
+/// Falla con AccountFrozen si `id` está congelada
+pub fn require_authorized(env: &Env, id: &Address) -> Result<(), TokenError> {
+    if !storage::is_authorized(env, id) {
+        return Err(TokenError::AccountFrozen);
+    }
+    
+    Ok(())
+}
+
+/// Falla con LedgerNotFinalized si `ledger` no es anterior al actual
+/// 
+/// Dentro del ledger actual el valor todavía puede cambiar
+pub fn require_past_ledger(env: &Env, ledger: u32) -> Result<(), TokenError> {
+    if ledger >= env.ledger().sequence() {
+        return Err(TokenError::LedgerNotFinalized);
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,141 @@
+// src/mintable.rs
+use soroban_sdk::{Address, Env, String, Vec};
+
+use crate::base::validate_memo;
+use crate::errors::TokenError;
+use crate::events::{MintEvent, Sep41MintEvent};
+use crate::storage::{self, DataKey};
+use crate::{require_authorized, TokenHooks, MAX_BATCH_SIZE};
+#[cfg(feature = "capped")]
+use crate::capped::require_within_cap;
+
+// Extensión `mintable`: creación de tokens
+// El contrato verifica quién puede mintear (admin o rol Minter) antes
+// de llamar a estas funciones.
+
+/// Validaciones comunes de mint() y mint_batch()
+fn require_mint_enabled(env: &Env) -> Result<(), TokenError> {
+    // Si el admin renunció, el mint queda deshabilitado para todos
+    if env.storage().instance().has(&DataKey::AdminRenounced) {
+        return Err(TokenError::AdminRenounced);
+    }
+    
+    // Verificar que el mint no esté pausado
+    #[cfg(feature = "pausable")]
+    crate::pausable::require_not_paused(env, storage::PauseTarget::Mint)?;
+    
+    Ok(())
+}
+
+/// Sin la extensión `capped` el supply no tiene tope
+#[cfg(not(feature = "capped"))]
+fn require_within_cap(_env: &Env, _new_total: i128) -> Result<(), TokenError> {
+    Ok(())
+}
+
+/// Acredita `amount` nuevos tokens a `to`
+pub fn mint<H: TokenHooks>(env: &Env, to: Address, amount: i128) -> Result<(), TokenError> {
+    mint_with_memo::<H>(env, to, amount, None)
+}
+
+/// mint() con una referencia que viaja en MintEvent
+pub fn mint_with_memo<H: TokenHooks>(
+    env: &Env,
+    to: Address,
+    amount: i128,
+    memo: Option<String>
+) -> Result<(), TokenError> {
+    // 1. Verificar que el mint esté habilitado
+    require_mint_enabled(env)?;
+    
+    // 2. Validaciones
+    if amount <= 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    validate_memo(&memo)?;
+    
+    // Verificar que el destinatario no esté congelado
+    require_authorized(env, &to)?;
+    
+    // 3. Obtener balance actual y verificar overflow
+    let new_balance = storage::read_balance(env, &to).checked_add(amount)
+        .ok_or(TokenError::OverflowError)?;
+    
+    // 4. Calcular nuevo supply y verificar el tope (si está configurado)
+    let new_total = storage::read_total_supply(env).checked_add(amount)
+        .ok_or(TokenError::OverflowError)?;
+    require_within_cap(env, new_total)?;
+    
+    // 5. Actualizar balance y total supply (con sus checkpoints)
+    storage::write_balance(env, &to, new_balance)?;
+    storage::write_total_supply(env, new_total);
+    H::on_balance_moved(env, None, Some(&to), amount);
+    
+    // 6. Emitir evento detallado y el estándar
+    MintEvent {
+        to: to.clone(),
+        amount,
+        new_balance,
+        new_supply: new_total,
+        memo,
+    }.publish(env);
+    Sep41MintEvent { to, amount }.publish(env);
+    
+    Ok(())
+}
+
+/// Mintea a varios destinatarios; todo o nada
+pub fn mint_batch<H: TokenHooks>(
+    env: &Env,
+    recipients: Vec<(Address, i128)>
+) -> Result<(), TokenError> {
+    // 1. Verificar que el mint esté habilitado
+    require_mint_enabled(env)?;
+    
+    // 2. Validar tamaño del lote
+    if recipients.is_empty() || recipients.len() > MAX_BATCH_SIZE {
+        return Err(TokenError::InvalidBatchSize);
+    }
+    
+    // 3. Validar cada destinatario y calcular el total
+    let mut amount_total: i128 = 0;
+    for (to, amount) in recipients.iter() {
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        require_authorized(env, &to)?;
+        amount_total = amount_total.checked_add(amount)
+            .ok_or(TokenError::OverflowError)?;
+    }
+    
+    // 4. Verificar el tope de supply contra el total del lote
+    let total = storage::read_total_supply(env);
+    let new_total = total.checked_add(amount_total)
+        .ok_or(TokenError::OverflowError)?;
+    require_within_cap(env, new_total)?;
+    
+    // 5. Acreditar a cada destinatario y emitir su evento
+    let mut new_supply = total;
+    for (to, amount) in recipients.iter() {
+        new_supply += amount;
+        let new_balance = storage::read_balance(env, &to).checked_add(amount)
+            .ok_or(TokenError::OverflowError)?;
+        
+        storage::write_balance(env, &to, new_balance)?;
+        H::on_balance_moved(env, None, Some(&to), amount);
+        
+        MintEvent {
+            to: to.clone(),
+            amount,
+            new_balance,
+            new_supply,
+            memo: None,
+        }.publish(env);
+        Sep41MintEvent { to, amount }.publish(env);
+    }
+    
+    // 6. Actualizar total supply una sola vez
+    storage::write_total_supply(env, new_total);
+    
+    Ok(())
+}
@@ -0,0 +1,51 @@
+// src/pausable.rs
+use soroban_sdk::{Address, Env};
+
+use crate::errors::TokenError;
+use crate::events::{PausedEvent, UnpausedEvent};
+use crate::storage::{self, PauseTarget};
+
+// Extensión `pausable`: pausas por operación y modo "redeem only"
+// El contrato verifica quién puede pausar (admin o rol Pauser) y pasa
+// esa cuenta como `caller`.
+
+/// Falla con Paused si `target` (o la pausa global) está activo
+pub fn require_not_paused(env: &Env, target: PauseTarget) -> Result<(), TokenError> {
+    if storage::is_paused(env, target) {
+        return Err(TokenError::Paused);
+    }
+    
+    Ok(())
+}
+
+/// Pausa `target`
+pub fn pause(env: &Env, caller: Address, target: PauseTarget) {
+    // 1. Activar el flag
+    storage::set_paused(env, target, true);
+    
+    // 2. Emitir evento
+    PausedEvent { target, caller }.publish(env);
+}
+
+/// Reanuda `target`
+/// 
+/// Reanudar una operación no levanta la pausa global (All)
+pub fn unpause(env: &Env, caller: Address, target: PauseTarget) {
+    // 1. Desactivar el flag
+    storage::set_paused(env, target, false);
+    
+    // 2. Emitir evento
+    UnpausedEvent { target, caller }.publish(env);
+}
+
+/// Pausa transfer y mint; burn queda habilitado
+pub fn set_redeem_only(env: &Env, caller: Address) {
+    for target in [PauseTarget::Transfer, PauseTarget::Mint] {
+        storage::set_paused(env, target, true);
+        PausedEvent { target, caller: caller.clone() }.publish(env);
+    }
+}
+
+pub fn is_paused(env: &Env, target: PauseTarget) -> bool {
+    storage::is_paused(env, target)
+}
@@ -0,0 +1,122 @@
+// src/periodic.rs
+use soroban_sdk::{Address, Env};
+
+use crate::errors::TokenError;
+use crate::events::PeriodicApproveEvent;
+use crate::storage::{self, PeriodicAllowance};
+use crate::require_initialized;
+
+// Extensión `periodic`: allowances que se renuevan cada período
+// 
+// Pensado para nóminas y suscripciones: el spender puede retirar hasta
+// `limit` por período (medido en ledgers) sin que el owner vuelva a
+// aprobar. Mientras exista un allowance periódico vigente, transfer_from()
+// y burn_from() lo usan en lugar del allowance común.
+
+/// Aprueba a `spender` a gastar hasta `limit` por cada `period_ledgers`
+/// 
+/// Requiere autorización de `from`. El primer período empieza en el
+/// ledger actual; `limit` = 0 revoca el allowance periódico.
+pub fn approve_periodic(
+    env: &Env,
+    from: Address,
+    spender: Address,
+    limit: i128,
+    period_ledgers: u32,
+    expiration_ledger: u32
+) -> Result<(), TokenError> {
+    // 1. Verificar inicialización
+    require_initialized(env)?;
+    
+    // 2. Verificar autorización del owner
+    from.require_auth();
+    
+    // 3. Validaciones (permitir limit = 0 para revocar)
+    if limit < 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    if limit > 0 && period_ledgers == 0 {
+        return Err(TokenError::InvalidPeriod);
+    }
+    if limit > 0 && expiration_ledger < env.ledger().sequence() {
+        return Err(TokenError::InvalidExpiration);
+    }
+    
+    // 4. Guardar el allowance con el período arrancando ahora
+    if limit == 0 {
+        storage::remove_periodic_allowance(env, &from, &spender)?;
+    } else {
+        storage::write_periodic_allowance(env, &from, &spender, &PeriodicAllowance {
+            limit,
+            period_ledgers,
+            period_start: env.ledger().sequence(),
+            spent: 0,
+            expiration_ledger,
+        })?;
+    }
+    
+    // 5. Emitir evento
+    PeriodicApproveEvent {
+        from,
+        spender,
+        limit,
+        period_ledgers,
+        expiration_ledger,
+    }.publish(env);
+    
+    Ok(())
+}
+
+/// Avanza `allowance` al período que contiene el ledger actual
+/// 
+/// Si ya terminó el período guardado, `spent` vuelve a 0. Los períodos
+/// son fijos desde la aprobación: no se corren por gastar tarde.
+fn roll_period(env: &Env, mut allowance: PeriodicAllowance) -> PeriodicAllowance {
+    let elapsed = env.ledger().sequence() - allowance.period_start;
+    if elapsed >= allowance.period_ledgers {
+        allowance.period_start += elapsed - elapsed % allowance.period_ledgers;
+        allowance.spent = 0;
+    }
+    allowance
+}
+
+/// Allowance periódico vigente, con el período al día
+pub fn periodic_allowance(
+    env: &Env,
+    from: &Address,
+    spender: &Address
+) -> Option<PeriodicAllowance> {
+    storage::read_periodic_allowance(env, from, spender)
+        .map(|allowance| roll_period(env, allowance))
+}
+
+/// Lo que `spender` todavía puede gastar en el período actual
+/// 
+/// 0 si no hay allowance periódico o si ya expiró
+pub fn spendable_allowance(env: &Env, from: &Address, spender: &Address) -> i128 {
+    periodic_allowance(env, from, spender)
+        .map_or(0, |allowance| allowance.limit - allowance.spent)
+}
+
+/// Descuenta `amount` del período actual y devuelve lo que queda
+/// 
+/// None si no hay allowance periódico vigente (se usa el común)
+pub fn spend_periodic_allowance(
+    env: &Env,
+    from: &Address,
+    spender: &Address,
+    amount: i128
+) -> Result<Option<i128>, TokenError> {
+    let mut allowance = match periodic_allowance(env, from, spender) {
+        Some(allowance) => allowance,
+        None => return Ok(None),
+    };
+    
+    if allowance.limit - allowance.spent < amount {
+        return Err(TokenError::InsufficientAllowance);
+    }
+    allowance.spent += amount;
+    storage::write_periodic_allowance(env, from, spender, &allowance)?;
+    
+    Ok(Some(allowance.limit - allowance.spent))
+}
@@ -0,0 +1,835 @@
+// src/storage.rs
+use soroban_sdk::{contracttype, Address, Env, IntoVal, Map, String, Val, Vec};
+
+use crate::errors::TokenError;
+
+/// Enum que define las claves de almacenamiento del núcleo
+/// 
+/// Cada contrato agrega las claves de sus extensiones propias (roles,
+/// votos, clawback) en un enum aparte. Las keys se serializan por nombre
+/// de variante, así que mover una variante entre enums no cambia su key.
+/// 
+/// Separamos los datos en dos tipos de storage:
+/// - Instance Storage: Metadatos globales (más barato)
+/// - Persistent Storage: Datos de usuarios (requiere TTL)
+#[contracttype]
+pub enum DataKey {
+    /// Balance de cada usuario - Persistent Storage
+    /// Usa Address como key para acceso O(1)
+    Balance(Address),
+    
+    /// Permisos de gasto entre usuarios - Persistent Storage
+    /// Tupla (owner, spender) para lookup eficiente
+    /// Guarda un `AllowanceValue` (monto + ledger de expiración)
+    Allowance(Address, Address),
+    
+    /// Permiso de gasto que se renueva cada período - Persistent Storage
+    /// Tupla (owner, spender); guarda un `PeriodicAllowance`
+    /// Solo existe mientras está aprobado (extensión `periodic`)
+    PeriodicAllowance(Address, Address),
+    
+    /// Supply total de tokens - Instance Storage
+    /// Contador global de tokens en circulación
+    TotalSupply,
+    
+    /// Dirección del administrador - Instance Storage
+    /// Solo esta cuenta puede mintear tokens
+    Admin,
+    
+    /// Nombre del token - Instance Storage (esquema v1)
+    /// Desde el esquema v2 vive en `Metadata`; migrate() la elimina
+    /// Ejemplo: "Buen Día Builders Token"
+    TokenName,
+    
+    /// Símbolo del token - Instance Storage (esquema v1)
+    /// Ejemplo: "BDB", "WORX", "USDC", etc (máximo 32 caracteres)
+    TokenSymbol,
+    
+    /// Número de decimales - Instance Storage (esquema v1)
+    /// Típicamente 7 para Stellar (alineado con XLM)
+    Decimals,
+    
+    /// Metadatos del token - Instance Storage
+    /// `TokenMetadata` completo; reemplaza a TokenName, TokenSymbol y Decimals
+    Metadata,
+    
+    /// Flag para verificar inicialización - Instance Storage
+    /// Previene re-inicialización del contrato
+    Initialized,
+    
+    /// Admin propuesto pendiente de aceptar - Instance Storage
+    /// Se borra en accept_admin() o renounce_admin()
+    PendingAdmin,
+    
+    /// Flag de renuncia del admin - Instance Storage
+    /// Una vez escrito no se borra: el mint queda deshabilitado
+    AdminRenounced,
+    
+    /// Flag de pausa por operación - Instance Storage
+    /// Solo existe mientras la pausa está activa
+    Paused(PauseTarget),
+    
+    /// Cuenta congelada - Persistent Storage
+    /// Solo existe mientras la cuenta está congelada
+    Frozen(Address),
+    
+    /// Tope de supply - Instance Storage
+    /// Solo existe si el token tiene tope; solo puede bajar
+    MaxSupply,
+    
+    /// Versión del esquema de storage - Instance Storage
+    /// La escribe initialize() y la actualiza migrate()
+    StorageVersion,
+    
+    /// Historial de balances de una cuenta - Persistent Storage
+    /// `History`; las páginas llenas van en la key (BalanceHistory(id), n)
+    BalanceHistory(Address),
+    
+    /// Historial del total supply - Persistent Storage
+    /// `History`; las páginas llenas van en la key (SupplyHistory, n)
+    SupplyHistory,
+    
+    /// Ledger desde el que los historiales están completos - Instance Storage
+    /// Solo existe en contratos migrados desde un esquema sin historial;
+    /// antes de ese ledger no se puede reconstruir ningún valor
+    HistoryStart,
+    
+    /// Configuración del fee de transferencia - Instance Storage
+    /// Solo existe si el admin configuró un fee (extensión `fee`)
+    FeeConfig,
+    
+    /// Cuenta exenta del fee - Persistent Storage
+    /// Solo existe mientras la exención está activa (extensión `fee`)
+    FeeExempt(Address),
+    
+    /// Cantidad de cuentas con balance > 0 - Instance Storage
+    HolderCount,
+    
+    /// Holder en la posición `i` de la lista - Persistent Storage
+    /// Posiciones 0..HolderCount; al salir un holder se mueve el último a su lugar
+    Holder(u32),
+    
+    /// Posición de una cuenta en la lista de holders - Persistent Storage
+    /// Solo existe mientras la cuenta tiene balance > 0
+    HolderIndex(Address),
+    
+    /// Cantidad de spenders con allowance (común o periódico) de una cuenta - Persistent Storage
+    /// Solo existe si hay al menos un allowance guardado
+    SpenderCount(Address),
+    
+    /// Spender en la posición `i` de la lista de una cuenta - Persistent Storage
+    /// Posiciones 0..SpenderCount; al salir un spender se mueve el último a su lugar
+    Spender(Address, u32),
+    
+    /// Posición de un spender en la lista de una cuenta - Persistent Storage
+    /// Tupla (owner, spender); solo existe mientras tiene un allowance guardado
+    SpenderIndex(Address, Address),
+    
+    /// Las listas de holders y spenders están incompletas - Instance Storage
+    /// Solo existe en contratos migrados desde un esquema < 3, que no las llevaban
+    ListsIncomplete,
+    
+    /// La cuenta exige memo en las transferencias que recibe - Persistent Storage
+    /// Solo existe mientras el flag está activo
+    MemoRequired(Address),
+}
+
+/// Checkpoints por página de un historial
+/// 
+/// Los historiales no se podan (un snapshot de votación tiene que poder
+/// consultarse aunque la cuenta se mueva en cada ledger). Cada entry
+/// queda acotada a una página y cada cambio escribe una sola entry, dos
+/// cuando la página en curso se llena y se archiva.
+pub const CHECKPOINTS_PER_PAGE: u32 = 32;
+
+/// Clases de operación que se pueden pausar
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PauseTarget {
+    /// Pausa global: todas las operaciones
+    All,
+    /// transfer() y transfer_from()
+    Transfer,
+    /// mint()
+    Mint,
+    /// burn() y burn_from()
+    Burn,
+}
+
+/// Metadata struct para almacenar información del token
+/// Se guarda completa en `DataKey::Metadata`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+    /// Descripción libre del proyecto
+    pub description: String,
+    /// URL del ícono del token
+    pub icon_url: String,
+    /// Dominio que publica el stellar.toml del emisor (SEP-1)
+    pub home_domain: String,
+    /// Metadatos adicionales (ej: "website", "twitter")
+    pub extra: Map<String, String>,
+}
+
+/// Valor vigente desde un ledger (balance o total supply)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub value: i128,
+}
+
+/// Historial de checkpoints de un valor (balance, supply, votos)
+/// 
+/// La key del historial guarda la página en curso; las anteriores, de
+/// CHECKPOINTS_PER_PAGE checkpoints cada una, viven en `(key, 0..pages)`
+/// en orden de ledger.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct History {
+    pub pages: u32,
+    pub recent: Vec<Checkpoint>,
+}
+
+/// Valor almacenado en `DataKey::Allowance`
+///
+/// SEP-41: cada allowance expira en un ledger concreto. A partir de
+/// `expiration_ledger + 1` el allowance vale 0 aunque siga en storage.
+#[contracttype]
+#[derive(Clone)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// Valor almacenado en `DataKey::PeriodicAllowance`
+/// 
+/// El spender puede gastar hasta `limit` por período de `period_ledgers`
+/// ledgers. `spent` vuelve a 0 al empezar cada período nuevo; el
+/// período vigente empezó en `period_start`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeriodicAllowance {
+    pub limit: i128,
+    pub period_ledgers: u32,
+    pub period_start: u32,
+    pub spent: i128,
+    pub expiration_ledger: u32,
+}
+
+/// Destino del fee de transferencia
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FeeDestination {
+    /// Se acredita a la tesorería
+    Treasury(Address),
+    /// Se quema (reduce el total supply)
+    Burn,
+}
+
+/// Valor almacenado en `DataKey::FeeConfig`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    pub fee_bps: u32,
+    pub destination: FeeDestination,
+}
+
+/// Resultado de quote_transfer(): lo que paga el sender y recibe el destinatario
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferQuote {
+    pub amount: i128,
+    pub fee: i128,
+    pub net_amount: i128,
+}
+
+/// Lee el balance de `id` (0 si nunca recibió tokens)
+pub fn read_balance(env: &Env, id: &Address) -> i128 {
+    env.storage().persistent()
+        .get(&DataKey::Balance(id.clone()))
+        .unwrap_or(0)
+}
+
+/// Guarda el balance de `id` y registra su checkpoint
+/// 
+/// Optimización: elimina la key si el balance queda en 0
+pub fn write_balance(env: &Env, id: &Address, balance: i128) -> Result<(), TokenError> {
+    let previous = read_balance(env, id);
+    let key = DataKey::Balance(id.clone());
+    if balance == 0 {
+        env.storage().persistent().remove(&key);
+        remove_holder(env, id)?;
+    } else {
+        env.storage().persistent().set(&key, &balance);
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+        add_holder(env, id)?;
+    }
+    write_balance_checkpoint(env, id, previous, balance);
+    Ok(())
+}
+
+/// Indica si las listas de holders y spenders están completas
+/// 
+/// false en contratos migrados desde un esquema anterior a las listas:
+/// las cuentas que ya tenían balance o allowance no figuran en ellas
+pub fn lists_complete(env: &Env) -> bool {
+    !env.storage().instance().has(&DataKey::ListsIncomplete)
+}
+
+/// Marca las listas de holders y spenders como incompletas
+/// 
+/// Migración al esquema v3: los balances y allowances anteriores no
+/// tienen posición en las listas y no se pueden enumerar on-chain para
+/// reconstruirlas, así que las consultas de listas quedan deshabilitadas
+pub fn mark_lists_incomplete(env: &Env) {
+    env.storage().instance().set(&DataKey::ListsIncomplete, &true);
+}
+
+/// Cantidad de cuentas con balance > 0
+pub fn read_holder_count(env: &Env) -> u32 {
+    env.storage().instance()
+        .get(&DataKey::HolderCount)
+        .unwrap_or(0)
+}
+
+/// Holder en la posición `index` (None fuera de rango)
+pub fn read_holder(env: &Env, index: u32) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::Holder(index))
+}
+
+/// Guarda `id` en la posición `index` de la lista de holders
+fn write_holder(env: &Env, index: u32, id: &Address) {
+    let key = DataKey::Holder(index);
+    env.storage().persistent().set(&key, id);
+    env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+    
+    let key = DataKey::HolderIndex(id.clone());
+    env.storage().persistent().set(&key, &index);
+    env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+}
+
+/// Agrega `id` al final de la lista de holders si no está
+fn add_holder(env: &Env, id: &Address) -> Result<(), TokenError> {
+    if env.storage().persistent().has(&DataKey::HolderIndex(id.clone())) {
+        return Ok(());
+    }
+    let count = read_holder_count(env);
+    let new_count = count.checked_add(1).ok_or(TokenError::OverflowError)?;
+    write_holder(env, count, id);
+    env.storage().instance().set(&DataKey::HolderCount, &new_count);
+    Ok(())
+}
+
+/// Quita `id` de la lista de holders moviendo el último a su lugar
+/// 
+/// No hace nada si `id` no está en la lista. Falla con ListCorrupted si
+/// la cuenta tiene posición pero el contador está en 0
+fn remove_holder(env: &Env, id: &Address) -> Result<(), TokenError> {
+    let index_key = DataKey::HolderIndex(id.clone());
+    let index: u32 = match env.storage().persistent().get(&index_key) {
+        Some(index) => index,
+        None => return Ok(()),
+    };
+    
+    let last = read_holder_count(env).checked_sub(1)
+        .ok_or(TokenError::ListCorrupted)?;
+    if index != last {
+        if let Some(moved) = read_holder(env, last) {
+            write_holder(env, index, &moved);
+        }
+    }
+    env.storage().persistent().remove(&DataKey::Holder(last));
+    env.storage().persistent().remove(&index_key);
+    env.storage().instance().set(&DataKey::HolderCount, &last);
+    Ok(())
+}
+
+/// Lee el total supply
+pub fn read_total_supply(env: &Env) -> i128 {
+    env.storage().instance()
+        .get(&DataKey::TotalSupply)
+        .unwrap_or(0)
+}
+
+/// Guarda el total supply y registra su checkpoint
+pub fn write_total_supply(env: &Env, supply: i128) {
+    let previous = read_total_supply(env);
+    env.storage().instance().set(&DataKey::TotalSupply, &supply);
+    write_supply_checkpoint(env, previous, supply);
+}
+
+/// Lee el allowance de `spender` sobre los tokens de `from`
+///
+/// Devuelve amount = 0 si no existe o si ya expiró
+pub fn read_allowance(env: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+    match env.storage().persistent().get::<_, AllowanceValue>(&key) {
+        Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+        Some(allowance) => AllowanceValue {
+            amount: 0,
+            expiration_ledger: allowance.expiration_ledger,
+        },
+        None => AllowanceValue {
+            amount: 0,
+            expiration_ledger: 0,
+        },
+    }
+}
+
+/// Guarda el allowance de `spender` sobre los tokens de `from`
+///
+/// Optimización: elimina la key si amount = 0
+pub fn write_allowance(
+    env: &Env,
+    from: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+) -> Result<(), TokenError> {
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(
+            &key,
+            &AllowanceValue {
+                amount,
+                expiration_ledger,
+            },
+        );
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+    }
+    sync_spender(env, from, spender)
+}
+
+/// Cantidad de spenders con un allowance (común o periódico) guardado sobre
+/// los tokens de `from`
+/// 
+/// Incluye los allowances vencidos que siguen en storage
+pub fn read_spender_count(env: &Env, from: &Address) -> u32 {
+    env.storage().persistent()
+        .get(&DataKey::SpenderCount(from.clone()))
+        .unwrap_or(0)
+}
+
+/// Spender de `from` en la posición `index` (None fuera de rango)
+pub fn read_spender(env: &Env, from: &Address, index: u32) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::Spender(from.clone(), index))
+}
+
+/// Guarda `spender` en la posición `index` de la lista de `from`
+fn write_spender(env: &Env, from: &Address, index: u32, spender: &Address) {
+    let key = DataKey::Spender(from.clone(), index);
+    env.storage().persistent().set(&key, spender);
+    env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+    
+    let key = DataKey::SpenderIndex(from.clone(), spender.clone());
+    env.storage().persistent().set(&key, &index);
+    env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+}
+
+/// Guarda la cantidad de spenders de `from` (se elimina la key en 0)
+fn write_spender_count(env: &Env, from: &Address, count: u32) {
+    let key = DataKey::SpenderCount(from.clone());
+    if count == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &count);
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+    }
+}
+
+/// Agrega o quita `spender` de la lista de spenders de `from`
+/// 
+/// Queda listado mientras tenga un allowance común o periódico guardado.
+/// Al salir, el último spender pasa a su posición. Falla con ListCorrupted
+/// si el spender tiene posición pero el contador está en 0
+fn sync_spender(env: &Env, from: &Address, spender: &Address) -> Result<(), TokenError> {
+    let storage = env.storage().persistent();
+    let listed = storage.has(&DataKey::Allowance(from.clone(), spender.clone()))
+        || storage.has(&DataKey::PeriodicAllowance(from.clone(), spender.clone()));
+    let index_key = DataKey::SpenderIndex(from.clone(), spender.clone());
+    
+    match (storage.get::<_, u32>(&index_key), listed) {
+        (None, true) => {
+            let count = read_spender_count(env, from);
+            let new_count = count.checked_add(1).ok_or(TokenError::OverflowError)?;
+            write_spender(env, from, count, spender);
+            write_spender_count(env, from, new_count);
+        }
+        (Some(index), false) => {
+            let last = read_spender_count(env, from).checked_sub(1)
+                .ok_or(TokenError::ListCorrupted)?;
+            if index != last {
+                if let Some(moved) = read_spender(env, from, last) {
+                    write_spender(env, from, index, &moved);
+                }
+            }
+            storage.remove(&DataKey::Spender(from.clone(), last));
+            storage.remove(&index_key);
+            write_spender_count(env, from, last);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Indica si `target` está pausado (directamente o por la pausa global)
+pub fn is_paused(env: &Env, target: PauseTarget) -> bool {
+    env.storage().instance().has(&DataKey::Paused(PauseTarget::All))
+        || env.storage().instance().has(&DataKey::Paused(target))
+}
+
+/// Activa o desactiva la pausa de `target`
+/// 
+/// Optimización: la key se elimina al reanudar
+pub fn set_paused(env: &Env, target: PauseTarget, paused: bool) {
+    if paused {
+        env.storage().instance().set(&DataKey::Paused(target), &true);
+    } else {
+        env.storage().instance().remove(&DataKey::Paused(target));
+    }
+}
+
+/// Indica si `id` está autorizada (no congelada)
+pub fn is_authorized(env: &Env, id: &Address) -> bool {
+    !env.storage().persistent().has(&DataKey::Frozen(id.clone()))
+}
+
+/// Congela o descongela `id`
+/// 
+/// Optimización: la key se elimina al descongelar
+pub fn write_authorized(env: &Env, id: &Address, authorize: bool) {
+    let key = DataKey::Frozen(id.clone());
+    if authorize {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+    }
+}
+
+/// Lee el tope de supply (None = sin tope)
+pub fn read_max_supply(env: &Env) -> Option<i128> {
+    env.storage().instance().get(&DataKey::MaxSupply)
+}
+
+/// Lee la versión del esquema de storage (0 = anterior al versionado)
+pub fn read_storage_version(env: &Env) -> u32 {
+    env.storage().instance().get(&DataKey::StorageVersion).unwrap_or(0)
+}
+
+/// Ledger desde el que los historiales están completos
+/// 
+/// None en contratos que registran checkpoints desde initialize()
+pub fn read_history_start(env: &Env) -> Option<u32> {
+    env.storage().instance().get(&DataKey::HistoryStart)
+}
+
+/// Migración al esquema v3: los historiales empiezan en el ledger actual
+/// 
+/// Los balances anteriores no tienen checkpoints; se registran con el
+/// ledger de la migración en su primer cambio (ver write_checkpoint).
+/// El supply se registra ahora.
+pub fn start_history(env: &Env) {
+    env.storage().instance().set(&DataKey::HistoryStart, &env.ledger().sequence());
+    let supply = read_total_supply(env);
+    write_supply_checkpoint(env, supply, supply);
+}
+
+/// Agrega `value` al historial `key` en el ledger actual
+/// 
+/// Varios cambios en el mismo ledger comparten un solo checkpoint
+/// (queda el último valor). `previous` es el valor antes del cambio: si
+/// el historial está vacío y `previous` no es 0, el valor viene de antes
+/// de la migración y se registra primero en el ledger de HistoryStart.
+/// Es pública para que los contratos lleven historiales propios (ej:
+/// votos) con la misma lógica.
+/// 
+/// Nada se descarta: cuando la página en curso se llena, se archiva en
+/// la entry `(key, n)` y se empieza una nueva.
+pub fn write_checkpoint<K: IntoVal<Env, Val>>(
+    env: &Env,
+    key: &K,
+    previous: i128,
+    value: i128
+) {
+    let ledger = env.ledger().sequence();
+    let mut history: History = env.storage().persistent()
+        .get(key)
+        .unwrap_or(History { pages: 0, recent: Vec::new(env) });
+    
+    if history.pages == 0 && history.recent.is_empty() && previous != 0 {
+        if let Some(start) = read_history_start(env).filter(|start| *start < ledger) {
+            history.recent.push_back(Checkpoint { ledger: start, value: previous });
+        }
+    }
+    
+    match history.recent.last() {
+        Some(last) if last.ledger == ledger => {
+            history.recent.set(history.recent.len() - 1, Checkpoint { ledger, value });
+        }
+        _ => {
+            if history.recent.len() >= CHECKPOINTS_PER_PAGE {
+                let page_key = (key.into_val(env), history.pages);
+                env.storage().persistent().set(&page_key, &history.recent);
+                env.storage().persistent().extend_ttl(&page_key, 100_000, 200_000);
+                history.pages += 1;
+                history.recent = Vec::new(env);
+            }
+            history.recent.push_back(Checkpoint { ledger, value });
+        }
+    }
+    
+    env.storage().persistent().set(key, &history);
+    env.storage().persistent().extend_ttl(key, 100_000, 200_000);
+}
+
+/// Valor del último checkpoint de `checkpoints` con ledger <= `ledger`
+/// 
+/// Búsqueda binaria; None si `ledger` es anterior al primero
+fn find_checkpoint(checkpoints: &Vec<Checkpoint>, ledger: u32) -> Option<i128> {
+    // Índice del primer checkpoint posterior a `ledger`
+    let mut low = 0;
+    let mut high = checkpoints.len();
+    while low < high {
+        let mid = (low + high) / 2;
+        if checkpoints.get_unchecked(mid).ledger <= ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    
+    if low == 0 {
+        return None;
+    }
+    Some(checkpoints.get_unchecked(low - 1).value)
+}
+
+/// Página archivada `page` del historial `key`
+fn read_history_page<K: IntoVal<Env, Val>>(
+    env: &Env,
+    key: &K,
+    page: u32
+) -> Result<Vec<Checkpoint>, TokenError> {
+    env.storage().persistent()
+        .get(&(key.into_val(env), page))
+        .ok_or(TokenError::CheckpointUnavailable)
+}
+
+/// Busca en el historial `key` el valor vigente al cierre de `ledger`
+/// 
+/// Primero en la página en curso y, si `ledger` es anterior, búsqueda
+/// binaria sobre las páginas archivadas (se leen O(log n) entries).
+/// `current` es el valor actual, que aplica si el historial está vacío
+/// (el valor nunca cambió desde que se registran checkpoints). Antes de
+/// HistoryStart, o si el contrato todavía no migró al esquema v3, falla
+/// con CheckpointUnavailable en lugar de inventar un 0.
+pub fn read_checkpoint<K: IntoVal<Env, Val>>(
+    env: &Env,
+    key: &K,
+    ledger: u32,
+    current: i128
+) -> Result<i128, TokenError> {
+    if read_storage_version(env) < 3 {
+        return Err(TokenError::CheckpointUnavailable);
+    }
+    if read_history_start(env).is_some_and(|start| ledger < start) {
+        return Err(TokenError::CheckpointUnavailable);
+    }
+    
+    let history: History = match env.storage().persistent().get(key) {
+        Some(history) => history,
+        None => return Ok(current),
+    };
+    if let Some(value) = find_checkpoint(&history.recent, ledger) {
+        return Ok(value);
+    }
+    
+    // Índice de la primera página que empieza después de `ledger`
+    let mut low = 0;
+    let mut high = history.pages;
+    while low < high {
+        let mid = (low + high) / 2;
+        let first = read_history_page(env, key, mid)?.get_unchecked(0).ledger;
+        if first <= ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    
+    // Anterior al primer checkpoint: era 0
+    if low == 0 {
+        return Ok(0);
+    }
+    Ok(find_checkpoint(&read_history_page(env, key, low - 1)?, ledger).unwrap_or(0))
+}
+
+/// Valor del último checkpoint del historial `key` (None si está vacío)
+pub fn read_last_checkpoint<K: IntoVal<Env, Val>>(env: &Env, key: &K) -> Option<i128> {
+    env.storage().persistent()
+        .get::<_, History>(key)
+        .and_then(|history| history.recent.last())
+        .map(|checkpoint| checkpoint.value)
+}
+
+/// Registra el balance de `id` en el ledger actual
+pub fn write_balance_checkpoint(env: &Env, id: &Address, previous: i128, balance: i128) {
+    write_checkpoint(env, &DataKey::BalanceHistory(id.clone()), previous, balance);
+}
+
+/// Registra el total supply en el ledger actual
+pub fn write_supply_checkpoint(env: &Env, previous: i128, supply: i128) {
+    write_checkpoint(env, &DataKey::SupplyHistory, previous, supply);
+}
+
+/// Balance de `id` al cierre de `ledger`
+pub fn read_balance_at(env: &Env, id: &Address, ledger: u32) -> Result<i128, TokenError> {
+    let current = env.storage().persistent()
+        .get(&DataKey::Balance(id.clone()))
+        .unwrap_or(0);
+    read_checkpoint(env, &DataKey::BalanceHistory(id.clone()), ledger, current)
+}
+
+/// Total supply al cierre de `ledger`
+pub fn read_supply_at(env: &Env, ledger: u32) -> Result<i128, TokenError> {
+    let current = env.storage().instance()
+        .get(&DataKey::TotalSupply)
+        .unwrap_or(0);
+    read_checkpoint(env, &DataKey::SupplyHistory, ledger, current)
+}
+
+/// Lee los metadatos del token (None si no fue inicializado)
+/// 
+/// Si el storage sigue en el esquema v1 (upgrade sin migrate), los arma
+/// desde las keys sueltas con los campos nuevos vacíos.
+pub fn read_metadata(env: &Env) -> Option<TokenMetadata> {
+    if let Some(metadata) = env.storage().instance().get(&DataKey::Metadata) {
+        return Some(metadata);
+    }
+    
+    let name: String = env.storage().instance().get(&DataKey::TokenName)?;
+    Some(TokenMetadata {
+        name,
+        symbol: env.storage().instance()
+            .get(&DataKey::TokenSymbol)
+            .unwrap_or(String::from_str(env, "")),
+        decimals: env.storage().instance()
+            .get(&DataKey::Decimals)
+            .unwrap_or(0),
+        description: String::from_str(env, ""),
+        icon_url: String::from_str(env, ""),
+        home_domain: String::from_str(env, ""),
+        extra: Map::new(env),
+    })
+}
+
+/// Guarda los metadatos del token
+pub fn write_metadata(env: &Env, metadata: &TokenMetadata) {
+    env.storage().instance().set(&DataKey::Metadata, metadata);
+}
+
+/// Migración de esquema v1 -> v2: name, symbol y decimals pasan de
+/// keys sueltas a `DataKey::Metadata`
+pub fn migrate_metadata_v1(env: &Env) {
+    if env.storage().instance().has(&DataKey::Metadata) {
+        return;
+    }
+    
+    if let Some(metadata) = read_metadata(env) {
+        write_metadata(env, &metadata);
+    }
+    env.storage().instance().remove(&DataKey::TokenName);
+    env.storage().instance().remove(&DataKey::TokenSymbol);
+    env.storage().instance().remove(&DataKey::Decimals);
+}
+
+/// Lee el allowance periódico de `spender` sobre los tokens de `from`
+/// 
+/// None si no existe o si ya expiró
+pub fn read_periodic_allowance(
+    env: &Env,
+    from: &Address,
+    spender: &Address,
+) -> Option<PeriodicAllowance> {
+    let key = DataKey::PeriodicAllowance(from.clone(), spender.clone());
+    env.storage().persistent()
+        .get::<_, PeriodicAllowance>(&key)
+        .filter(|allowance| allowance.expiration_ledger >= env.ledger().sequence())
+}
+
+/// Guarda el allowance periódico de `spender` sobre los tokens de `from`
+pub fn write_periodic_allowance(
+    env: &Env,
+    from: &Address,
+    spender: &Address,
+    allowance: &PeriodicAllowance,
+) -> Result<(), TokenError> {
+    let key = DataKey::PeriodicAllowance(from.clone(), spender.clone());
+    env.storage().persistent().set(&key, allowance);
+    env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+    sync_spender(env, from, spender)
+}
+
+/// Elimina el allowance periódico de `spender` sobre los tokens de `from`
+pub fn remove_periodic_allowance(
+    env: &Env,
+    from: &Address,
+    spender: &Address,
+) -> Result<(), TokenError> {
+    env.storage().persistent()
+        .remove(&DataKey::PeriodicAllowance(from.clone(), spender.clone()));
+    sync_spender(env, from, spender)
+}
+
+/// Lee la configuración del fee de transferencia
+pub fn read_fee_config(env: &Env) -> Option<FeeConfig> {
+    env.storage().instance().get(&DataKey::FeeConfig)
+}
+
+/// Guarda la configuración del fee de transferencia
+pub fn write_fee_config(env: &Env, config: &FeeConfig) {
+    env.storage().instance().set(&DataKey::FeeConfig, config);
+}
+
+/// Indica si `id` está exenta del fee
+pub fn is_fee_exempt(env: &Env, id: &Address) -> bool {
+    env.storage().persistent().has(&DataKey::FeeExempt(id.clone()))
+}
+
+/// Marca o desmarca `id` como exenta del fee
+/// 
+/// Optimización: la key se elimina al quitar la exención
+pub fn write_fee_exempt(env: &Env, id: &Address, exempt: bool) {
+    let key = DataKey::FeeExempt(id.clone());
+    if exempt {
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Indica si `id` exige memo en las transferencias que recibe
+pub fn is_memo_required(env: &Env, id: &Address) -> bool {
+    env.storage().persistent().has(&DataKey::MemoRequired(id.clone()))
+}
+
+/// Activa o desactiva el requisito de memo de `id`
+/// 
+/// Optimización: la key se elimina al desactivarlo
+pub fn write_memo_required(env: &Env, id: &Address, required: bool) {
+    let key = DataKey::MemoRequired(id.clone());
+    if required {
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, 100_000, 200_000);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
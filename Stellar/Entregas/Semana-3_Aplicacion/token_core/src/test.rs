@@ -0,0 +1,561 @@
+// src/test.rs
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    contract, contracttype, map, symbol_short,
+    testutils::{Address as _, Events, MuxedAddress as _},
+    vec, Address, Env, IntoVal, Map, MuxedAddress, String, Symbol, TryFromVal, Val, Vec,
+};
+
+use crate::storage::{DataKey, TokenMetadata};
+
+/// Contrato vacío: solo aporta el contexto de storage para los tests
+#[contract]
+struct CoreTestContract;
+
+/// Registro de un cambio de balance recibido por el hook
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Moved {
+    from: Option<Address>,
+    to: Option<Address>,
+    amount: i128,
+}
+
+#[contracttype]
+enum TestKey {
+    Moves,
+}
+
+/// Hook que guarda cada llamada para verificarlas después
+struct RecordingHooks;
+
+impl TokenHooks for RecordingHooks {
+    fn on_balance_moved(
+        env: &Env,
+        from: Option<&Address>,
+        to: Option<&Address>,
+        amount: i128
+    ) {
+        let mut moves: Vec<Moved> = env.storage().instance()
+            .get(&TestKey::Moves)
+            .unwrap_or(Vec::new(env));
+        moves.push_back(Moved { from: from.cloned(), to: to.cloned(), amount });
+        env.storage().instance().set(&TestKey::Moves, &moves);
+    }
+}
+
+fn recorded_moves(env: &Env) -> Vec<Moved> {
+    env.storage().instance()
+        .get(&TestKey::Moves)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Registra el contrato de prueba y lo inicializa con `admin`
+fn setup(env: &Env) -> (Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(CoreTestContract, ());
+    let admin = Address::generate(env);
+    env.as_contract(&contract_id, || {
+        base::initialize(
+            env,
+            &admin,
+            String::from_str(env, "Core Token"),
+            String::from_str(env, "CORE"),
+            7,
+        ).unwrap();
+    });
+    (contract_id, admin)
+}
+
+/// Ejecuta `f` como el contrato de prueba
+/// 
+/// Cada llamada es un frame nuevo: una misma cuenta solo puede
+/// autorizar una vez por frame, igual que en una invocación real.
+fn call<T>(env: &Env, contract_id: &Address, f: impl FnOnce() -> T) -> T {
+    env.as_contract(contract_id, f)
+}
+
+fn sample_metadata(env: &Env) -> TokenMetadata {
+    TokenMetadata {
+        name: String::from_str(env, "Core Token"),
+        symbol: String::from_str(env, "CORE"),
+        decimals: 7,
+        description: String::from_str(env, ""),
+        icon_url: String::from_str(env, ""),
+        home_domain: String::from_str(env, ""),
+        extra: Map::new(env),
+    }
+}
+
+/// Test: initialize() guarda metadatos y no se puede repetir
+#[test]
+fn test_initialize_once() {
+    let env = Env::default();
+    let (contract_id, admin) = setup(&env);
+    
+    env.as_contract(&contract_id, || {
+        assert_eq!(base::name(&env), String::from_str(&env, "Core Token"));
+        assert_eq!(base::decimals(&env), 7);
+        assert_eq!(base::admin(&env), Some(admin.clone()));
+        assert_eq!(base::total_supply(&env), 0);
+        assert_eq!(admin::storage_version(&env), STORAGE_VERSION);
+        
+        assert_eq!(
+            base::initialize(
+                &env,
+                &admin,
+                String::from_str(&env, "Otro"),
+                String::from_str(&env, "OTRO"),
+                7,
+            ),
+            Err(TokenError::AlreadyInitialized)
+        );
+    });
+}
+
+/// Test: write_balance() elimina la key cuando el balance queda en 0
+#[test]
+fn test_write_balance_removes_zero() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    
+    env.as_contract(&contract_id, || {
+        storage::write_balance(&env, &alice, 100).unwrap();
+        assert!(env.storage().persistent().has(&DataKey::Balance(alice.clone())));
+        
+        storage::write_balance(&env, &alice, 0).unwrap();
+        assert!(!env.storage().persistent().has(&DataKey::Balance(alice.clone())));
+        assert_eq!(base::balance(&env, &alice), 0);
+    });
+}
+
+/// Test: write_balance() mantiene la lista de holders al pasar por 0
+#[test]
+fn test_write_balance_tracks_holders() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    env.as_contract(&contract_id, || {
+        storage::write_balance(&env, &alice, 100).unwrap();
+        storage::write_balance(&env, &bob, 100).unwrap();
+        storage::write_balance(&env, &charlie, 100).unwrap();
+        storage::write_balance(&env, &alice, 50).unwrap();
+        assert_eq!(base::holder_count(&env), Ok(3));
+        assert_eq!(base::holders(&env, 0, 10), Ok(vec![&env, alice.clone(), bob.clone(), charlie.clone()]));
+        
+        // El último ocupa el lugar del que sale
+        storage::write_balance(&env, &alice, 0).unwrap();
+        assert_eq!(base::holders(&env, 0, 10), Ok(vec![&env, charlie.clone(), bob.clone()]));
+        assert!(!env.storage().persistent().has(&DataKey::HolderIndex(alice.clone())));
+        assert!(!env.storage().persistent().has(&DataKey::Holder(2)));
+        
+        // Quitar dos veces no descuenta de más
+        storage::write_balance(&env, &alice, 0).unwrap();
+        assert_eq!(base::holder_count(&env), Ok(2));
+        assert_eq!(base::holders(&env, 1, 10), Ok(vec![&env, bob.clone()]));
+        assert_eq!(base::holders(&env, 5, 10), Ok(Vec::<Address>::new(&env)));
+    });
+}
+
+/// Test: una lista con posición pero contador en 0 falla en lugar de desbordar
+#[test]
+fn test_write_balance_corrupted_holders() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    
+    env.as_contract(&contract_id, || {
+        storage::write_balance(&env, &alice, 100).unwrap();
+        env.storage().instance().set(&DataKey::HolderCount, &0u32);
+        assert_eq!(storage::write_balance(&env, &alice, 0), Err(TokenError::ListCorrupted));
+    });
+}
+
+/// Test: el contador de holders no desborda al agregar
+#[test]
+fn test_write_balance_holder_count_overflow() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::HolderCount, &u32::MAX);
+        assert_eq!(storage::write_balance(&env, &alice, 100), Err(TokenError::OverflowError));
+    });
+}
+
+/// Test: la lista de spenders guarda una entrada por spender y se pagina
+#[test]
+fn test_allowances_of_paged() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    let dave = Address::generate(&env);
+    
+    env.as_contract(&contract_id, || {
+        storage::write_allowance(&env, &alice, &bob, 100, 1000).unwrap();
+        storage::write_allowance(&env, &alice, &charlie, 100, 1000).unwrap();
+        storage::write_allowance(&env, &alice, &dave, 100, 1000).unwrap();
+        assert_eq!(base::spender_count(&env, &alice), Ok(3));
+        assert_eq!(base::allowances_of(&env, &alice, 0, 2), Ok(vec![&env, bob.clone(), charlie.clone()]));
+        assert_eq!(base::allowances_of(&env, &alice, 2, 2), Ok(vec![&env, dave.clone()]));
+        
+        // El último ocupa el lugar del que sale
+        storage::write_allowance(&env, &alice, &bob, 0, 0).unwrap();
+        assert_eq!(storage::read_spender(&env, &alice, 0), Some(dave.clone()));
+        assert!(!env.storage().persistent().has(&DataKey::Spender(alice.clone(), 2)));
+        assert!(!env.storage().persistent().has(&DataKey::SpenderIndex(alice.clone(), bob.clone())));
+        
+        // Al quedar vacía se borra el contador
+        storage::write_allowance(&env, &alice, &charlie, 0, 0).unwrap();
+        storage::write_allowance(&env, &alice, &dave, 0, 0).unwrap();
+        assert_eq!(base::spender_count(&env, &alice), Ok(0));
+        assert!(!env.storage().persistent().has(&DataKey::SpenderCount(alice.clone())));
+    });
+}
+
+/// Test: el contador de spenders no desborda al agregar
+#[test]
+fn test_write_allowance_spender_count_overflow() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(&DataKey::SpenderCount(alice.clone()), &u32::MAX);
+        assert_eq!(
+            storage::write_allowance(&env, &alice, &bob, 100, 1000),
+            Err(TokenError::OverflowError)
+        );
+    });
+}
+
+/// Test: transfer() y transfer_from() llaman al hook con el monto movido
+#[test]
+fn test_transfer_calls_hook() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let spender = Address::generate(&env);
+    
+    call(&env, &contract_id, || storage::write_balance(&env, &alice, 1000)).unwrap();
+    call(&env, &contract_id, || {
+        base::transfer::<RecordingHooks>(&env, alice.clone(), bob.clone().into(), 300)
+    }).unwrap();
+    call(&env, &contract_id, || {
+        base::approve(&env, alice.clone(), spender.clone(), 200, 1000)
+    }).unwrap();
+    call(&env, &contract_id, || {
+        base::transfer_from::<RecordingHooks>(&env, spender.clone(), alice.clone(), bob.clone(), 200)
+    }).unwrap();
+    
+    env.as_contract(&contract_id, || {
+        assert_eq!(base::balance(&env, &alice), 500);
+        assert_eq!(base::balance(&env, &bob), 500);
+        assert_eq!(base::allowance(&env, &alice, &spender), 0);
+        assert_eq!(
+            recorded_moves(&env),
+            vec![
+                &env,
+                Moved { from: Some(alice.clone()), to: Some(bob.clone()), amount: 300 },
+                Moved { from: Some(alice.clone()), to: Some(bob.clone()), amount: 200 },
+            ]
+        );
+    });
+}
+
+/// Test: un transfer inválido no mueve balances ni llama al hook
+#[test]
+fn test_transfer_errors() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let transfer = |to: &Address, amount: i128| {
+        call(&env, &contract_id, || {
+            base::transfer::<RecordingHooks>(&env, alice.clone(), to.clone().into(), amount)
+        })
+    };
+    
+    call(&env, &contract_id, || storage::write_balance(&env, &alice, 100)).unwrap();
+    
+    assert_eq!(transfer(&bob, 0), Err(TokenError::InvalidAmount));
+    assert_eq!(transfer(&alice, 10), Err(TokenError::InvalidRecipient));
+    assert_eq!(transfer(&bob, 101), Err(TokenError::InsufficientBalance));
+    
+    call(&env, &contract_id, || storage::write_authorized(&env, &bob, false));
+    assert_eq!(transfer(&bob, 10), Err(TokenError::AccountFrozen));
+    
+    env.as_contract(&contract_id, || {
+        assert_eq!(base::balance(&env, &alice), 100);
+        assert!(recorded_moves(&env).is_empty());
+    });
+}
+
+/// Test: los eventos estándar tienen el layout SEP-41 / CAP-67
+/// 
+/// Se comparan contra topics y data armados a mano, no contra los
+/// structs, para que un cambio en los structs no pase desapercibido.
+#[test]
+fn test_sep41_event_layout() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let muxed_bob = MuxedAddress::generate(&env);
+    
+    call(&env, &contract_id, || storage::write_balance(&env, &alice, 1000)).unwrap();
+    
+    // Transfer a una dirección normal: data = amount
+    call(&env, &contract_id, || {
+        base::transfer::<RecordingHooks>(&env, alice.clone(), bob.clone().into(), 100)
+    }).unwrap();
+    let events = env.events().all();
+    let (_, topics, data) = events.last().unwrap();
+    let expected_topics: Vec<Val> = (symbol_short!("transfer"), alice.clone(), bob.clone())
+        .into_val(&env);
+    assert_eq!(topics, expected_topics);
+    assert_eq!(i128::try_from_val(&env, &data).unwrap(), 100);
+    
+    // Transfer muxed: data = { amount, to_muxed_id }, topics con la cuenta base
+    call(&env, &contract_id, || {
+        base::transfer::<RecordingHooks>(&env, alice.clone(), muxed_bob.clone(), 50)
+    }).unwrap();
+    let events = env.events().all();
+    let (_, topics, data) = events.last().unwrap();
+    let expected_topics: Vec<Val> = (symbol_short!("transfer"), alice.clone(), muxed_bob.address())
+        .into_val(&env);
+    assert_eq!(topics, expected_topics);
+    let expected_data: Map<Symbol, Val> = map![
+        &env,
+        (symbol_short!("amount"), 50i128.into_val(&env)),
+        (Symbol::new(&env, "to_muxed_id"), muxed_bob.id().unwrap().into_val(&env)),
+    ];
+    assert_eq!(Map::<Symbol, Val>::try_from_val(&env, &data).unwrap(), expected_data);
+    
+    // Approve: data = [amount, expiration_ledger]
+    let bob = muxed_bob.address();
+    call(&env, &contract_id, || {
+        base::approve(&env, alice.clone(), bob.clone(), 300, 1000)
+    }).unwrap();
+    let events = env.events().all();
+    let (_, topics, data) = events.last().unwrap();
+    let expected_topics: Vec<Val> = (symbol_short!("approve"), alice.clone(), bob.clone())
+        .into_val(&env);
+    assert_eq!(topics, expected_topics);
+    let expected_data: Vec<Val> = (300i128, 1000u32).into_val(&env);
+    assert_eq!(Vec::<Val>::try_from_val(&env, &data).unwrap(), expected_data);
+    
+    env.as_contract(&contract_id, || {
+        assert_eq!(base::balance(&env, &bob), 50);
+    });
+}
+
+/// Test: burn_balance() reduce balance y supply y avisa al hook
+#[test]
+fn test_burn_balance_calls_hook() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    
+    env.as_contract(&contract_id, || {
+        storage::write_balance(&env, &alice, 100).unwrap();
+        storage::write_total_supply(&env, 100);
+        
+        assert_eq!(base::burn_balance::<RecordingHooks>(&env, &alice, 40), Ok((60, 60)));
+        assert_eq!(
+            base::burn_balance::<RecordingHooks>(&env, &alice, 61),
+            Err(TokenError::InsufficientBalance)
+        );
+        assert_eq!(
+            recorded_moves(&env),
+            vec![&env, Moved { from: Some(alice.clone()), to: None, amount: 40 }]
+        );
+    });
+}
+
+/// Test: spend_allowance() descuenta salvo que el allowance sea ilimitado
+#[test]
+fn test_spend_allowance() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    env.as_contract(&contract_id, || {
+        storage::write_allowance(&env, &alice, &bob, 100, 1000).unwrap();
+        assert_eq!(base::spend_allowance(&env, &alice, &bob, 40), Ok(60));
+        assert_eq!(base::spend_allowance(&env, &alice, &bob, 61), Err(TokenError::InsufficientAllowance));
+        assert_eq!(base::spend_allowance(&env, &alice, &bob, 60), Ok(0));
+        assert!(!env.storage().persistent().has(&DataKey::Allowance(alice.clone(), bob.clone())));
+        
+        storage::write_allowance(&env, &alice, &bob, UNLIMITED_ALLOWANCE, 1000).unwrap();
+        assert_eq!(base::spend_allowance(&env, &alice, &bob, 500), Ok(UNLIMITED_ALLOWANCE));
+        assert_eq!(base::allowance(&env, &alice, &bob), UNLIMITED_ALLOWANCE);
+    });
+}
+
+/// Test: validate_metadata() aplica los límites de longitud
+#[test]
+fn test_validate_metadata() {
+    let env = Env::default();
+    
+    let valid = sample_metadata(&env);
+    assert_eq!(base::validate_metadata(&valid), Ok(()));
+    
+    let mut long_name = sample_metadata(&env);
+    long_name.name = String::from_str(&env, &"a".repeat(MAX_NAME_LENGTH as usize + 1));
+    assert_eq!(base::validate_metadata(&long_name), Err(TokenError::InvalidMetadata));
+    
+    let mut long_url = sample_metadata(&env);
+    long_url.icon_url = String::from_str(&env, &"u".repeat(MAX_URL_LENGTH as usize + 1));
+    assert_eq!(base::validate_metadata(&long_url), Err(TokenError::InvalidMetadata));
+    
+    let mut empty_symbol = sample_metadata(&env);
+    empty_symbol.symbol = String::from_str(&env, "");
+    assert_eq!(base::validate_metadata(&empty_symbol), Err(TokenError::InvalidMetadata));
+}
+
+/// Test: require_admin() falla después de renunciar
+#[test]
+fn test_require_admin_after_renounce() {
+    let env = Env::default();
+    let (contract_id, admin) = setup(&env);
+    
+    assert_eq!(call(&env, &contract_id, || require_admin(&env)), Ok(admin));
+    call(&env, &contract_id, || admin::renounce_admin(&env)).unwrap();
+    assert_eq!(
+        call(&env, &contract_id, || require_admin(&env)),
+        Err(TokenError::AdminRenounced)
+    );
+    assert_eq!(call(&env, &contract_id, || base::admin(&env)), None);
+}
+
+/// Test: mint() respeta el tope y avisa al hook
+#[cfg(feature = "capped")]
+#[test]
+fn test_mint_respects_cap() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    
+    env.as_contract(&contract_id, || {
+        capped::initialize(&env, Some(1000)).unwrap();
+        
+        mintable::mint::<RecordingHooks>(&env, alice.clone(), 1000).unwrap();
+        assert_eq!(
+            mintable::mint::<RecordingHooks>(&env, alice.clone(), 1),
+            Err(TokenError::SupplyCapExceeded)
+        );
+        assert_eq!(base::total_supply(&env), 1000);
+        assert_eq!(
+            recorded_moves(&env),
+            vec![&env, Moved { from: None, to: Some(alice.clone()), amount: 1000 }]
+        );
+    });
+}
+
+/// Test: con la pausa activa, transfer() falla con Paused
+#[cfg(feature = "pausable")]
+#[test]
+fn test_pause_blocks_transfer() {
+    let env = Env::default();
+    let (contract_id, admin) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let transfer = || {
+        call(&env, &contract_id, || {
+            base::transfer::<RecordingHooks>(&env, alice.clone(), bob.clone().into(), 10)
+        })
+    };
+    
+    call(&env, &contract_id, || {
+        storage::write_balance(&env, &alice, 100).unwrap();
+        pausable::pause(&env, admin.clone(), storage::PauseTarget::Transfer);
+    });
+    assert_eq!(transfer(), Err(TokenError::Paused));
+    
+    call(&env, &contract_id, || {
+        pausable::unpause(&env, admin.clone(), storage::PauseTarget::Transfer);
+    });
+    assert_eq!(transfer(), Ok(()));
+}
+
+/// Test: el fee va a la tesorería y el hook ve el neto y el fee por separado
+#[cfg(feature = "fee")]
+#[test]
+fn test_fee_to_treasury_calls_hook() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    
+    call(&env, &contract_id, || storage::write_balance(&env, &alice, 1000)).unwrap();
+    call(&env, &contract_id, || {
+        fee::set_transfer_fee(&env, 250, storage::FeeDestination::Treasury(treasury.clone()))
+    }).unwrap();
+    call(&env, &contract_id, || {
+        base::transfer::<RecordingHooks>(&env, alice.clone(), bob.clone().into(), 1000)
+    }).unwrap();
+    
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            fee::quote_transfer(&env, &alice, &bob, 1000),
+            Ok(storage::TransferQuote { amount: 1000, fee: 25, net_amount: 975 })
+        );
+        assert_eq!(base::balance(&env, &bob), 975);
+        assert_eq!(base::balance(&env, &treasury), 25);
+        assert_eq!(
+            recorded_moves(&env),
+            vec![
+                &env,
+                Moved { from: Some(alice.clone()), to: Some(bob.clone()), amount: 975 },
+                Moved { from: Some(alice.clone()), to: Some(treasury.clone()), amount: 25 },
+            ]
+        );
+    });
+}
+
+/// Test: el allowance periódico se renueva al empezar cada período
+#[cfg(feature = "periodic")]
+#[test]
+fn test_periodic_allowance_resets() {
+    use soroban_sdk::testutils::Ledger as _;
+    
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.ledger().set_sequence_number(100);
+    
+    call(&env, &contract_id, || {
+        periodic::approve_periodic(&env, alice.clone(), bob.clone(), 300, 10, 1000)
+    }).unwrap();
+    
+    env.as_contract(&contract_id, || {
+        assert_eq!(base::spend_allowance(&env, &alice, &bob, 200), Ok(100));
+        assert_eq!(base::spend_allowance(&env, &alice, &bob, 101), Err(TokenError::InsufficientAllowance));
+    });
+    
+    // Ledger 125 cae en el período [120, 130): el gasto vuelve a 0
+    env.ledger().set_sequence_number(125);
+    env.as_contract(&contract_id, || {
+        assert_eq!(periodic::spendable_allowance(&env, &alice, &bob), 300);
+        assert_eq!(base::spend_allowance(&env, &alice, &bob, 300), Ok(0));
+        let allowance = periodic::periodic_allowance(&env, &alice, &bob).unwrap();
+        assert_eq!(allowance.period_start, 120);
+        assert_eq!(allowance.spent, 300);
+    });
+}
@@ -0,0 +1,43 @@
+// src/errors.rs
+use soroban_sdk::contracterror;
+
+/// Enum de errores personalizados del vesting
+///
+/// Cada error tiene un código único para debugging en el ledger
+/// Los códigos empiezan en 1 (0 está reservado para "sin error")
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VestingError {
+    /// El contrato ya fue inicializado
+    /// Se lanza si se intenta llamar initialize() dos veces
+    AlreadyInitialized = 1,
+
+    /// El contrato no ha sido inicializado
+    /// Debe llamarse initialize() primero
+    NotInitialized = 2,
+
+    /// Amount debe ser mayor a 0
+    /// El total a vestear no puede ser 0 ni negativo
+    InvalidAmount = 3,
+
+    /// Calendario inválido
+    /// duration debe ser > 0 y el cliff no puede superar la duración
+    InvalidSchedule = 4,
+
+    /// No hay tokens liberables todavía
+    /// release() antes del cliff o sin nuevo monto vesteado
+    NothingToRelease = 5,
+
+    /// El vesting no es revocable
+    /// Se creó con revocable = false
+    NotRevocable = 6,
+
+    /// El vesting ya fue revocado
+    /// revoke() solo se puede llamar una vez
+    AlreadyRevoked = 7,
+
+    /// Overflow en operación aritmética
+    /// total_amount * tiempo transcurrido no entra en i128
+    OverflowError = 8,
+}
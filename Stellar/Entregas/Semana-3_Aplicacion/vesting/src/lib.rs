@@ -0,0 +1,268 @@
+// src/lib.rs
+#![no_std]
+
+use soroban_sdk::{contract, contractevent, contractimpl, token, Address, Env};
+
+mod errors;
+mod storage;
+
+pub use errors::VestingError;
+pub use storage::VestingSchedule;
+use storage::DataKey;
+
+// EVENTOS
+
+#[contractevent]
+pub struct VestingCreatedEvent {
+    pub grantor: Address,
+    pub beneficiary: Address,
+    pub token: Address,
+    pub total_amount: i128,
+    pub start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    pub revocable: bool,
+}
+
+#[contractevent]
+pub struct ReleasedEvent {
+    pub beneficiary: Address,
+    pub amount: i128,
+    pub total_released: i128,
+}
+
+#[contractevent]
+pub struct RevokedEvent {
+    pub grantor: Address,
+    pub vested: i128,
+    pub refunded: i128,
+}
+
+/// Contrato de vesting con cliff y liberación lineal
+///
+/// Custodia los tokens de una asignación (equipo, contribuidores) y los
+/// libera al beneficiario con el paso del tiempo. Cada instancia maneja
+/// un único calendario.
+#[contract]
+pub struct TokenVesting;
+
+/// Interfaz pública del vesting
+pub trait VestingTrait {
+    /// Crea el calendario y deposita los tokens (solo una vez)
+    ///
+    /// Requiere autorización del grantor: `total_amount` se transfiere
+    /// del grantor al contrato en la misma invocación. El calendario
+    /// registra lo que efectivamente llegó al contrato (con un token con
+    /// fee de transferencia, `total_amount` menos el fee).
+    fn initialize(env: Env, schedule: VestingSchedule) -> Result<(), VestingError>;
+
+    /// Libera al beneficiario todo lo vesteado y aún no cobrado
+    ///
+    /// Requiere autorización del beneficiario. Devuelve el monto pagado.
+    fn release(env: Env) -> Result<i128, VestingError>;
+
+    /// Revoca el vesting (solo grantor, si es revocable)
+    ///
+    /// Lo ya vesteado queda disponible para el beneficiario; el resto
+    /// vuelve al grantor.
+    fn revoke(env: Env) -> Result<(), VestingError>;
+
+    /// Monto vesteado al timestamp actual del ledger
+    fn vested_amount(env: Env) -> Result<i128, VestingError>;
+
+    /// Monto que release() pagaría ahora
+    fn releasable(env: Env) -> Result<i128, VestingError>;
+
+    // Métodos de consulta (getters)
+    fn released(env: Env) -> i128;
+    fn revoked(env: Env) -> bool;
+    fn schedule(env: Env) -> Result<VestingSchedule, VestingError>;
+}
+
+/// Monto vesteado del calendario al timestamp `now`
+///
+/// - Antes de start + cliff: 0
+/// - Entre el cliff y start + duration: lineal desde start
+/// - Desde start + duration: el total
+fn vested_at(schedule: &VestingSchedule, now: u64) -> Result<i128, VestingError> {
+    if now < schedule.start.saturating_add(schedule.cliff) {
+        return Ok(0);
+    }
+
+    let elapsed = now - schedule.start;
+    if elapsed >= schedule.duration {
+        return Ok(schedule.total_amount);
+    }
+
+    let vested = schedule.total_amount
+        .checked_mul(elapsed as i128)
+        .ok_or(VestingError::OverflowError)?;
+    Ok(vested / schedule.duration as i128)
+}
+
+/// Monto vesteado considerando una posible revocación
+///
+/// Al revocar, total_amount se reduce a lo vesteado en ese momento,
+/// así que un calendario revocado queda 100% vesteado.
+fn current_vested(env: &Env, schedule: &VestingSchedule) -> Result<i128, VestingError> {
+    if env.storage().instance().has(&DataKey::Revoked) {
+        return Ok(schedule.total_amount);
+    }
+
+    vested_at(schedule, env.ledger().timestamp())
+}
+
+#[contractimpl]
+impl VestingTrait for TokenVesting {
+    fn initialize(env: Env, schedule: VestingSchedule) -> Result<(), VestingError> {
+        // 1. Verificar que no esté inicializado
+        if env.storage().instance().has(&DataKey::Schedule) {
+            return Err(VestingError::AlreadyInitialized);
+        }
+
+        // 2. Verificar autorización del grantor
+        schedule.grantor.require_auth();
+
+        // 3. Validaciones
+        if schedule.total_amount <= 0 {
+            return Err(VestingError::InvalidAmount);
+        }
+        if schedule.duration == 0 || schedule.cliff > schedule.duration {
+            return Err(VestingError::InvalidSchedule);
+        }
+
+        // 4. Depositar los tokens en el contrato y medir lo recibido
+        let token = token::TokenClient::new(&env, &schedule.token);
+        let contract = env.current_contract_address();
+        let before = token.balance(&contract);
+        token.transfer(&schedule.grantor, &contract, &schedule.total_amount);
+        let received = token.balance(&contract)
+            .checked_sub(before)
+            .ok_or(VestingError::OverflowError)?;
+        if received <= 0 {
+            return Err(VestingError::InvalidAmount);
+        }
+
+        // 5. Guardar calendario sobre lo recibido: con fee, vestear
+        // total_amount dejaría el último release sin fondos
+        let schedule = VestingSchedule { total_amount: received, ..schedule };
+        env.storage().instance().set(&DataKey::Schedule, &schedule);
+        env.storage().instance().set(&DataKey::Released, &0i128);
+        env.storage().instance().extend_ttl(100_000, 200_000);
+
+        // 6. Emitir evento
+        VestingCreatedEvent {
+            grantor: schedule.grantor,
+            beneficiary: schedule.beneficiary,
+            token: schedule.token,
+            total_amount: schedule.total_amount,
+            start: schedule.start,
+            cliff: schedule.cliff,
+            duration: schedule.duration,
+            revocable: schedule.revocable,
+        }.publish(&env);
+
+        Ok(())
+    }
+
+    fn release(env: Env) -> Result<i128, VestingError> {
+        // 1. Verificar inicialización y autorización del beneficiario
+        let schedule = Self::schedule(env.clone())?;
+        schedule.beneficiary.require_auth();
+
+        // 2. Calcular lo liberable
+        let amount = Self::releasable(env.clone())?;
+        if amount == 0 {
+            return Err(VestingError::NothingToRelease);
+        }
+
+        // 3. Registrar antes de transferir
+        let total_released = Self::released(env.clone()) + amount;
+        env.storage().instance().set(&DataKey::Released, &total_released);
+        env.storage().instance().extend_ttl(100_000, 200_000);
+
+        // 4. Pagar con transfer del token
+        token::TokenClient::new(&env, &schedule.token).transfer(
+            &env.current_contract_address(),
+            &schedule.beneficiary,
+            &amount,
+        );
+
+        // 5. Emitir evento
+        ReleasedEvent {
+            beneficiary: schedule.beneficiary,
+            amount,
+            total_released,
+        }.publish(&env);
+
+        Ok(amount)
+    }
+
+    fn revoke(env: Env) -> Result<(), VestingError> {
+        // 1. Verificar inicialización y autorización del grantor
+        let mut schedule = Self::schedule(env.clone())?;
+        schedule.grantor.require_auth();
+
+        // 2. Validaciones
+        if !schedule.revocable {
+            return Err(VestingError::NotRevocable);
+        }
+        if env.storage().instance().has(&DataKey::Revoked) {
+            return Err(VestingError::AlreadyRevoked);
+        }
+
+        // 3. Congelar el calendario en lo vesteado hasta ahora
+        let vested = vested_at(&schedule, env.ledger().timestamp())?;
+        let refunded = schedule.total_amount - vested;
+        schedule.total_amount = vested;
+        env.storage().instance().set(&DataKey::Schedule, &schedule);
+        env.storage().instance().set(&DataKey::Revoked, &true);
+
+        // 4. Devolver lo no vesteado al grantor
+        if refunded > 0 {
+            token::TokenClient::new(&env, &schedule.token).transfer(
+                &env.current_contract_address(),
+                &schedule.grantor,
+                &refunded,
+            );
+        }
+
+        // 5. Emitir evento
+        RevokedEvent {
+            grantor: schedule.grantor,
+            vested,
+            refunded,
+        }.publish(&env);
+
+        Ok(())
+    }
+
+    fn vested_amount(env: Env) -> Result<i128, VestingError> {
+        let schedule = Self::schedule(env.clone())?;
+        current_vested(&env, &schedule)
+    }
+
+    fn releasable(env: Env) -> Result<i128, VestingError> {
+        let schedule = Self::schedule(env.clone())?;
+        Ok(current_vested(&env, &schedule)? - Self::released(env))
+    }
+
+    fn released(env: Env) -> i128 {
+        env.storage().instance()
+            .get(&DataKey::Released)
+            .unwrap_or(0)
+    }
+
+    fn revoked(env: Env) -> bool {
+        env.storage().instance().has(&DataKey::Revoked)
+    }
+
+    fn schedule(env: Env) -> Result<VestingSchedule, VestingError> {
+        env.storage().instance()
+            .get(&DataKey::Schedule)
+            .ok_or(VestingError::NotInitialized)
+    }
+}
+
+#[cfg(test)]
+mod test;
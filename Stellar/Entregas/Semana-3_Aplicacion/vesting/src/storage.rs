@@ -0,0 +1,37 @@
+// src/storage.rs
+use soroban_sdk::{contracttype, Address};
+
+/// Enum que define todas las claves de almacenamiento
+///
+/// Un contrato de vesting maneja un único calendario, por lo que
+/// todo vive en Instance Storage.
+#[contracttype]
+pub enum DataKey {
+    /// Calendario de vesting - Instance Storage
+    Schedule,
+
+    /// Total ya liberado al beneficiario - Instance Storage
+    Released,
+
+    /// Marca de revocación - Instance Storage
+    /// Solo existe si el grantor revocó el vesting
+    Revoked,
+}
+
+/// Calendario de vesting
+///
+/// Nada se libera antes de `start + cliff`. Desde ahí el monto vesteado
+/// crece linealmente desde `start` hasta `start + duration`, donde se
+/// completa `total_amount`. Los tiempos son timestamps del ledger (segundos).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub grantor: Address,
+    pub beneficiary: Address,
+    pub token: Address,
+    pub total_amount: i128,
+    pub start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    pub revocable: bool,
+}
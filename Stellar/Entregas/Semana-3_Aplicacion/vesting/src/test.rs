@@ -0,0 +1,285 @@
+// src/test.rs
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, Env, Event, String,
+};
+
+// WorxToken compilado a wasm: el vesting paga con su transfer()
+// Regenerar con: ../fixtures.sh build
+mod worx_token {
+    soroban_sdk::contractimport!(file = "testdata/worx_token.wasm");
+}
+
+const START: u64 = 1_000_000;
+const CLIFF: u64 = 100;
+const DURATION: u64 = 1_000;
+const TOTAL: i128 = 10_000;
+
+/// Helper: avanza el timestamp del ledger
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| {
+        li.timestamp = timestamp;
+    });
+}
+
+/// Helper: WorxToken + vesting de TOTAL tokens (cliff 100s, duración 1000s)
+fn setup(
+    env: &Env,
+    revocable: bool,
+) -> (TokenVestingClient<'_>, worx_token::Client<'_>, Address, Address) {
+    env.mock_all_auths();
+    set_time(env, START);
+
+    let admin = Address::generate(env);
+    let grantor = Address::generate(env);
+    let beneficiary = Address::generate(env);
+
+    let token_id = env.register(worx_token::WASM, ());
+    let token = worx_token::Client::new(env, &token_id);
+    token.initialize(
+        &admin,
+        &String::from_str(env, "WORX Token"),
+        &String::from_str(env, "WORX"),
+        &7,
+        &true,
+        &None
+    );
+    token.mint(&grantor, &TOTAL);
+
+    let vesting_id = env.register(TokenVesting, ());
+    let vesting = TokenVestingClient::new(env, &vesting_id);
+    vesting.initialize(&VestingSchedule {
+        grantor: grantor.clone(),
+        beneficiary: beneficiary.clone(),
+        token: token_id,
+        total_amount: TOTAL,
+        start: START,
+        cliff: CLIFF,
+        duration: DURATION,
+        revocable,
+    });
+
+    (vesting, token, grantor, beneficiary)
+}
+
+/// Test: initialize deposita los tokens en el contrato
+#[test]
+fn test_initialize_deposits_tokens() {
+    let env = Env::default();
+    let (vesting, token, grantor, beneficiary) = setup(&env, false);
+
+    assert_eq!(token.balance(&vesting.address), TOTAL);
+    assert_eq!(token.balance(&grantor), 0);
+    assert_eq!(
+        vesting.schedule(),
+        VestingSchedule {
+            grantor,
+            beneficiary,
+            token: token.address.clone(),
+            total_amount: TOTAL,
+            start: START,
+            cliff: CLIFF,
+            duration: DURATION,
+            revocable: false,
+        }
+    );
+}
+
+/// Test: nada se libera antes del cliff
+#[test]
+fn test_nothing_before_cliff() {
+    let env = Env::default();
+    let (vesting, _token, _grantor, _beneficiary) = setup(&env, false);
+
+    set_time(&env, START + CLIFF - 1);
+    assert_eq!(vesting.vested_amount(), 0);
+    assert_eq!(vesting.try_release(), Err(Ok(VestingError::NothingToRelease)));
+}
+
+/// Test: liberación lineal desde start una vez pasado el cliff
+#[test]
+fn test_linear_release() {
+    let env = Env::default();
+    let (vesting, token, _grantor, beneficiary) = setup(&env, false);
+
+    // En el cliff ya vestea lo acumulado desde start (10%)
+    set_time(&env, START + CLIFF);
+    assert_eq!(vesting.releasable(), 1_000);
+    assert_eq!(vesting.release(), 1_000);
+
+    let event = ReleasedEvent {
+        beneficiary: beneficiary.clone(),
+        amount: 1_000,
+        total_released: 1_000,
+    };
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![&env, (vesting.address.clone(), event.topics(&env), event.data(&env))]
+    );
+
+    // A mitad de la duración: 50% vesteado, se pagan solo los 4000 nuevos
+    set_time(&env, START + DURATION / 2);
+    assert_eq!(vesting.vested_amount(), 5_000);
+    assert_eq!(vesting.release(), 4_000);
+    assert_eq!(token.balance(&beneficiary), 5_000);
+
+    // Sin tiempo nuevo no hay nada que liberar
+    assert_eq!(vesting.try_release(), Err(Ok(VestingError::NothingToRelease)));
+
+    // Pasada la duración se completa el total
+    set_time(&env, START + DURATION + 500);
+    assert_eq!(vesting.release(), 5_000);
+    assert_eq!(token.balance(&beneficiary), TOTAL);
+    assert_eq!(token.balance(&vesting.address), 0);
+    assert_eq!(vesting.released(), TOTAL);
+}
+
+/// Test: release requiere la firma del beneficiario
+#[test]
+fn test_release_requires_beneficiary() {
+    let env = Env::default();
+    let (vesting, _token, _grantor, beneficiary) = setup(&env, false);
+
+    set_time(&env, START + DURATION);
+    vesting.release();
+    assert_eq!(env.auths()[0].0, beneficiary);
+}
+
+/// Test: revoke devuelve lo no vesteado y deja cobrar lo vesteado
+#[test]
+fn test_revoke() {
+    let env = Env::default();
+    let (vesting, token, grantor, beneficiary) = setup(&env, true);
+
+    set_time(&env, START + 300);
+    vesting.revoke();
+
+    let event = RevokedEvent {
+        grantor: grantor.clone(),
+        vested: 3_000,
+        refunded: 7_000,
+    };
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![&env, (vesting.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(token.balance(&grantor), 7_000);
+    assert!(vesting.revoked());
+
+    // Lo vesteado al revocar sigue disponible y no crece más
+    set_time(&env, START + DURATION);
+    assert_eq!(vesting.vested_amount(), 3_000);
+    assert_eq!(vesting.release(), 3_000);
+    assert_eq!(token.balance(&beneficiary), 3_000);
+    assert_eq!(token.balance(&vesting.address), 0);
+
+    assert_eq!(vesting.try_revoke(), Err(Ok(VestingError::AlreadyRevoked)));
+}
+
+/// Test: un vesting no revocable no se puede revocar
+#[test]
+fn test_revoke_not_revocable() {
+    let env = Env::default();
+    let (vesting, _token, _grantor, _beneficiary) = setup(&env, false);
+
+    assert_eq!(vesting.try_revoke(), Err(Ok(VestingError::NotRevocable)));
+}
+
+/// Test: validaciones de initialize
+#[test]
+fn test_initialize_validations() {
+    let env = Env::default();
+    let (vesting, _token, _grantor, _beneficiary) = setup(&env, false);
+    let schedule = vesting.schedule();
+
+    assert_eq!(
+        vesting.try_initialize(&schedule),
+        Err(Ok(VestingError::AlreadyInitialized))
+    );
+
+    let fresh = TokenVestingClient::new(&env, &env.register(TokenVesting, ()));
+    assert_eq!(
+        fresh.try_initialize(&VestingSchedule { total_amount: 0, ..schedule.clone() }),
+        Err(Ok(VestingError::InvalidAmount))
+    );
+    assert_eq!(
+        fresh.try_initialize(&VestingSchedule { cliff: 0, duration: 0, ..schedule.clone() }),
+        Err(Ok(VestingError::InvalidSchedule))
+    );
+    assert_eq!(
+        fresh.try_initialize(&VestingSchedule { cliff: DURATION + 1, ..schedule }),
+        Err(Ok(VestingError::InvalidSchedule))
+    );
+    assert_eq!(fresh.try_release(), Err(Ok(VestingError::NotInitialized)));
+}
+
+/// Test: con fee de transferencia el calendario vestea lo recibido
+#[test]
+fn test_initialize_with_fee_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, START);
+
+    let admin = Address::generate(&env);
+    let grantor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let treasury = Address::generate(&env);
+
+    let token_id = env.register(worx_token::WASM, ());
+    let token = worx_token::Client::new(&env, &token_id);
+    token.initialize(
+        &admin,
+        &String::from_str(&env, "WORX Token"),
+        &String::from_str(&env, "WORX"),
+        &7,
+        &true,
+        &None
+    );
+    token.mint(&grantor, &TOTAL);
+    // 2,5%: el depósito de 10_000 llega como 9_750
+    token.set_transfer_fee(&250, &worx_token::FeeDestination::Treasury(treasury.clone()));
+
+    let vesting = TokenVestingClient::new(&env, &env.register(TokenVesting, ()));
+    vesting.initialize(&VestingSchedule {
+        grantor: grantor.clone(),
+        beneficiary: beneficiary.clone(),
+        token: token_id,
+        total_amount: TOTAL,
+        start: START,
+        cliff: CLIFF,
+        duration: DURATION,
+        revocable: false,
+    });
+
+    let event = VestingCreatedEvent {
+        grantor,
+        beneficiary: beneficiary.clone(),
+        token: token.address.clone(),
+        total_amount: 9_750,
+        start: START,
+        cliff: CLIFF,
+        duration: DURATION,
+        revocable: false,
+    };
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![&env, (vesting.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(vesting.schedule().total_amount, 9_750);
+    assert_eq!(token.balance(&vesting.address), 9_750);
+    assert_eq!(token.balance(&treasury), 250);
+
+    // Todo lo vesteado se puede pagar: el contrato no queda corto
+    set_time(&env, START + DURATION / 2);
+    assert_eq!(vesting.release(), 4_875);
+    set_time(&env, START + DURATION);
+    assert_eq!(vesting.release(), 4_875);
+    assert_eq!(vesting.released(), 9_750);
+    assert_eq!(token.balance(&vesting.address), 0);
+}
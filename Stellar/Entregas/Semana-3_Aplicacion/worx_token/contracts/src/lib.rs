@@ -2,16 +2,24 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contractclient, contractevent, Address, Env, String, 
-    symbol_short,
+    contract, contractimpl, contractevent, Address, BytesN, Env, MuxedAddress, String, Vec,
+};
+use token_core::{
+    admin, base, burnable, capped, fee, mintable, pausable, periodic, require_admin, TokenHooks,
 };
 
 mod storage;
-mod errors;
 
-use storage::{DataKey, TokenMetadata};
-use errors::TokenError;
+use storage::{
+    TokenMetadata, FeeConfig, FeeDestination, PauseTarget, PeriodicAllowance, TransferQuote,
+};
+pub use token_core::events::*;
+pub use token_core::{
+    TokenError, MAX_BATCH_SIZE, MAX_MEMO_LENGTH, MAX_PAGE_SIZE, STORAGE_VERSION,
+    UNLIMITED_ALLOWANCE,
+};
 
+// EVENTOS propios de WorxToken (los comunes vienen de token_core::events)
 
 #[contractevent]
 pub struct InitEvent {
@@ -19,66 +27,34 @@ pub struct InitEvent {
     pub name: String,
     pub symbol: String,
     pub decimals: u32,
+    pub clawback_enabled: bool,
 }
 
 #[contractevent]
-pub struct MintEvent {
-    pub to: Address,
-    pub amount: i128,
-    pub new_balance: i128,
-    pub new_supply: i128,
-}
-
-#[contractevent]
-pub struct BurnEvent {
+pub struct ClawbackEvent {
+    pub admin: Address,
     pub from: Address,
     pub amount: i128,
-    pub new_balance: i128, 
+    pub new_balance: i128,
     pub new_supply: i128,
 }
 
-#[contractevent]
-pub struct TransferEvent {
-    pub from: Address,
-    pub to: Address,
-    pub amount: i128,
-    pub from_new_balance: i128,
-    pub to_new_balance: i128,
-}
-
-#[contractevent]
-pub struct TransferFromEvent {
-    pub spender: Address,
-    pub from: Address, 
-    pub to: Address,
-    pub amount: i128,
-    pub from_new_balance: i128,
-    pub to_new_balance: i128,
-    pub new_allowance: i128,
-}
-
-#[contractevent]
-pub struct ApproveEvent {
-    pub from: Address,
-    pub spender: Address,
-    pub old_allowance: i128,
-    pub new_allowance: i128,
-}
-
-/// Constantes de configuración
-const MAX_DECIMALS: u32 = 18;
-const MAX_NAME_LENGTH: u32 = 100;
-const MAX_SYMBOL_LENGTH: u32 = 32;
-
+/// Versión del código del contrato (se incrementa en cada release)
+const CONTRACT_VERSION: u32 = 3;
 
-/// Estructura del contrato Token BDB
+/// Estructura del contrato WorxToken
 #[contract]
 pub struct WorxToken;
 
-/// Trait que define la interfaz del token según CAP-46
+/// Trait que define la interfaz del token según CAP-46 / SEP-41
 /// 
 /// Esta es la interfaz estándar de tokens fungibles en Stellar
 /// Compatible con wallets, DEXs, y el ecosistema completo
+///
+/// Los métodos SEP-41 tienen el mismo nombre, orden y tipos de argumentos
+/// que `soroban_sdk::token::TokenInterface`, por lo que el contrato se
+/// puede usar con `soroban_sdk::token::TokenClient`. Los errores se
+/// devuelven como `TokenError` (errores de contrato en el ledger).
 pub trait TokenTrait {
     /// Inicializa el token con metadatos y admin
     /// 
@@ -87,12 +63,17 @@ pub trait TokenTrait {
     /// - Name: nombre completo del token
     /// - Symbol: identificador corto (ej: BDB, USDC)
     /// - Decimals: precisión del token (7 para Stellar)
+    /// - Max supply: tope opcional del supply (None = sin tope)
+    /// - Clawback: si el admin puede recuperar tokens de los holders
+    ///   (false = el token queda sin clawback para siempre)
     fn initialize(
         env: Env, 
         admin: Address, 
         name: String, 
         symbol: String,
-        decimals: u32
+        decimals: u32,
+        clawback_enabled: bool,
+        max_supply: Option<i128>
     ) -> Result<(), TokenError>;
     
     /// Crea nuevos tokens (solo admin)
@@ -107,36 +88,51 @@ pub trait TokenTrait {
     /// Requiere autorización del owner
     fn burn(env: Env, from: Address, amount: i128) -> Result<(), TokenError>;
     
+    /// Destruye tokens de `from` usando el allowance de `spender`
+    /// 
+    /// Requiere autorización del spender
+    /// Reduce el allowance, el balance y el supply total
+    fn burn_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        amount: i128
+    ) -> Result<(), TokenError>;
+    
     /// Consulta el balance de una cuenta
     /// 
     /// Devuelve 0 si la cuenta nunca ha recibido tokens
-    fn balance(env: Env, account: Address) -> i128;
+    fn balance(env: Env, id: Address) -> i128;
     
     /// Transfiere tokens entre cuentas
     /// 
     /// Requiere autorización de `from`
     /// No permite transferencias a sí mismo
+    /// `to` puede ser una dirección muxed (se acredita a la cuenta base)
     fn transfer(
         env: Env, 
         from: Address, 
-        to: Address, 
+        to: MuxedAddress, 
         amount: i128
     ) -> Result<(), TokenError>;
     
     /// Aprueba a otro usuario para gastar tokens
     /// 
     /// Permite que `spender` gaste hasta `amount` tokens
-    /// de la cuenta de `from`. Se puede revocar con amount=0
+    /// de la cuenta de `from` hasta el ledger `expiration_ledger`.
+    /// Se puede revocar con amount=0
     fn approve(
         env: Env, 
         from: Address, 
         spender: Address, 
-        amount: i128
+        amount: i128,
+        expiration_ledger: u32
     ) -> Result<(), TokenError>;
     
     /// Consulta el allowance entre dos cuentas
     /// 
     /// Devuelve cuánto puede gastar `spender` de los tokens de `from`
+    /// Devuelve 0 si el allowance ya expiró
     fn allowance(env: Env, from: Address, spender: Address) -> i128;
     
     /// Transfiere tokens en nombre de otro usuario
@@ -156,10 +152,376 @@ pub trait TokenTrait {
     fn symbol(env: Env) -> String;
     fn decimals(env: Env) -> u32;
     fn total_supply(env: Env) -> i128;
-    fn admin(env: Env) -> Address;
+    
+    /// Devuelve el admin actual
+    /// 
+    /// None si el contrato no fue inicializado o si el admin renunció
+    fn admin(env: Env) -> Option<Address>;
+}
+
+/// Gestión del administrador del token
+/// 
+/// La transferencia del admin se hace en dos pasos para no perder
+/// el control del token por un error de tipeo:
+/// 1. El admin actual propone al nuevo admin (propose_admin)
+/// 2. El nuevo admin acepta con su propia firma (accept_admin)
+pub trait AdminTrait {
+    /// Propone un nuevo admin (solo admin actual)
+    /// 
+    /// Reemplaza cualquier propuesta pendiente anterior
+    fn propose_admin(env: Env, new_admin: Address) -> Result<(), TokenError>;
+    
+    /// Acepta la propuesta pendiente
+    /// 
+    /// Requiere autorización del admin propuesto
+    fn accept_admin(env: Env) -> Result<(), TokenError>;
+    
+    /// Renuncia al admin de forma permanente (solo admin actual)
+    /// 
+    /// Después de renunciar nadie puede volver a mintear:
+    /// el supply queda fijo para siempre
+    fn renounce_admin(env: Env) -> Result<(), TokenError>;
+    
+    /// Consulta el admin propuesto pendiente de aceptar
+    fn pending_admin(env: Env) -> Option<Address>;
+    
+    /// Indica si el admin renunció
+    fn admin_renounced(env: Env) -> bool;
 }
 
+/// Circuit breaker: pausa de operaciones ante un incidente
+/// 
+/// Cada clase de operación se pausa por separado, o todas a la vez
+/// con `PauseTarget::All`:
+/// - Transfer: transfer() y transfer_from()
+/// - Mint: mint()
+/// - Burn: burn() y burn_from()
+/// 
+/// approve() y las consultas nunca se pausan.
+pub trait PauseTrait {
+    /// Pausa `target` (solo admin)
+    fn pause(env: Env, target: PauseTarget) -> Result<(), TokenError>;
+    
+    /// Reanuda `target` (solo admin)
+    /// 
+    /// Reanudar `All` no borra las pausas por operación
+    fn unpause(env: Env, target: PauseTarget) -> Result<(), TokenError>;
+    
+    /// Modo "redeem only" (solo admin)
+    /// 
+    /// Pausa transfer y mint pero deja habilitado el burn,
+    /// para que los holders puedan redimir durante un incidente
+    fn set_redeem_only(env: Env) -> Result<(), TokenError>;
+    
+    /// Consulta si `target` está pausado
+    /// 
+    /// Para una operación concreta considera también la pausa global
+    fn is_paused(env: Env, target: PauseTarget) -> bool;
+}
+
+/// Autorización por cuenta (freeze), como en el Stellar Asset Contract
+/// 
+/// Las cuentas están autorizadas por defecto. Una cuenta congelada no
+/// puede enviar ni recibir: transfer, transfer_from, burn, burn_from
+/// y mint fallan con AccountFrozen si involucran su balance.
+pub trait AuthorizationTrait {
+    /// Congela (authorize = false) o descongela (authorize = true) una cuenta
+    /// 
+    /// Requiere autorización del admin
+    fn set_authorized(env: Env, id: Address, authorize: bool) -> Result<(), TokenError>;
+    
+    /// Consulta si la cuenta está autorizada (no congelada)
+    fn authorized(env: Env, id: Address) -> bool;
+}
+
+/// Clawback regulatorio: el admin recupera tokens de un holder
+/// 
+/// Solo disponible si el token se inicializó con clawback_enabled = true.
+/// Es una decisión permanente: un token sin clawback nunca lo podrá activar.
+pub trait ClawbackTrait {
+    /// Quema `amount` tokens de `from` sin la firma del holder (solo admin)
+    /// 
+    /// Reduce el balance de `from` y el total supply. Funciona aunque
+    /// la cuenta esté congelada o las operaciones estén pausadas.
+    fn clawback(env: Env, from: Address, amount: i128) -> Result<(), TokenError>;
+    
+    /// Consulta si el token permite clawback
+    fn clawback_enabled(env: Env) -> bool;
+}
+
+/// Tope de supply verificable on-chain
+/// 
+/// El tope se fija en initialize() y solo se puede bajar, nunca subir.
+/// Así los holders pueden comprobar el máximo de tokens que existirán
+/// sin confiar en el admin.
+pub trait SupplyCapTrait {
+    /// Consulta el tope de supply (None = sin tope)
+    fn max_supply(env: Env) -> Option<i128>;
+    
+    /// Baja el tope de supply (solo admin)
+    /// 
+    /// El nuevo tope debe ser menor al actual y no menor al supply en
+    /// circulación. Si no había tope, fija uno por primera vez.
+    fn lower_max_supply(env: Env, new_max_supply: i128) -> Result<(), TokenError>;
+}
+
+/// Actualizaciones in-place del contrato
+/// 
+/// upgrade() reemplaza el wasm manteniendo el contract ID y todo el
+/// storage (balances, allowances, metadata). Si el nuevo wasm cambia el
+/// esquema de storage, el admin llama migrate() una vez después del upgrade.
+pub trait UpgradeTrait {
+    /// Reemplaza el wasm del contrato (solo admin)
+    /// 
+    /// El wasm debe estar subido previamente a la red
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), TokenError>;
+    
+    /// Migra el storage al esquema de este código (solo admin)
+    /// 
+    /// Corre una sola vez por cada cambio de esquema; si el storage ya
+    /// está al día falla con MigrationNotNeeded.
+    fn migrate(env: Env) -> Result<(), TokenError>;
+    
+    /// Versión del código desplegado
+    fn version(env: Env) -> u32;
+    
+    /// Versión del esquema de storage guardado on-chain
+    fn storage_version(env: Env) -> u32;
+}
+
+/// Operaciones en lote para distribuciones (ej: pago de nómina)
+/// 
+/// Una sola transacción y una sola firma para hasta MAX_BATCH_SIZE
+/// destinatarios. Son todo-o-nada: el total se valida antes de mover
+/// fondos y cualquier error revierte el lote completo. Se emite un
+/// evento por destinatario, igual que en transfer() y mint().
+pub trait BatchTrait {
+    /// Transfiere desde `from` a varios destinatarios
+    /// 
+    /// Requiere autorización de `from` una sola vez
+    fn transfer_batch(
+        env: Env,
+        from: Address,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError>;
+    
+    /// Mintea a varios destinatarios (solo admin)
+    /// 
+    /// Requiere autorización del admin una sola vez
+    fn mint_batch(
+        env: Env,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError>;
+    
+    /// Consulta los balances de varias cuentas (mismo orden que `ids`)
+    fn balances(env: Env, ids: Vec<Address>) -> Vec<i128>;
+}
+
+/// Consultas históricas de balances y supply
+/// 
+/// Cada cambio de balance (transfer, mint, burn y sus variantes) guarda
+/// un checkpoint con el ledger en que ocurrió. El historial completo se
+/// conserva (paginado); solo en un contrato actualizado desde un esquema
+/// sin checkpoints, los ledgers anteriores a la migración fallan con
+/// CheckpointUnavailable.
+pub trait CheckpointTrait {
+    /// Balance de `id` al cierre de `ledger` (debe ser un ledger pasado)
+    fn balance_at(env: Env, id: Address, ledger: u32) -> Result<i128, TokenError>;
+    
+    /// Total supply al cierre de `ledger` (debe ser un ledger pasado)
+    fn total_supply_at(env: Env, ledger: u32) -> Result<i128, TokenError>;
+}
+
+/// Fee sobre transferencias
+/// 
+/// transfer(), transfer_from() y transfer_batch() descuentan un fee
+/// en basis points del monto enviado: el destinatario recibe el neto.
+/// El fee va a la tesorería o se quema. Las cuentas exentas (y la
+/// tesorería) no pagan fee ni como origen ni como destino.
+pub trait FeeTrait {
+    /// Configura el fee (solo admin); 0 lo desactiva
+    /// 
+    /// Falla con InvalidFee si supera MAX_FEE_BPS
+    fn set_transfer_fee(
+        env: Env,
+        fee_bps: u32,
+        destination: FeeDestination
+    ) -> Result<(), TokenError>;
+    
+    /// Marca o desmarca `account` como exenta del fee (solo admin)
+    fn set_fee_exempt(env: Env, account: Address, exempt: bool) -> Result<(), TokenError>;
+    
+    /// Configuración actual del fee (None si nunca se configuró)
+    fn transfer_fee(env: Env) -> Option<FeeConfig>;
+    
+    /// Indica si `account` está exenta del fee
+    fn is_fee_exempt(env: Env, account: Address) -> bool;
+    
+    /// Fee y neto de transferir `amount` de `from` a `to`
+    /// 
+    /// Aplica las mismas exenciones que transfer(): cuentas exentas y tesorería
+    fn quote_transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<TransferQuote, TokenError>;
+}
+
+/// Metadatos extendidos del token (descripción, ícono, SEP-1)
+pub trait MetadataTrait {
+    /// Metadatos completos del token
+    fn metadata(env: Env) -> Result<TokenMetadata, TokenError>;
+    
+    /// Reemplaza los metadatos (solo admin)
+    /// 
+    /// Vuelve a validar los límites de longitud; los decimales no
+    /// se pueden cambiar porque alterarían todos los balances.
+    fn update_metadata(env: Env, metadata: TokenMetadata) -> Result<(), TokenError>;
+}
+
+/// Ajustes relativos del allowance
+/// 
+/// Alternativa a approve() que no pisa el valor anterior, así el spender
+/// no puede adelantarse y gastar el viejo y el nuevo. Un allowance de
+/// i128::MAX (UNLIMITED_ALLOWANCE) es ilimitado: transfer_from() y
+/// burn_from() no lo descuentan.
+pub trait AllowanceTrait {
+    /// Suma `amount` al allowance de `spender` (autoriza `from`)
+    /// 
+    /// `expiration_ledger` reemplaza la expiración actual.
+    /// Falla con OverflowError si el resultado supera i128::MAX
+    fn increase_allowance(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32
+    ) -> Result<(), TokenError>;
+    
+    /// Resta `amount` del allowance de `spender` (autoriza `from`)
+    /// 
+    /// Conserva la expiración actual. Falla con InsufficientAllowance
+    /// si el allowance es menor a `amount`
+    fn decrease_allowance(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128
+    ) -> Result<(), TokenError>;
+}
+
+/// Enumeración de holders y allowances
+/// 
+/// Las keys de balance no se pueden recorrer, así que el núcleo mantiene
+/// una lista de cuentas con balance > 0 y, por owner, la lista de
+/// spenders con allowance. Ambas se actualizan junto con la eliminación
+/// de keys en 0 (transfer, burn, approve...).
+pub trait HoldersTrait {
+    /// Cantidad de cuentas con balance > 0
+    /// 
+    /// Las consultas de listas fallan con ListsIncomplete en un contrato
+    /// migrado desde un esquema < 3: los holders y spenders anteriores no
+    /// figuran y no se pueden reconstruir on-chain.
+    fn holder_count(env: Env) -> Result<u32, TokenError>;
+    
+    /// Holders desde la posición `start`, hasta `limit` (máximo MAX_PAGE_SIZE)
+    /// 
+    /// El orden no es estable: cuando una cuenta queda en 0, el último
+    /// holder pasa a ocupar su posición.
+    fn holders(env: Env, start: u32, limit: u32) -> Result<Vec<Address>, TokenError>;
+    
+    /// Cantidad de spenders con un allowance guardado sobre los tokens de `owner`
+    /// 
+    /// Incluye los vencidos: es el total de posiciones de allowances_of()
+    fn spender_count(env: Env, owner: Address) -> Result<u32, TokenError>;
+    
+    /// Spenders con allowance vigente (> 0 y sin vencer) sobre los tokens de `owner`
+    /// 
+    /// Recorre las posiciones `start..start + limit` (máximo MAX_PAGE_SIZE)
+    /// y omite los vencidos, así que una página puede traer menos de `limit`.
+    /// Incluye a los spenders con un allowance periódico sin vencer.
+    fn allowances_of(env: Env, owner: Address, start: u32, limit: u32) -> Result<Vec<Address>, TokenError>;
+}
+
+/// Transferencias y mints con memo (referencia de pago)
+/// 
+/// El memo (hasta MAX_MEMO_LENGTH caracteres) viaja en TransferEvent /
+/// MintEvent. Una cuenta puede exigir memo: las transferencias que
+/// recibe sin memo (ni id muxed) fallan con MemoRequired.
+pub trait MemoTrait {
+    /// transfer() con memo; requiere autorización de `from`
+    fn transfer_with_memo(
+        env: Env,
+        from: Address,
+        to: MuxedAddress,
+        amount: i128,
+        memo: String
+    ) -> Result<(), TokenError>;
+    
+    /// mint() con memo (solo admin)
+    fn mint_with_memo(
+        env: Env,
+        to: Address,
+        amount: i128,
+        memo: String
+    ) -> Result<(), TokenError>;
+    
+    /// transfer_from() con memo; requiere autorización de `spender`
+    fn transfer_from_with_memo(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+        memo: String
+    ) -> Result<(), TokenError>;
+    
+    /// transfer_batch() con un memo por destinatario; requiere autorización de `from`
+    fn transfer_batch_with_memo(
+        env: Env,
+        from: Address,
+        recipients: Vec<(Address, i128, String)>
+    ) -> Result<(), TokenError>;
+    
+    /// Activa o desactiva el requisito de memo de `id` (requiere su autorización)
+    fn set_memo_required(env: Env, id: Address, required: bool) -> Result<(), TokenError>;
+    
+    /// Indica si `id` exige memo en las transferencias que recibe
+    fn memo_required(env: Env, id: Address) -> bool;
+}
+
+/// Allowances periódicos (nóminas, suscripciones)
+/// 
+/// El spender puede retirar hasta `limit` por período de
+/// `period_ledgers` ledgers; el límite se renueva solo al empezar cada
+/// período. Mientras exista un allowance periódico vigente,
+/// transfer_from() y burn_from() lo usan en lugar del de approve(): el
+/// común no se descuenta ni se suma, y vuelve a usarse cuando el periódico
+/// se revoca o vence. allowance() solo reporta el común.
+pub trait PeriodicAllowanceTrait {
+    /// Aprueba a `spender` a gastar hasta `limit` por período (autoriza `from`)
+    /// 
+    /// El primer período empieza en el ledger actual. `limit` = 0 lo
+    /// revoca. Falla con InvalidPeriod si `period_ledgers` es 0
+    fn approve_periodic(
+        env: Env,
+        from: Address,
+        spender: Address,
+        limit: i128,
+        period_ledgers: u32,
+        expiration_ledger: u32
+    ) -> Result<(), TokenError>;
+    
+    /// Allowance periódico vigente con el período al día (None si no hay)
+    fn periodic_allowance(env: Env, from: Address, spender: Address) -> Option<PeriodicAllowance>;
+    
+    /// Lo que `spender` todavía puede gastar en el período actual
+    fn spendable_allowance(env: Env, from: Address, spender: Address) -> i128;
+}
+
+/// WorxToken no agrega lógica a los cambios de balance
+impl TokenHooks for WorxToken {}
+
 /// Implementación del contrato
+/// 
+/// La lógica vive en token_core; aquí solo se resuelve la autorización
+/// (todo lo privilegiado lo firma el admin) y el clawback.
 #[contractimpl]
 impl TokenTrait for WorxToken {
     fn initialize(
@@ -167,442 +529,426 @@ impl TokenTrait for WorxToken {
         admin: Address, 
         name: String, 
         symbol: String,
-        decimals: u32
+        decimals: u32,
+        clawback_enabled: bool,
+        max_supply: Option<i128>
     ) -> Result<(), TokenError> {
-        // 1. Verificar que no esté inicializado
-        if env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::AlreadyInitialized);
-        }
-        
-        // 2. Validar decimales (máximo 18 como Ethereum)
-        if decimals > MAX_DECIMALS {
-            return Err(TokenError::InvalidDecimals);
-        }
-        
-        // 3. Validar metadatos (name y symbol no vacíos)
-        if name.is_empty() || name.len() as u32 > MAX_NAME_LENGTH {
-            return Err(TokenError::InvalidMetadata);
-        }
-        
-        if symbol.is_empty() || symbol.len() as u32 > MAX_SYMBOL_LENGTH {
-            return Err(TokenError::InvalidMetadata);
-        }
-        
-        // 4. Guardar metadata en instance storage
-        env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage().instance().set(&DataKey::TokenName, &name);
-        env.storage().instance().set(&DataKey::TokenSymbol, &symbol);
-        env.storage().instance().set(&DataKey::Decimals, &decimals);
-        env.storage().instance().set(&DataKey::TotalSupply, &0i128);
-        env.storage().instance().set(&DataKey::Initialized, &true);
-        
-        // 5. Extender TTL del storage de instance (30 días)
-        env.storage().instance().extend_ttl(100_000, 200_000);
-        
-        // 6. Emitir evento rico con todos los metadatos
-		InitEvent {
-			admin: admin.clone(),
-			name: name.clone(),       
-			symbol: symbol.clone(),   
-			decimals,                 
-		};
+        // 1. Validar y guardar admin, metadatos y tope
+        base::initialize(&env, &admin, name.clone(), symbol.clone(), decimals)?;
+        capped::initialize(&env, max_supply)?;
+        
+        // 2. El clawback se decide una sola vez
+        storage::write_clawback_enabled(&env, clawback_enabled);
+        
+        // 3. Emitir evento rico con todos los metadatos
+        InitEvent {
+            admin,
+            name,
+            symbol,
+            decimals,
+            clawback_enabled,
+        }.publish(&env);
         
         Ok(())
     }
     
     fn mint(env: Env, to: Address, amount: i128) -> Result<(), TokenError> {
-        // 1. Verificar inicialización
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::NotInitialized);
-        }
-        
-        // 2. Solo el admin puede mintear
-        let admin: Address = env.storage().instance()
-            .get(&DataKey::Admin)
-            .ok_or(TokenError::NotInitialized)?;
-        admin.require_auth();
-        
-        // 3. Validaciones
-        if amount <= 0 {
-            return Err(TokenError::InvalidAmount);
-        }
-        
-        // 4. Validar que `to` no sea igual a `admin` (opcional, pero buena práctica)
-        // Esto evita que el admin se mintee tokens a sí mismo por error
-        
-        // 5. Obtener balance actual y verificar overflow
-        let balance = Self::balance(env.clone(), to.clone());
-        let new_balance = balance.checked_add(amount)
-            .ok_or(TokenError::OverflowError)?;
-        
-        // 6. Actualizar balance con TTL extendido
-        env.storage().persistent().set(
-            &DataKey::Balance(to.clone()), 
-            &new_balance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Balance(to.clone()), 
-            100_000,
-            200_000
-        );
-        
-        // 7. Actualizar total supply
-        let total: i128 = env.storage().instance()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0);
-        let new_total = total.checked_add(amount)
-            .ok_or(TokenError::OverflowError)?;
-        env.storage().instance().set(
-            &DataKey::TotalSupply, 
-            &new_total
-        );
-        
-        // 8. Emitir evento detallado
-        MintEvent {
-            to: to.clone(),
-            amount,
-            new_balance,
-            new_supply: new_total,
-        };
-        
-        Ok(())
+        // Solo el admin puede mintear (si renunció, el mint queda deshabilitado)
+        require_admin(&env)?;
+        mintable::mint::<Self>(&env, to, amount)
     }
     
     fn burn(env: Env, from: Address, amount: i128) -> Result<(), TokenError> {
-        // 1. Verificar inicialización
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::NotInitialized);
-        }
-        
-        // 2. Requiere autorización del dueño de los tokens
-        from.require_auth();
-        
-        // 3. Validaciones
-        if amount <= 0 {
-            return Err(TokenError::InvalidAmount);
-        }
-        
-        let balance = Self::balance(env.clone(), from.clone());
-        if balance < amount {
-            return Err(TokenError::InsufficientBalance);
-        }
-        
-        // 4. Actualizar balance
-        let new_balance = balance - amount;
-        if new_balance == 0 {
-            // Optimización: eliminar key si balance = 0
-            env.storage().persistent().remove(&DataKey::Balance(from.clone()));
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Balance(from.clone()), 
-                &new_balance
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Balance(from.clone()), 
-                100_000,
-                200_000
-            );
-        }
-        
-        // 5. Actualizar total supply
-        let total: i128 = env.storage().instance()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0);
-        let new_total = total.checked_sub(amount)
-            .ok_or(TokenError::OverflowError)?;
-        env.storage().instance().set(
-            &DataKey::TotalSupply,
-            &new_total
-        );
-        
-        // 6. Emitir evento
-        BurnEvent {
-            from: from.clone(),
-            amount,
-            new_balance,
-            new_supply: new_total,
-        };
-        
-        Ok(())
+        burnable::burn::<Self>(&env, from, amount)
     }
     
-    fn balance(env: Env, account: Address) -> i128 {
-        env.storage().persistent()
-            .get(&DataKey::Balance(account))
-            .unwrap_or(0)
+    fn burn_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        amount: i128
+    ) -> Result<(), TokenError> {
+        burnable::burn_from::<Self>(&env, spender, from, amount)
+    }
+    
+    fn balance(env: Env, id: Address) -> i128 {
+        base::balance(&env, &id)
     }
     
     fn transfer(
         env: Env, 
         from: Address, 
-        to: Address, 
+        to: MuxedAddress, 
         amount: i128
     ) -> Result<(), TokenError> {
-        // 1. Verificar inicialización
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::NotInitialized);
-        }
-        
-        // 2. Verificar autorización del sender
-        from.require_auth();
-        
-        // 3. Validaciones
-        if amount <= 0 {
-            return Err(TokenError::InvalidAmount);
-        }
-        
-        // 4. No permitir transferencia a sí mismo (gas-efficient)
-        if from == to {
-            return Err(TokenError::InvalidRecipient);
-        }
-        
-        let from_balance = Self::balance(env.clone(), from.clone());
-        if from_balance < amount {
-            return Err(TokenError::InsufficientBalance);
-        }
-        
-        // 5. Calcular nuevos balances con verificación de overflow
-        let new_from_balance = from_balance - amount;
-        let to_balance = Self::balance(env.clone(), to.clone());
-        let new_to_balance = to_balance.checked_add(amount)
-            .ok_or(TokenError::OverflowError)?;
-        
-        // 6. Actualizar balances con TTL
-        if new_from_balance == 0 {
-            env.storage().persistent().remove(&DataKey::Balance(from.clone()));
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Balance(from.clone()), 
-                &new_from_balance
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Balance(from.clone()), 
-                100_000,
-                200_000
-            );
-        }
-        
-        env.storage().persistent().set(
-            &DataKey::Balance(to.clone()), 
-            &new_to_balance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Balance(to.clone()), 
-            100_000,
-            200_000
-        );
-        
-        // 7. Emitir evento con balances post-transferencia
-        TransferEvent {
-            from: from.clone(),
-            to: to.clone(),
-            amount,
-            from_new_balance: new_from_balance,  
-            to_new_balance: new_to_balance,        
-        };
-                
-        Ok(())
+        // El balance se acredita a la cuenta base de la dirección muxed
+        base::transfer::<Self>(&env, from, to, amount)
     }
     
     fn approve(
         env: Env, 
         from: Address, 
         spender: Address, 
+        amount: i128,
+        expiration_ledger: u32
+    ) -> Result<(), TokenError> {
+        base::approve(&env, from, spender, amount, expiration_ledger)
+    }
+    
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        base::allowance(&env, &from, &spender)
+    }
+
+    fn transfer_from(
+        env: Env, 
+        spender: Address, 
+        from: Address, 
+        to: Address, 
         amount: i128
     ) -> Result<(), TokenError> {
-        // 1. Verificar inicialización
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::NotInitialized);
-        }
+        base::transfer_from::<Self>(&env, spender, from, to, amount)
+    }
+    
+    // Métodos de consulta
+    fn name(env: Env) -> String {
+        base::name(&env)
+    }
+    
+    fn symbol(env: Env) -> String {
+        base::symbol(&env)
+    }
+    
+    fn decimals(env: Env) -> u32 {
+        base::decimals(&env)
+    }
+    
+    fn total_supply(env: Env) -> i128 {
+        base::total_supply(&env)
+    }
+    
+    fn admin(env: Env) -> Option<Address> {
+        base::admin(&env)
+    }
+}
+
+#[contractimpl]
+impl AdminTrait for WorxToken {
+    fn propose_admin(env: Env, new_admin: Address) -> Result<(), TokenError> {
+        admin::propose_admin(&env, new_admin)
+    }
+    
+    fn accept_admin(env: Env) -> Result<(), TokenError> {
+        admin::accept_admin(&env)
+    }
+    
+    fn renounce_admin(env: Env) -> Result<(), TokenError> {
+        admin::renounce_admin(&env)
+    }
+    
+    fn pending_admin(env: Env) -> Option<Address> {
+        admin::pending_admin(&env)
+    }
+    
+    fn admin_renounced(env: Env) -> bool {
+        admin::admin_renounced(&env)
+    }
+}
+
+#[contractimpl]
+impl PauseTrait for WorxToken {
+    fn pause(env: Env, target: PauseTarget) -> Result<(), TokenError> {
+        // Solo el admin puede pausar
+        let caller = require_admin(&env)?;
+        pausable::pause(&env, caller, target);
         
-        // 2. Verificar autorización del owner
-        from.require_auth();
+        Ok(())
+    }
+    
+    fn unpause(env: Env, target: PauseTarget) -> Result<(), TokenError> {
+        // Solo el admin puede pausar
+        let caller = require_admin(&env)?;
+        pausable::unpause(&env, caller, target);
         
-        // 3. Validación: amount debe ser >= 0 (permitir 0 para revocar)
-        if amount < 0 {
-            return Err(TokenError::InvalidAmount);
-        }
+        Ok(())
+    }
+    
+    fn set_redeem_only(env: Env) -> Result<(), TokenError> {
+        // Solo el admin puede pausar
+        let caller = require_admin(&env)?;
+        pausable::set_redeem_only(&env, caller);
         
-        // 4. Obtener allowance anterior para el evento
-        let old_allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
+        Ok(())
+    }
+    
+    fn is_paused(env: Env, target: PauseTarget) -> bool {
+        pausable::is_paused(&env, target)
+    }
+}
+
+#[contractimpl]
+impl AuthorizationTrait for WorxToken {
+    fn set_authorized(env: Env, id: Address, authorize: bool) -> Result<(), TokenError> {
+        admin::set_authorized(&env, id, authorize)
+    }
+    
+    fn authorized(env: Env, id: Address) -> bool {
+        admin::authorized(&env, &id)
+    }
+}
+
+#[contractimpl]
+impl ClawbackTrait for WorxToken {
+    fn clawback(env: Env, from: Address, amount: i128) -> Result<(), TokenError> {
+        // 1. Solo el admin puede recuperar tokens
+        let admin = require_admin(&env)?;
+        
+        // 2. Verificar que el token permita clawback
+        if !storage::read_clawback_enabled(&env) {
+            return Err(TokenError::ClawbackDisabled);
+        }
         
-        // 5. Actualizar allowance
-        if amount == 0 {
-            // Optimización: eliminar key si allowance = 0
-            env.storage().persistent().remove(
-                &DataKey::Allowance(from.clone(), spender.clone()) 
-            );
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Allowance(from.clone(), spender.clone()), 
-                &amount
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Allowance(from.clone(), spender.clone()), 
-                100_000,
-                200_000
-            );
+        // 3. Validaciones
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
         }
         
-        // 6. Evento mejorado con allowance anterior y nuevo
-        ApproveEvent {
+        // 4. Actualizar balance y total supply (sin firma del holder)
+        let (new_balance, new_total) = base::burn_balance::<Self>(&env, &from, amount)?;
+        
+        // 5. Emitir evento detallado y el estándar
+        ClawbackEvent {
+            admin,
             from: from.clone(),
-            spender: spender.clone(),
-            old_allowance,
-            new_allowance: amount,
-        };
+            amount,
+            new_balance,
+            new_supply: new_total,
+        }.publish(&env);
+        Sep41ClawbackEvent { from, amount }.publish(&env);
         
         Ok(())
     }
     
-    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
-    env.storage().persistent()
-        .get(&DataKey::Allowance(from.clone(), spender.clone()))
-        .unwrap_or(0)
-}
-
-fn transfer_from(
-    env: Env, 
-    spender: Address, 
-    from: Address, 
-    to: Address, 
-    amount: i128
-) -> Result<(), TokenError> {
-    // 1. Verificar inicialización
-    if !env.storage().instance().has(&DataKey::Initialized) {
-        return Err(TokenError::NotInitialized);
-    }
-    
-    // 2. Verificar autorización del spender
-    spender.require_auth();
-    
-    // 3. Validaciones
-    if amount <= 0 {
-        return Err(TokenError::InvalidAmount);
-    }
-    
-    // 4. No permitir transferencia a sí mismo
-    if to == from {
-        return Err(TokenError::InvalidRecipient);
-    }
-    
-    // 5. Verificar allowance
-    let allowed = Self::allowance(env.clone(), from.clone(), spender.clone());
-    if allowed < amount {
-        return Err(TokenError::InsufficientAllowance);
-    }
-    
-    // 6. Verificar balance
-    let from_balance = Self::balance(env.clone(), from.clone());
-    if from_balance < amount {
-        return Err(TokenError::InsufficientBalance);
-    }
-    
-    // 7. Calcular nuevos valores
-    let new_from_balance = from_balance - amount;
-    let to_balance = Self::balance(env.clone(), to.clone());
-    let new_to_balance = to_balance.checked_add(amount)
-        .ok_or(TokenError::OverflowError)?;
-    let new_allowance = allowed - amount;
-    
-    // 8. Actualizar estado atómicamente
-    if new_from_balance == 0 {
-        env.storage().persistent().remove(&DataKey::Balance(from.clone()));
-    } else {
-        env.storage().persistent().set(
-            &DataKey::Balance(from.clone()), 
-            &new_from_balance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Balance(from.clone()), 
-            100_000,
-            200_000
-        );
-    }
-    
-    env.storage().persistent().set(
-        &DataKey::Balance(to.clone()), 
-        &new_to_balance
-    );
-    env.storage().persistent().extend_ttl(
-        &DataKey::Balance(to.clone()), 
-        100_000,
-        200_000
-    );
-    
-    if new_allowance == 0 {
-        env.storage().persistent().remove(
-            &DataKey::Allowance(from.clone(), spender.clone()) 
-        );
-    } else {
-        env.storage().persistent().set(
-            &DataKey::Allowance(from.clone(), spender.clone()), 
-            &new_allowance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Allowance(from.clone(), spender.clone()), 
-            100_000,
-            200_000
-        );
-    }
-    
-		// 9. Emitir evento 
-		TransferFromEvent {
-		    spender: spender.clone(),
-		    from: from.clone(), 
-		    to: to.clone(),
-		    amount: amount,
-		    from_new_balance: new_from_balance,
-		    to_new_balance: new_to_balance,
-		    new_allowance: new_allowance,
-		};
-		
-		Ok(())
-	}
+    fn clawback_enabled(env: Env) -> bool {
+        storage::read_clawback_enabled(&env)
+    }
+}
 
+#[contractimpl]
+impl SupplyCapTrait for WorxToken {
+    fn max_supply(env: Env) -> Option<i128> {
+        capped::max_supply(&env)
+    }
     
-    // Métodos de consulta
-    fn name(env: Env) -> String {
-        // Verificar inicialización antes de devolver metadata
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return String::from_str(&env, "");
-        }
-        
-        env.storage().instance()
-            .get(&DataKey::TokenName)
-            .unwrap_or(String::from_str(&env, "")) 
+    fn lower_max_supply(env: Env, new_max_supply: i128) -> Result<(), TokenError> {
+        capped::lower_max_supply(&env, new_max_supply)
+    }
+}
+
+#[contractimpl]
+impl UpgradeTrait for WorxToken {
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), TokenError> {
+        admin::upgrade(&env, new_wasm_hash)
     }
     
-    fn symbol(env: Env) -> String {
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return String::from_str(&env, ""); 
-        }
-        
-        env.storage().instance()
-            .get(&DataKey::TokenSymbol)
-            .unwrap_or(String::from_str(&env, ""))
+    fn migrate(env: Env) -> Result<(), TokenError> {
+        admin::migrate(&env)
     }
     
-    fn decimals(env: Env) -> u32 {
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return 0;
-        }
-        
-        env.storage().instance()
-            .get(&DataKey::Decimals)
-            .unwrap_or(0)
+    fn version(_env: Env) -> u32 {
+        CONTRACT_VERSION
     }
     
-    fn total_supply(env: Env) -> i128 {
-        env.storage().instance()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0)
+    fn storage_version(env: Env) -> u32 {
+        admin::storage_version(&env)
+    }
+}
+
+#[contractimpl]
+impl BatchTrait for WorxToken {
+    fn transfer_batch(
+        env: Env,
+        from: Address,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError> {
+        base::transfer_batch::<Self>(&env, from, recipients)
+    }
+    
+    fn mint_batch(
+        env: Env,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError> {
+        // Solo el admin puede mintear
+        require_admin(&env)?;
+        mintable::mint_batch::<Self>(&env, recipients)
+    }
+    
+    fn balances(env: Env, ids: Vec<Address>) -> Vec<i128> {
+        base::balances(&env, ids)
+    }
+}
+
+#[contractimpl]
+impl CheckpointTrait for WorxToken {
+    fn balance_at(env: Env, id: Address, ledger: u32) -> Result<i128, TokenError> {
+        base::balance_at(&env, &id, ledger)
+    }
+    
+    fn total_supply_at(env: Env, ledger: u32) -> Result<i128, TokenError> {
+        base::total_supply_at(&env, ledger)
+    }
+}
+
+#[contractimpl]
+impl FeeTrait for WorxToken {
+    fn set_transfer_fee(
+        env: Env,
+        fee_bps: u32,
+        destination: FeeDestination
+    ) -> Result<(), TokenError> {
+        fee::set_transfer_fee(&env, fee_bps, destination)
+    }
+    
+    fn set_fee_exempt(env: Env, account: Address, exempt: bool) -> Result<(), TokenError> {
+        fee::set_fee_exempt(&env, account, exempt)
+    }
+    
+    fn transfer_fee(env: Env) -> Option<FeeConfig> {
+        fee::fee_config(&env)
+    }
+    
+    fn is_fee_exempt(env: Env, account: Address) -> bool {
+        fee::is_fee_exempt(&env, &account)
+    }
+    
+    fn quote_transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<TransferQuote, TokenError> {
+        fee::quote_transfer(&env, &from, &to, amount)
+    }
+}
+
+#[contractimpl]
+impl MetadataTrait for WorxToken {
+    fn metadata(env: Env) -> Result<TokenMetadata, TokenError> {
+        base::metadata(&env)
+    }
+    
+    fn update_metadata(env: Env, metadata: TokenMetadata) -> Result<(), TokenError> {
+        // Solo el admin actualiza los metadatos
+        let admin = require_admin(&env)?;
+        base::update_metadata(&env, admin, metadata)
+    }
+}
+
+#[contractimpl]
+impl AllowanceTrait for WorxToken {
+    fn increase_allowance(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32
+    ) -> Result<(), TokenError> {
+        base::increase_allowance(&env, from, spender, amount, expiration_ledger)
+    }
+    
+    fn decrease_allowance(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128
+    ) -> Result<(), TokenError> {
+        base::decrease_allowance(&env, from, spender, amount)
+    }
+}
+
+#[contractimpl]
+impl PeriodicAllowanceTrait for WorxToken {
+    fn approve_periodic(
+        env: Env,
+        from: Address,
+        spender: Address,
+        limit: i128,
+        period_ledgers: u32,
+        expiration_ledger: u32
+    ) -> Result<(), TokenError> {
+        periodic::approve_periodic(&env, from, spender, limit, period_ledgers, expiration_ledger)
+    }
+    
+    fn periodic_allowance(env: Env, from: Address, spender: Address) -> Option<PeriodicAllowance> {
+        periodic::periodic_allowance(&env, &from, &spender)
+    }
+    
+    fn spendable_allowance(env: Env, from: Address, spender: Address) -> i128 {
+        periodic::spendable_allowance(&env, &from, &spender)
+    }
+}
+
+#[contractimpl]
+impl HoldersTrait for WorxToken {
+    fn holder_count(env: Env) -> Result<u32, TokenError> {
+        base::holder_count(&env)
+    }
+    
+    fn holders(env: Env, start: u32, limit: u32) -> Result<Vec<Address>, TokenError> {
+        base::holders(&env, start, limit)
+    }
+    
+    fn spender_count(env: Env, owner: Address) -> Result<u32, TokenError> {
+        base::spender_count(&env, &owner)
+    }
+    
+    fn allowances_of(env: Env, owner: Address, start: u32, limit: u32) -> Result<Vec<Address>, TokenError> {
+        base::allowances_of(&env, &owner, start, limit)
+    }
+}
+
+#[contractimpl]
+impl MemoTrait for WorxToken {
+    fn transfer_with_memo(
+        env: Env,
+        from: Address,
+        to: MuxedAddress,
+        amount: i128,
+        memo: String
+    ) -> Result<(), TokenError> {
+        base::transfer_with_memo::<Self>(&env, from, to, amount, Some(memo))
+    }
+    
+    fn mint_with_memo(
+        env: Env,
+        to: Address,
+        amount: i128,
+        memo: String
+    ) -> Result<(), TokenError> {
+        // Solo el admin puede mintear
+        require_admin(&env)?;
+        mintable::mint_with_memo::<Self>(&env, to, amount, Some(memo))
+    }
+    
+    fn transfer_from_with_memo(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+        memo: String
+    ) -> Result<(), TokenError> {
+        base::transfer_from_with_memo::<Self>(&env, spender, from, to, amount, Some(memo))
+    }
+    
+    fn transfer_batch_with_memo(
+        env: Env,
+        from: Address,
+        recipients: Vec<(Address, i128, String)>
+    ) -> Result<(), TokenError> {
+        base::transfer_batch_with_memo::<Self>(&env, from, recipients)
+    }
+    
+    fn set_memo_required(env: Env, id: Address, required: bool) -> Result<(), TokenError> {
+        base::set_memo_required(&env, id, required)
     }
     
-    fn admin(env: Env) -> Address {
-        env.storage().instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not initialized")
+    fn memo_required(env: Env, id: Address) -> bool {
+        base::memo_required(&env, &id)
     }
 }
 
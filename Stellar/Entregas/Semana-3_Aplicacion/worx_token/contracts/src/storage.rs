@@ -1,52 +1,29 @@
 // src/storage.rs
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Env};
 
-/// Enum que define todas las claves de almacenamiento
+// Claves y helpers comunes (balances, allowances, pausa, fee...)
+pub use token_core::storage::*;
+
+/// Claves de almacenamiento propias de WorxToken
 /// 
-/// Separamos los datos en dos tipos de storage:
-/// - Instance Storage: Metadatos globales (más barato)
-/// - Persistent Storage: Datos de usuarios (requiere TTL)
+/// Se suman a las `DataKey` del núcleo. El nombre de la variante es el
+/// mismo que antes de extraer token_core, así que la key ya guardada
+/// sigue siendo válida.
 #[contracttype]
-pub enum DataKey {
-    /// Balance de cada usuario - Persistent Storage
-    /// Usa Address como key para acceso O(1)
-    Balance(Address),
-    
-    /// Permisos de gasto entre usuarios - Persistent Storage
-    /// Tupla (owner, spender) para lookup eficiente
-    Allowance(Address, Address),
-    
-    /// Supply total de tokens - Instance Storage
-    /// Contador global de tokens en circulación
-    TotalSupply,
-    
-    /// Dirección del administrador - Instance Storage
-    /// Solo esta cuenta puede mintear tokens
-    Admin,
-    
-    /// Nombre del token - Instance Storage
-    /// Ejemplo: "Buen Día Builders Token"
-    TokenName,
-    
-    /// Símbolo del token - Instance Storage
-    /// Ejemplo: "BDB", "USDC", etc (máximo 32 caracteres)
-    TokenSymbol,
-    
-    /// Número de decimales - Instance Storage
-    /// Típicamente 7 para Stellar (alineado con XLM)
-    Decimals,
-    
-    /// Flag para verificar inicialización - Instance Storage
-    /// Previene re-inicialización del contrato
-    Initialized,
+pub enum ExtensionKey {
+    /// Flag de clawback - Instance Storage
+    /// Se fija en initialize() y no se puede cambiar
+    ClawbackEnabled,
 }
 
-/// Metadata struct para almacenar información del token
-/// Usado en initialize() para pasar múltiples parámetros
-#[contracttype]
-#[derive(Clone)]
-pub struct TokenMetadata {
-    pub name: String,
-    pub symbol: String,
-    pub decimals: u32,
-}
\ No newline at end of file
+/// Indica si el token permite clawback
+pub fn read_clawback_enabled(env: &Env) -> bool {
+    env.storage().instance()
+        .get(&ExtensionKey::ClawbackEnabled)
+        .unwrap_or(false)
+}
+
+/// Fija el flag de clawback (solo en initialize())
+pub fn write_clawback_enabled(env: &Env, enabled: bool) {
+    env.storage().instance().set(&ExtensionKey::ClawbackEnabled, &enabled);
+}
@@ -1,13 +1,19 @@
 // src/test.rs
 #![cfg(test)]
+extern crate std;
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Events},
-    Address, Env, String, symbol_short,
+    testutils::{Address as _, Events, Ledger, MuxedAddress as _},
+    token::TokenClient,
+    vec, Address, BytesN, Env, Event, MuxedAddress, String,
 };
 
+use crate::storage::DataKey;
 use crate::WorxTokenClient;
+use soroban_sdk::Map;
+use token_core::fee::MAX_FEE_BPS;
+use token_core::{MAX_METADATA_ENTRIES, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URL_LENGTH};
 
 /// Test básico de inicialización del token
 /// 
@@ -27,7 +33,7 @@ fn test_initialize() {
     let symbol = String::from_str(&env, "WORX");
     
     // Act: Inicializar el token
-    let result = client.initialize(&admin, &name, &symbol, &7);
+    client.initialize(&admin, &name, &symbol, &7, &true, &None);
     
     // Assert: Verificar que los metadatos se guardaron correctamente
     assert_eq!(client.name(), name);
@@ -51,10 +57,10 @@ fn test_initialize_twice_fails() {
     let symbol = String::from_str(&env, "TOK");
     
     // Primera inicialización debe funcionar
-    client.initialize(&admin, &name, &symbol, &7);
+    client.initialize(&admin, &name, &symbol, &7, &true, &None);
     
     // Segunda debe fallar con AlreadyInitialized
-    let result = client.try_initialize(&admin, &name, &symbol, &7);
+    let result = client.try_initialize(&admin, &name, &symbol, &7, &true, &None);
     assert_eq!(result, Err(Ok(TokenError::AlreadyInitialized)));
 }
 
@@ -76,7 +82,9 @@ fn test_invalid_decimals() {
         &admin,
         &String::from_str(&env, "Token"), 
         &String::from_str(&env, "TOK"),   
-        &19  // ❌ Inválido: excede MAX_DECIMALS (18)
+        &19, // ❌ Inválido: excede MAX_DECIMALS (18)
+        &true,
+        &None
     );
     assert_eq!(result, Err(Ok(TokenError::InvalidDecimals)));
 }
@@ -102,7 +110,9 @@ fn test_mint_and_balance() {
         &admin, 
         &String::from_str(&env, "WORX Token"), 
         &String::from_str(&env, "WORX"),           
-        &7
+        &7,
+        &true,
+        &None
     );
     
     // Mock auth: En tests, simulamos autorizaciones sin firmas reales
@@ -133,7 +143,9 @@ fn test_mint_zero_fails() {
         &admin,
         &String::from_str(&env, "Token"), 
         &String::from_str(&env, "TOK"),   
-        &7
+        &7,
+        &true,
+        &None
     );
     
     env.mock_all_auths();
@@ -164,7 +176,9 @@ fn test_transfer() {
         &admin,
         &String::from_str(&env, "WORX Token"), 
         &String::from_str(&env, "WORX"),           
-        &7
+        &7,
+        &true,
+        &None
     );
     
     env.mock_all_auths();
@@ -196,7 +210,9 @@ fn test_transfer_insufficient_balance() {
         &admin,
         &String::from_str(&env, "Token"), 
         &String::from_str(&env, "TOK"),   
-        &7
+        &7,
+        &true,
+        &None
     );
     
     env.mock_all_auths();
@@ -226,7 +242,9 @@ fn test_transfer_to_self() {
         &admin,
         &String::from_str(&env, "Token"), 
         &String::from_str(&env, "TOK"),   
-        &7
+        &7,
+        &true,
+        &None
     );
     
     env.mock_all_auths();
@@ -260,14 +278,16 @@ fn test_approve_and_transfer_from() {
         &admin,
         &String::from_str(&env, "Token"), 
         &String::from_str(&env, "TOK"),   
-        &7
+        &7,
+        &true,
+        &None
     );
     
     env.mock_all_auths();
     client.mint(&alice, &1000);
     
     // Alice aprueba a Bob para gastar hasta 300 tokens
-    client.approve(&alice, &bob, &300);
+    client.approve(&alice, &bob, &300, &1000);
     assert_eq!(client.allowance(&alice, &bob), 300);
     
     // Bob transfiere 200 tokens de Alice a Charlie
@@ -297,12 +317,14 @@ fn test_transfer_from_insufficient_allowance() {
         &admin,
         &String::from_str(&env, "Token"), 
         &String::from_str(&env, "TOK"),   
-        &7
+        &7,
+        &true,
+        &None
     );
     
     env.mock_all_auths();
     client.mint(&alice, &1000);
-    client.approve(&alice, &bob, &100);  // Solo 100 aprobados
+    client.approve(&alice, &bob, &100, &1000);  // Solo 100 aprobados
     
     // Bob intenta transferir más de lo aprobado
     let result = client.try_transfer_from(&bob, &alice, &charlie, &200);
@@ -326,7 +348,9 @@ fn test_burn() {
         &admin,
         &String::from_str(&env, "Token"), 
         &String::from_str(&env, "TOK"),   
-        &7
+        &7,
+        &true,
+        &None
     );
     
     env.mock_all_auths();
@@ -386,7 +410,9 @@ fn test_initialize_empty_name_fails() {
         &admin,
         &String::from_str(&env, ""),  // Nombre vacío
         &String::from_str(&env, "WORX"),
-        &7
+        &7,
+        &true,
+        &None
     );
     assert_eq!(result, Err(Ok(TokenError::InvalidMetadata)));
 }
@@ -405,7 +431,2613 @@ fn test_initialize_empty_symbol_fails() {
         &admin,
         &String::from_str(&env, "WORX Token"),
         &String::from_str(&env, ""),  // Símbolo vacío
-        &7
+        &7,
+        &true,
+        &None
     );
     assert_eq!(result, Err(Ok(TokenError::InvalidMetadata)));
 }
+
+// ============================================================
+// Conformidad SEP-41: el contrato usado vía soroban_sdk::token::TokenClient
+// ============================================================
+
+/// Helper: registra e inicializa el token con 1000 tokens para `holder`
+///
+/// Devuelve el address del contrato para crear un `TokenClient`
+fn setup_sep41(env: &Env, holder: &Address) -> Address {
+    let contract_id = env.register(WorxToken, ());
+    let client = WorxTokenClient::new(env, &contract_id);
+    
+    client.initialize(
+        &Address::generate(env),
+        &String::from_str(env, "WORX Token"),
+        &String::from_str(env, "WORX"),
+        &7,
+        &true,
+        &None
+    );
+    
+    env.mock_all_auths();
+    client.mint(holder, &1000);
+    
+    contract_id
+}
+
+/// Test: metadatos y balance leídos con el cliente estándar
+#[test]
+fn test_sep41_metadata_and_balance() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let token = TokenClient::new(&env, &setup_sep41(&env, &alice));
+    
+    assert_eq!(token.name(), String::from_str(&env, "WORX Token"));
+    assert_eq!(token.symbol(), String::from_str(&env, "WORX"));
+    assert_eq!(token.decimals(), 7);
+    assert_eq!(token.balance(&alice), 1000);
+}
+
+/// Test: transfer con el cliente estándar, incluyendo destino muxed
+///
+/// Los tokens enviados a una dirección muxed se acreditan a la cuenta base
+#[test]
+fn test_sep41_transfer() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token = TokenClient::new(&env, &setup_sep41(&env, &alice));
+    
+    token.transfer(&alice, &bob, &250);
+    assert_eq!(token.balance(&alice), 750);
+    assert_eq!(token.balance(&bob), 250);
+    
+    let muxed = MuxedAddress::generate(&env);
+    token.transfer(&alice, &muxed, &50);
+    assert_eq!(token.balance(&muxed.address()), 50);
+    assert_eq!(token.balance(&alice), 700);
+}
+
+/// Test: approve + transfer_from con el cliente estándar
+#[test]
+fn test_sep41_approve_and_transfer_from() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    let token = TokenClient::new(&env, &setup_sep41(&env, &alice));
+    
+    let expiration = env.ledger().sequence() + 100;
+    token.approve(&alice, &bob, &300, &expiration);
+    assert_eq!(token.allowance(&alice, &bob), 300);
+    
+    token.transfer_from(&bob, &alice, &charlie, &200);
+    assert_eq!(token.balance(&alice), 800);
+    assert_eq!(token.balance(&charlie), 200);
+    assert_eq!(token.allowance(&alice, &bob), 100);
+}
+
+/// Test: el allowance deja de valer después de `expiration_ledger`
+///
+/// En el ledger de expiración todavía se puede gastar,
+/// en el siguiente el allowance vale 0
+#[test]
+fn test_sep41_allowance_expires() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    let token = TokenClient::new(&env, &setup_sep41(&env, &alice));
+    
+    let expiration = env.ledger().sequence() + 10;
+    token.approve(&alice, &bob, &300, &expiration);
+    
+    env.ledger().set_sequence_number(expiration);
+    token.transfer_from(&bob, &alice, &charlie, &100);
+    assert_eq!(token.allowance(&alice, &bob), 200);
+    
+    env.ledger().set_sequence_number(expiration + 1);
+    assert_eq!(token.allowance(&alice, &bob), 0);
+    
+    let result = token.try_transfer_from(&bob, &alice, &charlie, &100);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance.into())));
+}
+
+/// Test: approve con un ledger de expiración ya pasado debe fallar
+///
+/// Revocar (amount = 0) sí se permite con cualquier expiración
+#[test]
+fn test_sep41_approve_expired_ledger_fails() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token = TokenClient::new(&env, &setup_sep41(&env, &alice));
+    
+    env.ledger().set_sequence_number(100);
+    
+    let result = token.try_approve(&alice, &bob, &300, &99);
+    assert_eq!(result, Err(Ok(TokenError::InvalidExpiration.into())));
+    
+    token.approve(&alice, &bob, &300, &200);
+    token.approve(&alice, &bob, &0, &0);
+    assert_eq!(token.allowance(&alice, &bob), 0);
+}
+
+/// Test: burn y burn_from con el cliente estándar
+///
+/// burn_from consume allowance y reduce el supply total
+#[test]
+fn test_sep41_burn_and_burn_from() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let contract_id = setup_sep41(&env, &alice);
+    let token = TokenClient::new(&env, &contract_id);
+    let client = WorxTokenClient::new(&env, &contract_id);
+    
+    token.burn(&alice, &100);
+    assert_eq!(token.balance(&alice), 900);
+    
+    token.approve(&alice, &bob, &300, &(env.ledger().sequence() + 100));
+    token.burn_from(&bob, &alice, &200);
+    
+    assert_eq!(token.balance(&alice), 700);
+    assert_eq!(token.allowance(&alice, &bob), 100);
+    assert_eq!(client.total_supply(), 700);
+}
+
+/// Test: burn_from sin allowance suficiente debe fallar
+#[test]
+fn test_sep41_burn_from_insufficient_allowance() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token = TokenClient::new(&env, &setup_sep41(&env, &alice));
+    
+    token.approve(&alice, &bob, &50, &(env.ledger().sequence() + 100));
+    
+    let result = token.try_burn_from(&bob, &alice, &100);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance.into())));
+    assert_eq!(token.balance(&alice), 1000);
+}
+
+// ============================================================
+// Gestión del admin: transferencia en dos pasos y renuncia
+// ============================================================
+
+/// Helper: registra e inicializa el token, devuelve (client, admin)
+fn setup_admin(env: &Env) -> (WorxTokenClient<'_>, Address) {
+    let contract_id = env.register(WorxToken, ());
+    let client = WorxTokenClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(env, "WORX Token"),
+        &String::from_str(env, "WORX"),
+        &7,
+        &true,
+        &None
+    );
+    env.mock_all_auths();
+    
+    (client, admin)
+}
+
+/// Test: flujo completo propose_admin + accept_admin
+/// 
+/// Cada paso requiere la firma de una de las dos partes
+#[test]
+fn test_propose_and_accept_admin() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let new_admin = Address::generate(&env);
+    
+    client.propose_admin(&new_admin);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.admin(), Some(admin.clone()));  // Aún no cambia
+    
+    client.accept_admin();
+    assert_eq!(env.auths()[0].0, new_admin);
+    assert_eq!(client.admin(), Some(new_admin.clone()));
+    assert_eq!(client.pending_admin(), None);
+    
+    // El nuevo admin puede mintear
+    let user = Address::generate(&env);
+    client.mint(&user, &100);
+    assert_eq!(env.auths()[0].0, new_admin);
+}
+
+/// Test: accept_admin sin propuesta previa debe fallar
+#[test]
+fn test_accept_admin_without_proposal_fails() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    
+    let result = client.try_accept_admin();
+    assert_eq!(result, Err(Ok(TokenError::NoPendingAdmin)));
+}
+
+/// Test: renounce_admin deshabilita el mint para siempre
+/// 
+/// admin() informa el estado en lugar de hacer panic
+#[test]
+fn test_renounce_admin_disables_mint() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let user = Address::generate(&env);
+    let candidate = Address::generate(&env);
+    
+    client.mint(&user, &500);
+    client.propose_admin(&candidate);
+    
+    client.renounce_admin();
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.admin(), None);
+    assert_eq!(client.pending_admin(), None);
+    assert!(client.admin_renounced());
+    
+    // Nadie puede mintear ni recuperar el admin
+    assert_eq!(client.try_mint(&user, &1), Err(Ok(TokenError::AdminRenounced)));
+    assert_eq!(
+        client.try_propose_admin(&candidate),
+        Err(Ok(TokenError::AdminRenounced))
+    );
+    assert_eq!(client.try_accept_admin(), Err(Ok(TokenError::NoPendingAdmin)));
+    assert_eq!(client.total_supply(), 500);
+    
+    // Las operaciones de los holders siguen funcionando
+    client.transfer(&user, &candidate, &200);
+    assert_eq!(client.balance(&candidate), 200);
+}
+
+/// Test: admin() devuelve None antes de initialize (sin panic)
+#[test]
+fn test_admin_not_initialized() {
+    let env = Env::default();
+    let contract_id = env.register(WorxToken, ());
+    let client = WorxTokenClient::new(&env, &contract_id);
+    
+    assert_eq!(client.admin(), None);
+    assert!(!client.admin_renounced());
+}
+
+// ============================================================
+// Pausa (circuit breaker) y modo redeem only
+// ============================================================
+
+/// Test: la pausa global bloquea transfer, transfer_from, mint y burn
+#[test]
+fn test_pause_all_blocks_operations() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    client.approve(&alice, &bob, &500, &(env.ledger().sequence() + 100));
+    
+    client.pause(&PauseTarget::All);
+    assert!(client.is_paused(&PauseTarget::All));
+    assert!(client.is_paused(&PauseTarget::Transfer));
+    
+    assert_eq!(client.try_transfer(&alice, &bob, &10), Err(Ok(TokenError::Paused)));
+    assert_eq!(
+        client.try_transfer_from(&bob, &alice, &bob, &10),
+        Err(Ok(TokenError::Paused))
+    );
+    assert_eq!(client.try_mint(&alice, &10), Err(Ok(TokenError::Paused)));
+    assert_eq!(client.try_burn(&alice, &10), Err(Ok(TokenError::Paused)));
+    assert_eq!(client.try_burn_from(&bob, &alice, &10), Err(Ok(TokenError::Paused)));
+    
+    // approve no se pausa
+    client.approve(&alice, &bob, &0, &0);
+    
+    client.unpause(&PauseTarget::All);
+    assert!(!client.is_paused(&PauseTarget::Transfer));
+    client.transfer(&alice, &bob, &10);
+    assert_eq!(client.balance(&bob), 10);
+}
+
+/// Test: pausar una sola clase de operación
+#[test]
+fn test_pause_single_operation() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    client.pause(&PauseTarget::Mint);
+    
+    assert!(client.is_paused(&PauseTarget::Mint));
+    assert!(!client.is_paused(&PauseTarget::All));
+    assert_eq!(client.try_mint(&alice, &10), Err(Ok(TokenError::Paused)));
+    
+    // El resto sigue operando
+    client.transfer(&alice, &bob, &100);
+    client.burn(&alice, &100);
+    assert_eq!(client.balance(&alice), 800);
+}
+
+/// Test: modo redeem only, el burn sigue funcionando
+#[test]
+fn test_redeem_only_mode() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    client.set_redeem_only();
+    
+    assert!(client.is_paused(&PauseTarget::Transfer));
+    assert!(client.is_paused(&PauseTarget::Mint));
+    assert!(!client.is_paused(&PauseTarget::Burn));
+    
+    assert_eq!(client.try_transfer(&alice, &bob, &10), Err(Ok(TokenError::Paused)));
+    assert_eq!(client.try_mint(&alice, &10), Err(Ok(TokenError::Paused)));
+    
+    client.burn(&alice, &400);
+    assert_eq!(client.balance(&alice), 600);
+    assert_eq!(client.total_supply(), 600);
+}
+
+/// Test: pausar requiere la firma del admin
+#[test]
+fn test_pause_requires_admin() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    
+    client.pause(&PauseTarget::All);
+    assert_eq!(env.auths()[0].0, admin);
+    assert!(client.is_paused(&PauseTarget::All));
+    
+    client.renounce_admin();
+    assert_eq!(
+        client.try_unpause(&PauseTarget::All),
+        Err(Ok(TokenError::AdminRenounced))
+    );
+}
+
+/// Test: pausar y reanudar emite eventos
+#[test]
+fn test_pause_emits_events() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    
+    client.pause(&PauseTarget::Transfer);
+    let event = PausedEvent {
+        target: PauseTarget::Transfer,
+        caller: admin.clone(),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    
+    client.unpause(&PauseTarget::Transfer);
+    let event = UnpausedEvent {
+        target: PauseTarget::Transfer,
+        caller: admin,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+}
+
+// ============================================================
+// Congelamiento de cuentas (set_authorized / authorized)
+// ============================================================
+
+/// Test: una cuenta congelada no puede enviar ni recibir
+#[test]
+fn test_frozen_account_cannot_send_or_receive() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    client.mint(&bob, &1000);
+    assert!(client.authorized(&alice));
+    
+    client.set_authorized(&alice, &false);
+    assert_eq!(env.auths()[0].0, admin);
+    assert!(!client.authorized(&alice));
+    
+    // No puede enviar
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &10),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    assert_eq!(client.try_burn(&alice, &10), Err(Ok(TokenError::AccountFrozen)));
+    
+    // No puede recibir
+    assert_eq!(
+        client.try_transfer(&bob, &alice, &10),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    assert_eq!(
+        client.try_mint(&alice, &10),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    assert_eq!(client.balance(&alice), 1000);
+    
+    // Al descongelar vuelve a operar
+    client.set_authorized(&alice, &true);
+    client.transfer(&alice, &bob, &10);
+    assert_eq!(client.balance(&bob), 1010);
+}
+
+/// Test: el freeze aplica a transfer_from y burn_from
+/// 
+/// Un spender no puede mover ni quemar tokens de una cuenta congelada,
+/// ni enviarlos a una cuenta congelada
+#[test]
+fn test_frozen_account_blocks_allowance_operations() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    client.approve(&alice, &bob, &500, &(env.ledger().sequence() + 100));
+    
+    client.set_authorized(&charlie, &false);
+    assert_eq!(
+        client.try_transfer_from(&bob, &alice, &charlie, &10),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    
+    client.set_authorized(&alice, &false);
+    assert_eq!(
+        client.try_transfer_from(&bob, &alice, &bob, &10),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    assert_eq!(
+        client.try_burn_from(&bob, &alice, &10),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    assert_eq!(client.allowance(&alice, &bob), 500);
+}
+
+/// Test: set_authorized emite el evento para compliance
+#[test]
+fn test_set_authorized_emits_event() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    client.set_authorized(&alice, &false);
+    let event = SetAuthorizedEvent {
+        admin,
+        id: alice,
+        authorize: false,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+}
+
+// ============================================================
+// Clawback regulatorio
+// ============================================================
+
+/// Test: el admin recupera tokens sin la firma del holder
+/// 
+/// Reduce balance y supply; si el balance queda en 0 la key se elimina
+#[test]
+fn test_clawback() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    assert!(client.clawback_enabled());
+    
+    client.clawback(&alice, &300);
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.balance(&alice), 700);
+    assert_eq!(client.total_supply(), 700);
+    
+    let event = ClawbackEvent {
+        admin: admin.clone(),
+        from: alice.clone(),
+        amount: 700,
+        new_balance: 0,
+        new_supply: 0,
+    };
+    let std_event = Sep41ClawbackEvent { from: alice.clone(), amount: 700 };
+    client.clawback(&alice, &700);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (client.address.clone(), event.topics(&env), event.data(&env)),
+            (client.address.clone(), std_event.topics(&env), std_event.data(&env)),
+        ]
+    );
+    assert_eq!(client.balance(&alice), 0);
+    assert_eq!(client.total_supply(), 0);
+}
+
+/// Test: clawback funciona sobre cuentas congeladas y con pausa activa
+#[test]
+fn test_clawback_frozen_account() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    client.set_authorized(&alice, &false);
+    client.pause(&PauseTarget::All);
+    
+    client.clawback(&alice, &1000);
+    assert_eq!(client.balance(&alice), 0);
+}
+
+/// Test: clawback con amount inválido o mayor al balance falla
+#[test]
+fn test_clawback_invalid_amount() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    client.mint(&alice, &100);
+    
+    assert_eq!(client.try_clawback(&alice, &0), Err(Ok(TokenError::InvalidAmount)));
+    assert_eq!(
+        client.try_clawback(&alice, &101),
+        Err(Ok(TokenError::InsufficientBalance))
+    );
+}
+
+/// Test: un token inicializado sin clawback nunca lo permite
+#[test]
+fn test_clawback_disabled_at_initialize() {
+    let env = Env::default();
+    let contract_id = env.register(WorxToken, ());
+    let client = WorxTokenClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "WORX Token"),
+        &String::from_str(&env, "WORX"),
+        &7,
+        &false,
+        &None
+    );
+    env.mock_all_auths();
+    client.mint(&alice, &1000);
+    
+    assert!(!client.clawback_enabled());
+    assert_eq!(
+        client.try_clawback(&alice, &100),
+        Err(Ok(TokenError::ClawbackDisabled))
+    );
+    assert_eq!(client.balance(&alice), 1000);
+}
+
+// ============================================================================
+// Tope de supply
+// ============================================================================
+
+/// Helper: token con tope de supply configurado en initialize
+fn setup_capped(env: &Env, max_supply: i128) -> (WorxTokenClient<'_>, Address) {
+    let contract_id = env.register(WorxToken, ());
+    let client = WorxTokenClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(env, "WORX Token"),
+        &String::from_str(env, "WORX"),
+        &7,
+        &true,
+        &Some(max_supply)
+    );
+    env.mock_all_auths();
+    
+    (client, admin)
+}
+
+/// Test: sin tope configurado, max_supply() devuelve None
+#[test]
+fn test_max_supply_none_by_default() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    
+    assert_eq!(client.max_supply(), None);
+}
+
+/// Test: mint hasta el tope funciona, superarlo falla con SupplyCapExceeded
+#[test]
+fn test_mint_respects_supply_cap() {
+    let env = Env::default();
+    let (client, _admin) = setup_capped(&env, 1000);
+    let alice = Address::generate(&env);
+    
+    assert_eq!(client.max_supply(), Some(1000));
+    
+    client.mint(&alice, &600);
+    client.mint(&alice, &400);
+    assert_eq!(client.total_supply(), 1000);
+    
+    let result = client.try_mint(&alice, &1);
+    assert_eq!(result, Err(Ok(TokenError::SupplyCapExceeded)));
+    
+    // El intento fallido no modifica el estado
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.total_supply(), 1000);
+}
+
+/// Test: el burn libera espacio bajo el tope
+#[test]
+fn test_burn_frees_room_under_cap() {
+    let env = Env::default();
+    let (client, _admin) = setup_capped(&env, 1000);
+    let alice = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    client.burn(&alice, &300);
+    client.mint(&alice, &300);
+    
+    assert_eq!(client.total_supply(), 1000);
+}
+
+/// Test: initialize rechaza un tope <= 0
+#[test]
+fn test_initialize_invalid_supply_cap() {
+    let env = Env::default();
+    let contract_id = env.register(WorxToken, ());
+    let client = WorxTokenClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    
+    let result = client.try_initialize(
+        &admin,
+        &String::from_str(&env, "WORX Token"),
+        &String::from_str(&env, "WORX"),
+        &7,
+        &true,
+        &Some(0)
+    );
+    assert_eq!(result, Err(Ok(TokenError::InvalidSupplyCap)));
+}
+
+/// Test: el tope se puede bajar pero nunca subir
+#[test]
+fn test_lower_max_supply() {
+    let env = Env::default();
+    let (client, _admin) = setup_capped(&env, 1000);
+    let alice = Address::generate(&env);
+    
+    client.mint(&alice, &500);
+    
+    client.lower_max_supply(&800);
+    assert_eq!(client.max_supply(), Some(800));
+    
+    // Subir (o repetir) el tope falla
+    assert_eq!(
+        client.try_lower_max_supply(&900),
+        Err(Ok(TokenError::InvalidSupplyCap))
+    );
+    assert_eq!(
+        client.try_lower_max_supply(&800),
+        Err(Ok(TokenError::InvalidSupplyCap))
+    );
+    
+    // No puede quedar por debajo del supply en circulación
+    assert_eq!(
+        client.try_lower_max_supply(&499),
+        Err(Ok(TokenError::InvalidSupplyCap))
+    );
+    
+    // Igual al supply actual sí es válido: no se puede mintear más
+    client.lower_max_supply(&500);
+    assert_eq!(
+        client.try_mint(&alice, &1),
+        Err(Ok(TokenError::SupplyCapExceeded))
+    );
+}
+
+/// Test: un token sin tope puede fijar uno más tarde
+#[test]
+fn test_lower_max_supply_sets_first_cap() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    
+    client.lower_max_supply(&5000);
+    
+    let event = MaxSupplyUpdatedEvent {
+        admin,
+        old_max_supply: None,
+        new_max_supply: 5000,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(client.max_supply(), Some(5000));
+}
+
+/// Test: solo el admin puede bajar el tope
+#[test]
+#[should_panic]
+fn test_lower_max_supply_requires_admin() {
+    let env = Env::default();
+    let contract_id = env.register(WorxToken, ());
+    let client = WorxTokenClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "WORX Token"),
+        &String::from_str(&env, "WORX"),
+        &7,
+        &true,
+        &Some(1000)
+    );
+    
+    // Sin mock_all_auths: require_auth del admin falla
+    client.lower_max_supply(&500);
+}
+
+// ============================================================================
+// Upgrades
+// ============================================================================
+
+// Wasm de prueba para los tests de upgrade: v1 es un build del primer release
+// con upgrade() (esquema v1) y v3 es un build del código actual, así que los
+// tests recorren la migración real desde v1 hasta HEAD.
+// Regenerar v3 con: ../fixtures.sh build (test_fixtures_are_current de
+// token_bdb falla si quedó desactualizado)
+mod worx_token_v1 {
+    soroban_sdk::contractimport!(file = "testdata/worx_token_v1.wasm");
+}
+
+mod worx_token_v3 {
+    soroban_sdk::contractimport!(file = "testdata/worx_token_v3.wasm");
+}
+
+/// Helper: despliega el wasm v1 ya inicializado
+fn setup_v1(env: &Env) -> (worx_token_v1::Client<'_>, Address) {
+    let contract_id = env.register(worx_token_v1::WASM, ());
+    let client = worx_token_v1::Client::new(env, &contract_id);
+    let admin = Address::generate(env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(env, "WORX Token"),
+        &String::from_str(env, "WORX"),
+        &7,
+        &true,
+        &None
+    );
+    env.mock_all_auths();
+    
+    (client, admin)
+}
+
+/// Test: upgrade de v1 al código actual + migrate, de punta a punta
+/// 
+/// Balances, allowances y metadata sobreviven; el historial empieza en la
+/// migración y las listas de holders quedan deshabilitadas
+#[test]
+fn test_upgrade_preserves_balances_and_allowances() {
+    let env = Env::default();
+    let (v1, admin) = setup_v1(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    // Estado creado con el código v1
+    set_sequence(&env, 10);
+    v1.mint(&alice, &1000);
+    v1.transfer(&alice, &bob, &300);
+    v1.approve(&alice, &bob, &200, &1000);
+    assert_eq!(v1.version(), 1);
+    assert_eq!(v1.storage_version(), 1);
+    
+    // Subir el wasm actual y reemplazar el código
+    set_sequence(&env, 20);
+    let v3_hash = env.deployer().upload_contract_wasm(worx_token_v3::WASM);
+    v1.upgrade(&v3_hash);
+    
+    let v3 = worx_token_v3::Client::new(&env, &v1.address);
+    assert_eq!(v3.version(), CONTRACT_VERSION);
+    assert_eq!(v3.storage_version(), 1);
+    
+    // El storage no cambió con el upgrade
+    assert_eq!(v3.balance(&alice), 700);
+    assert_eq!(v3.balance(&bob), 300);
+    assert_eq!(v3.allowance(&alice, &bob), 200);
+    assert_eq!(v3.total_supply(), 1000);
+    assert_eq!(v3.admin(), Some(admin.clone()));
+    
+    // migrate aplica todos los pasos pendientes hasta el esquema actual
+    v3.migrate();
+    let event = MigratedEvent {
+        admin,
+        from_version: 1,
+        to_version: STORAGE_VERSION,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (v3.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(v3.storage_version(), STORAGE_VERSION);
+    assert_eq!(v3.symbol(), String::from_str(&env, "WORX"));
+    assert_eq!(v3.decimals(), 7);
+    
+    // El allowance migrado sigue siendo utilizable
+    set_sequence(&env, 30);
+    v3.transfer_from(&bob, &alice, &bob, &150);
+    assert_eq!(v3.balance(&bob), 450);
+    assert_eq!(v3.allowance(&alice, &bob), 50);
+    
+    // El historial arranca en la migración; las listas no se pueden reconstruir
+    set_sequence(&env, 40);
+    assert_eq!(
+        v3.try_balance_at(&alice, &15),
+        Err(Ok(worx_token_v3::TokenError::CheckpointUnavailable))
+    );
+    assert_eq!(v3.balance_at(&alice, &20), 700);
+    assert_eq!(v3.balance_at(&alice, &30), 550);
+    assert_eq!(v3.total_supply_at(&25), 1000);
+    assert_eq!(v3.try_holder_count(), Err(Ok(worx_token_v3::TokenError::ListsIncomplete)));
+}
+
+/// Test: migrate corre una sola vez por cambio de esquema
+#[test]
+fn test_migrate_runs_once() {
+    let env = Env::default();
+    let (v1, _admin) = setup_v1(&env);
+    
+    let v3_hash = env.deployer().upload_contract_wasm(worx_token_v3::WASM);
+    v1.upgrade(&v3_hash);
+    
+    let v3 = worx_token_v3::Client::new(&env, &v1.address);
+    v3.migrate();
+    assert_eq!(
+        v3.try_migrate(),
+        Err(Ok(worx_token_v3::TokenError::MigrationNotNeeded))
+    );
+}
+
+/// Test: un contrato recién inicializado ya está en el esquema actual
+#[test]
+fn test_migrate_not_needed_when_up_to_date() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    
+    assert_eq!(client.version(), CONTRACT_VERSION);
+    assert_eq!(client.storage_version(), STORAGE_VERSION);
+    assert_eq!(client.try_migrate(), Err(Ok(TokenError::MigrationNotNeeded)));
+}
+
+/// Test: solo el admin puede hacer upgrade
+#[test]
+#[should_panic]
+fn test_upgrade_requires_admin() {
+    let env = Env::default();
+    let contract_id = env.register(worx_token_v1::WASM, ());
+    let client = worx_token_v1::Client::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "WORX Token"),
+        &String::from_str(&env, "WORX"),
+        &7,
+        &true,
+        &None
+    );
+    let v3_hash = env.deployer().upload_contract_wasm(worx_token_v3::WASM);
+    
+    // Sin mock_all_auths: require_auth del admin falla
+    client.upgrade(&v3_hash);
+}
+
+/// Test: con el admin renunciado el código queda inmutable
+#[test]
+fn test_upgrade_after_renounce_fails() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    
+    client.renounce_admin();
+    assert_eq!(
+        client.try_upgrade(&BytesN::from_array(&env, &[0; 32])),
+        Err(Ok(TokenError::AdminRenounced))
+    );
+}
+
+// ============================================================================
+// Operaciones en lote
+// ============================================================================
+
+/// Helper: lote de `n` destinatarios nuevos, cada uno con `amount`
+fn batch_recipients(env: &Env, n: u32, amount: i128) -> soroban_sdk::Vec<(Address, i128)> {
+    let mut recipients = soroban_sdk::Vec::new(env);
+    for _ in 0..n {
+        recipients.push_back((Address::generate(env), amount));
+    }
+    recipients
+}
+
+/// Test: transfer_batch reparte a todos con una sola firma y un evento por destinatario
+#[test]
+fn test_transfer_batch() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    client.transfer_batch(&alice, &vec![&env, (bob.clone(), 300), (charlie.clone(), 200)]);
+    
+    // Una sola autorización: la de alice
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, alice);
+    
+    let to_bob = TransferEvent {
+        from: alice.clone(),
+        to: bob.clone(),
+        amount: 300,
+        fee: 0,
+        net_amount: 300,
+        from_new_balance: 700,
+        to_new_balance: 300,
+        memo: None,
+    };
+    let to_charlie = TransferEvent {
+        from: alice.clone(),
+        to: charlie.clone(),
+        amount: 200,
+        fee: 0,
+        net_amount: 200,
+        from_new_balance: 500,
+        to_new_balance: 200,
+        memo: None,
+    };
+    let std_to_bob = Sep41TransferEvent { from: alice.clone(), to: bob.clone(), amount: 300 };
+    let std_to_charlie = Sep41TransferEvent { from: alice.clone(), to: charlie.clone(), amount: 200 };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (client.address.clone(), to_bob.topics(&env), to_bob.data(&env)),
+            (client.address.clone(), std_to_bob.topics(&env), std_to_bob.data(&env)),
+            (client.address.clone(), to_charlie.topics(&env), to_charlie.data(&env)),
+            (client.address.clone(), std_to_charlie.topics(&env), std_to_charlie.data(&env)),
+        ]
+    );
+    
+    assert_eq!(
+        client.balances(&vec![&env, alice.clone(), bob.clone(), charlie.clone()]),
+        vec![&env, 500, 300, 200]
+    );
+    assert_eq!(client.total_supply(), 1000);
+}
+
+/// Test: transfer_batch es todo-o-nada
+#[test]
+fn test_transfer_batch_all_or_nothing() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    
+    // El total (1100) supera el balance aunque cada monto por separado no
+    assert_eq!(
+        client.try_transfer_batch(&alice, &vec![&env, (bob.clone(), 600), (charlie.clone(), 500)]),
+        Err(Ok(TokenError::InsufficientBalance))
+    );
+    
+    // Un destinatario inválido al final revierte todo el lote
+    assert_eq!(
+        client.try_transfer_batch(&alice, &vec![&env, (bob.clone(), 100), (charlie.clone(), 0)]),
+        Err(Ok(TokenError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_transfer_batch(&alice, &vec![&env, (bob.clone(), 100), (alice.clone(), 100)]),
+        Err(Ok(TokenError::InvalidRecipient))
+    );
+    client.set_authorized(&charlie, &false);
+    assert_eq!(
+        client.try_transfer_batch(&alice, &vec![&env, (bob.clone(), 100), (charlie.clone(), 100)]),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+    
+    assert_eq!(
+        client.balances(&vec![&env, alice.clone(), bob.clone(), charlie.clone()]),
+        vec![&env, 1000, 0, 0]
+    );
+}
+
+/// Test: un mismo destinatario repetido acumula los montos
+#[test]
+fn test_transfer_batch_repeated_recipient() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    client.transfer_batch(&alice, &vec![&env, (bob.clone(), 400), (bob.clone(), 600)]);
+    
+    assert_eq!(client.balance(&bob), 1000);
+    assert_eq!(client.balance(&alice), 0);
+}
+
+/// Test: lote vacío o con más de MAX_BATCH_SIZE destinatarios
+#[test]
+fn test_batch_size_limits() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    
+    assert_eq!(
+        client.try_transfer_batch(&alice, &soroban_sdk::Vec::new(&env)),
+        Err(Ok(TokenError::InvalidBatchSize))
+    );
+    assert_eq!(
+        client.try_transfer_batch(&alice, &batch_recipients(&env, MAX_BATCH_SIZE + 1, 1)),
+        Err(Ok(TokenError::InvalidBatchSize))
+    );
+    assert_eq!(
+        client.try_mint_batch(&batch_recipients(&env, MAX_BATCH_SIZE + 1, 1)),
+        Err(Ok(TokenError::InvalidBatchSize))
+    );
+}
+
+/// Test: mint_batch acredita a todos y actualiza el supply una vez
+#[test]
+fn test_mint_batch() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint_batch(&vec![&env, (alice.clone(), 100), (bob.clone(), 250)]);
+    
+    let to_alice = MintEvent {
+        to: alice.clone(),
+        amount: 100,
+        new_balance: 100,
+        new_supply: 100,
+        memo: None,
+    };
+    let to_bob = MintEvent {
+        to: bob.clone(),
+        amount: 250,
+        new_balance: 250,
+        new_supply: 350,
+        memo: None,
+    };
+    let std_to_alice = Sep41MintEvent { to: alice.clone(), amount: 100 };
+    let std_to_bob = Sep41MintEvent { to: bob.clone(), amount: 250 };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (client.address.clone(), to_alice.topics(&env), to_alice.data(&env)),
+            (client.address.clone(), std_to_alice.topics(&env), std_to_alice.data(&env)),
+            (client.address.clone(), to_bob.topics(&env), to_bob.data(&env)),
+            (client.address.clone(), std_to_bob.topics(&env), std_to_bob.data(&env)),
+        ]
+    );
+    
+    assert_eq!(client.balances(&vec![&env, alice, bob]), vec![&env, 100, 250]);
+    assert_eq!(client.total_supply(), 350);
+}
+
+/// Test: mint_batch verifica el tope de supply contra el total del lote
+#[test]
+fn test_mint_batch_respects_supply_cap() {
+    let env = Env::default();
+    let (client, _admin) = setup_capped(&env, 1000);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    assert_eq!(
+        client.try_mint_batch(&vec![&env, (alice.clone(), 600), (bob.clone(), 401)]),
+        Err(Ok(TokenError::SupplyCapExceeded))
+    );
+    assert_eq!(client.total_supply(), 0);
+    
+    client.mint_batch(&vec![&env, (alice, 600), (bob, 400)]);
+    assert_eq!(client.total_supply(), 1000);
+}
+
+/// Test: un lote de MAX_BATCH_SIZE destinatarios entra en el presupuesto por defecto
+/// 
+/// El Env de test aplica los límites de CPU y memoria por defecto de Soroban;
+/// si el lote no entrara, la invocación fallaría. Los costos medidos con el
+/// contrato nativo subestiman los del wasm, por eso se exige un margen amplio.
+#[test]
+fn test_batch_fits_default_budget() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    let recipients = batch_recipients(&env, MAX_BATCH_SIZE, 10);
+    client.mint(&alice, &(10 * MAX_BATCH_SIZE as i128));
+    
+    client.transfer_batch(&alice, &recipients);
+    let resources = env.cost_estimate().resources();
+    let cpu = env.cost_estimate().budget().cpu_instruction_cost();
+    // Balance, checkpoints y lugar en la lista de holders (dos entries) por
+    // destinatario nuevo; balance y checkpoints del sender, que queda en 0 y
+    // cede su lugar al último holder (dos entries más); la instance (cantidad
+    // de holders) y el nonce de su firma
+    assert_eq!(resources.write_entries, 4 * MAX_BATCH_SIZE + 6);
+    assert!(cpu < 100_000_000 / 4, "transfer_batch: {cpu} instrucciones");
+    
+    client.mint_batch(&recipients);
+    let resources = env.cost_estimate().resources();
+    let cpu = env.cost_estimate().budget().cpu_instruction_cost();
+    // Balance y checkpoints por destinatario (ya son holders), más la instance
+    // (total supply), los checkpoints del supply y el nonce
+    assert_eq!(resources.write_entries, 2 * MAX_BATCH_SIZE + 3);
+    assert!(cpu < 100_000_000 / 4, "mint_batch: {cpu} instrucciones");
+}
+
+/// Test: balances() devuelve 0 para cuentas sin tokens
+#[test]
+fn test_balances_query() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.mint(&alice, &42);
+    
+    assert_eq!(client.balances(&vec![&env, bob, alice]), vec![&env, 0, 42]);
+    assert_eq!(client.balances(&soroban_sdk::Vec::new(&env)), soroban_sdk::Vec::new(&env));
+}
+
+// ============================================================
+// Checkpoints: balances y supply históricos
+// ============================================================
+
+/// Helper: fija el número de secuencia del ledger
+fn set_sequence(env: &Env, sequence: u32) {
+    env.ledger().with_mut(|li| {
+        li.sequence_number = sequence;
+    });
+}
+
+/// Test: balance_at y total_supply_at reconstruyen el historial
+#[test]
+fn test_balance_at_and_total_supply_at() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    
+    set_sequence(&env, 10);
+    client.mint(&alice, &100);
+    set_sequence(&env, 20);
+    client.transfer(&alice, &bob, &30);
+    set_sequence(&env, 30);
+    client.burn(&alice, &20);
+    set_sequence(&env, 40);
+    client.approve(&bob, &carol, &10, &1000);
+    client.transfer_from(&carol, &bob, &carol, &10);
+    set_sequence(&env, 50);
+    
+    assert_eq!(client.balance_at(&alice, &9), 0);
+    assert_eq!(client.balance_at(&alice, &10), 100);
+    assert_eq!(client.balance_at(&alice, &19), 100);
+    assert_eq!(client.balance_at(&alice, &20), 70);
+    assert_eq!(client.balance_at(&alice, &30), 50);
+    assert_eq!(client.balance_at(&bob, &19), 0);
+    assert_eq!(client.balance_at(&bob, &39), 30);
+    assert_eq!(client.balance_at(&bob, &40), 20);
+    assert_eq!(client.balance_at(&carol, &40), 10);
+    
+    assert_eq!(client.total_supply_at(&9), 0);
+    assert_eq!(client.total_supply_at(&29), 100);
+    assert_eq!(client.total_supply_at(&49), 80);
+    
+    // Una cuenta sin movimientos vale 0 en cualquier ledger
+    assert_eq!(client.balance_at(&Address::generate(&env), &25), 0);
+}
+
+/// Test: solo se pueden consultar ledgers ya cerrados
+#[test]
+fn test_balance_at_requires_past_ledger() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    set_sequence(&env, 10);
+    client.mint(&alice, &100);
+    
+    assert_eq!(client.try_balance_at(&alice, &10), Err(Ok(TokenError::LedgerNotFinalized)));
+    assert_eq!(client.try_total_supply_at(&11), Err(Ok(TokenError::LedgerNotFinalized)));
+    assert_eq!(client.balance_at(&alice, &9), 0);
+}
+
+/// Test: varios cambios en un mismo ledger dejan un solo checkpoint
+#[test]
+fn test_checkpoints_same_ledger() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    set_sequence(&env, 10);
+    client.mint(&alice, &100);
+    client.transfer(&alice, &bob, &10);
+    client.transfer_batch(&alice, &vec![&env, (bob.clone(), 5)]);
+    set_sequence(&env, 11);
+    
+    assert_eq!(client.balance_at(&alice, &10), 85);
+    assert_eq!(client.balance_at(&bob, &10), 15);
+    
+    let history = env.as_contract(&client.address, || {
+        env.storage().persistent()
+            .get::<_, storage::History>(&DataKey::BalanceHistory(alice.clone()))
+            .unwrap()
+    });
+    assert_eq!(history.recent, vec![&env, storage::Checkpoint { ledger: 10, value: 85 }]);
+}
+
+/// Test: el historial no se poda; las páginas llenas se archivan
+#[test]
+fn test_checkpoints_paged() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    // Un mint por ledger: tres páginas archivadas y 4 checkpoints en curso
+    let changes = 3 * storage::CHECKPOINTS_PER_PAGE + 4;
+    for sequence in 1..=changes {
+        set_sequence(&env, sequence);
+        client.mint(&alice, &1);
+    }
+    set_sequence(&env, changes + 1);
+    
+    let history = env.as_contract(&client.address, || {
+        env.storage().persistent()
+            .get::<_, storage::History>(&DataKey::BalanceHistory(alice.clone()))
+            .unwrap()
+    });
+    assert_eq!(history.pages, 3);
+    assert_eq!(history.recent.len(), 4);
+    
+    // Todos los ledgers siguen cubiertos, en cualquier página
+    assert_eq!(client.balance_at(&alice, &0), 0);
+    for ledger in [1, 5, 32, 33, 64, 65, 96, 97, changes] {
+        assert_eq!(client.balance_at(&alice, &ledger), ledger as i128);
+        assert_eq!(client.total_supply_at(&ledger), ledger as i128);
+    }
+}
+
+// ============================================================
+// Fee de transferencia
+// ============================================================
+
+/// Helper: token con 10_000 tokens para alice y fee de 250 bps (2.5%)
+fn setup_fee<'a>(env: &'a Env, destination: &FeeDestination) -> (WorxTokenClient<'a>, Address) {
+    let (client, _admin) = setup_admin(env);
+    let alice = Address::generate(env);
+    
+    client.mint(&alice, &10_000);
+    client.set_transfer_fee(&250, destination);
+    
+    (client, alice)
+}
+
+/// Test: el fee va a la tesorería y el evento reporta bruto, fee y neto
+#[test]
+fn test_transfer_fee_to_treasury() {
+    let env = Env::default();
+    let treasury = Address::generate(&env);
+    let (client, alice) = setup_fee(&env, &FeeDestination::Treasury(treasury.clone()));
+    let bob = Address::generate(&env);
+    
+    client.transfer(&alice, &bob, &1_000);
+    
+    let fee_event = FeeCollectedEvent {
+        from: alice.clone(),
+        fee: 25,
+        treasury: Some(treasury.clone()),
+        new_supply: 10_000,
+    };
+    let transfer_event = TransferEvent {
+        from: alice.clone(),
+        to: bob.clone(),
+        amount: 1_000,
+        fee: 25,
+        net_amount: 975,
+        from_new_balance: 9_000,
+        to_new_balance: 975,
+        memo: None,
+    };
+    // El estándar refleja los dos movimientos: el fee a la tesorería y el neto a bob
+    let std_fee = Sep41TransferEvent { from: alice.clone(), to: treasury.clone(), amount: 25 };
+    let std_transfer = Sep41TransferEvent { from: alice.clone(), to: bob.clone(), amount: 975 };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (client.address.clone(), fee_event.topics(&env), fee_event.data(&env)),
+            (client.address.clone(), std_fee.topics(&env), std_fee.data(&env)),
+            (client.address.clone(), transfer_event.topics(&env), transfer_event.data(&env)),
+            (client.address.clone(), std_transfer.topics(&env), std_transfer.data(&env)),
+        ]
+    );
+    assert_eq!(client.balance(&bob), 975);
+    assert_eq!(client.balance(&treasury), 25);
+    assert_eq!(client.total_supply(), 10_000);
+}
+
+/// Test: con destino Burn el fee reduce el total supply
+#[test]
+fn test_transfer_fee_burned() {
+    let env = Env::default();
+    let (client, alice) = setup_fee(&env, &FeeDestination::Burn);
+    let bob = Address::generate(&env);
+    let spender = Address::generate(&env);
+    
+    client.transfer(&alice, &bob, &1_000);
+    assert_eq!(client.balance(&bob), 975);
+    assert_eq!(client.total_supply(), 9_975);
+    
+    // transfer_from también cobra fee y consume el allowance bruto
+    client.approve(&alice, &spender, &400, &1000);
+    client.transfer_from(&spender, &alice, &bob, &400);
+    assert_eq!(client.balance(&alice), 8_600);
+    assert_eq!(client.balance(&bob), 1_365);
+    assert_eq!(client.allowance(&alice, &spender), 0);
+    assert_eq!(client.total_supply(), 9_965);
+}
+
+/// Test: cuentas exentas y la tesorería no pagan fee
+#[test]
+fn test_transfer_fee_exemptions() {
+    let env = Env::default();
+    let treasury = Address::generate(&env);
+    let (client, alice) = setup_fee(&env, &FeeDestination::Treasury(treasury.clone()));
+    let exchange = Address::generate(&env);
+    
+    client.set_fee_exempt(&exchange, &true);
+    assert!(client.is_fee_exempt(&exchange));
+    
+    // Hacia y desde una cuenta exenta: sin fee
+    client.transfer(&alice, &exchange, &1_000);
+    assert_eq!(client.balance(&exchange), 1_000);
+    client.transfer(&exchange, &alice, &500);
+    assert_eq!(client.balance(&alice), 9_500);
+    
+    // La tesorería es exenta implícitamente
+    client.transfer(&alice, &treasury, &100);
+    assert_eq!(client.balance(&treasury), 100);
+    
+    // Al quitar la exención vuelve a cobrarse
+    client.set_fee_exempt(&exchange, &false);
+    client.transfer(&exchange, &alice, &400);
+    assert_eq!(client.balance(&treasury), 110);
+}
+
+/// Test: el fee se redondea hacia arriba y partir la transferencia no lo evita
+#[test]
+fn test_transfer_fee_rounds_up() {
+    let env = Env::default();
+    let treasury = Address::generate(&env);
+    let (client, alice) = setup_fee(&env, &FeeDestination::Treasury(treasury.clone()));
+    let bob = Address::generate(&env);
+    
+    // 250 bps de 39 son 0.975: se cobra 1
+    client.transfer(&alice, &bob, &39);
+    assert_eq!(client.balance(&bob), 38);
+    assert_eq!(client.balance(&treasury), 1);
+    
+    // Diez transferencias de 2 stroops pagan 1 cada una
+    for _ in 0..10 {
+        client.transfer(&alice, &bob, &2);
+    }
+    assert_eq!(client.balance(&bob), 48);
+    assert_eq!(client.balance(&treasury), 11);
+    assert_eq!(client.balance(&alice), 9_941);
+}
+
+/// Test: una transferencia cuyo fee se llevaría todo el monto falla
+#[test]
+fn test_transfer_fee_rejects_dust() {
+    let env = Env::default();
+    let treasury = Address::generate(&env);
+    let (client, alice) = setup_fee(&env, &FeeDestination::Treasury(treasury.clone()));
+    let bob = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.set_transfer_fee(&MAX_FEE_BPS, &FeeDestination::Treasury(treasury.clone()));
+    client.approve(&alice, &spender, &100, &1000);
+    
+    // 1000 bps de 1 stroop se redondean a 1: el neto sería 0
+    assert_eq!(client.try_transfer(&alice, &bob, &1), Err(Ok(TokenError::AmountTooSmall)));
+    assert_eq!(
+        client.try_transfer_from(&spender, &alice, &bob, &1),
+        Err(Ok(TokenError::AmountTooSmall))
+    );
+    assert_eq!(
+        client.try_transfer_batch(&alice, &vec![&env, (bob.clone(), 1)]),
+        Err(Ok(TokenError::AmountTooSmall))
+    );
+    assert_eq!(
+        client.try_quote_transfer(&alice, &bob, &1),
+        Err(Ok(TokenError::AmountTooSmall))
+    );
+    assert_eq!(client.balance(&alice), 10_000);
+    assert_eq!(client.balance(&treasury), 0);
+    
+    // Sin fee (cuenta exenta) el mismo monto pasa completo
+    client.set_fee_exempt(&bob, &true);
+    client.transfer(&alice, &bob, &1);
+    assert_eq!(client.balance(&bob), 1);
+    
+    // Con 2 stroops el destinatario recibe 1
+    let carol = Address::generate(&env);
+    client.transfer(&alice, &carol, &2);
+    assert_eq!(client.balance(&carol), 1);
+    assert_eq!(client.balance(&treasury), 1);
+}
+
+/// Test: quote_transfer aplica las exenciones de la cuenta y la tesorería
+#[test]
+fn test_quote_transfer_exemptions() {
+    let env = Env::default();
+    let treasury = Address::generate(&env);
+    let (client, alice) = setup_fee(&env, &FeeDestination::Treasury(treasury.clone()));
+    let bob = Address::generate(&env);
+    let exchange = Address::generate(&env);
+    client.set_fee_exempt(&exchange, &true);
+    
+    let full = TransferQuote { amount: 1_000, fee: 0, net_amount: 1_000 };
+    assert_eq!(
+        client.quote_transfer(&alice, &bob, &1_000),
+        TransferQuote { amount: 1_000, fee: 25, net_amount: 975 }
+    );
+    assert_eq!(client.quote_transfer(&alice, &exchange, &1_000), full);
+    assert_eq!(client.quote_transfer(&exchange, &bob, &1_000), full);
+    assert_eq!(client.quote_transfer(&alice, &treasury, &1_000), full);
+    assert_eq!(client.quote_transfer(&treasury, &bob, &1_000), full);
+    
+    // El quote coincide con lo que recibe el destinatario
+    client.transfer(&alice, &exchange, &1_000);
+    assert_eq!(client.balance(&exchange), 1_000);
+}
+
+/// Test: transfer_batch cobra fee por destinatario en una sola escritura
+#[test]
+fn test_transfer_batch_with_fee() {
+    let env = Env::default();
+    let treasury = Address::generate(&env);
+    let (client, alice) = setup_fee(&env, &FeeDestination::Treasury(treasury.clone()));
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.transfer_batch(&alice, &vec![&env, (bob.clone(), 1_000), (charlie.clone(), 2_000)]);
+    assert_eq!(client.balance(&alice), 7_000);
+    assert_eq!(client.balance(&bob), 975);
+    assert_eq!(client.balance(&charlie), 1_950);
+    assert_eq!(client.balance(&treasury), 75);
+}
+
+/// Test: tope del fee, permisos y quote_transfer
+#[test]
+fn test_transfer_fee_config() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    // Sin fee configurado el neto es el monto completo
+    assert_eq!(client.transfer_fee(), None);
+    assert_eq!(
+        client.quote_transfer(&alice, &bob, &1_000),
+        TransferQuote { amount: 1_000, fee: 0, net_amount: 1_000 }
+    );
+    
+    assert_eq!(
+        client.try_set_transfer_fee(&(MAX_FEE_BPS + 1), &FeeDestination::Burn),
+        Err(Ok(TokenError::InvalidFee))
+    );
+    client.set_transfer_fee(&MAX_FEE_BPS, &FeeDestination::Burn);
+    assert_eq!(
+        client.transfer_fee(),
+        Some(FeeConfig { fee_bps: MAX_FEE_BPS, destination: FeeDestination::Burn })
+    );
+    // El fee se redondea hacia arriba
+    assert_eq!(
+        client.quote_transfer(&alice, &bob, &1_005),
+        TransferQuote { amount: 1_005, fee: 101, net_amount: 904 }
+    );
+    assert_eq!(client.try_quote_transfer(&alice, &bob, &0), Err(Ok(TokenError::InvalidAmount)));
+}
+
+/// Test: configurar el fee y las exenciones requiere al admin
+#[test]
+fn test_transfer_fee_requires_admin() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    
+    client.set_transfer_fee(&100, &FeeDestination::Burn);
+    assert_eq!(env.auths()[0].0, admin);
+    
+    client.set_fee_exempt(&Address::generate(&env), &true);
+    assert_eq!(env.auths()[0].0, admin);
+}
+
+// ============================================================
+// Metadatos extendidos
+// ============================================================
+
+/// Helper: metadatos completos de ejemplo
+fn sample_metadata(env: &Env) -> TokenMetadata {
+    let mut extra = Map::new(env);
+    extra.set(String::from_str(env, "website"), String::from_str(env, "https://example.com"));
+    
+    TokenMetadata {
+        name: String::from_str(env, "WORX Token v2"),
+        symbol: String::from_str(env, "WORX"),
+        decimals: 7,
+        description: String::from_str(env, "Token de la comunidad"),
+        icon_url: String::from_str(env, "https://example.com/icon.png"),
+        home_domain: String::from_str(env, "example.com"),
+        extra,
+    }
+}
+
+/// Test: initialize guarda los metadatos con los campos nuevos vacíos
+#[test]
+fn test_metadata_after_initialize() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    
+    let metadata = client.metadata();
+    assert_eq!(metadata.name, String::from_str(&env, "WORX Token"));
+    assert_eq!(metadata.symbol, String::from_str(&env, "WORX"));
+    assert_eq!(metadata.decimals, 7);
+    assert!(metadata.description.is_empty());
+    assert!(metadata.extra.is_empty());
+}
+
+/// Test: update_metadata reemplaza los metadatos y emite evento
+#[test]
+fn test_update_metadata() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let metadata = sample_metadata(&env);
+    
+    client.update_metadata(&metadata);
+    assert_eq!(env.auths()[0].0, admin);
+    
+    let event = MetadataUpdatedEvent {
+        caller: admin.clone(),
+        metadata: metadata.clone(),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(client.metadata(), metadata);
+    assert_eq!(client.name(), String::from_str(&env, "WORX Token v2"));
+}
+
+/// Test: update_metadata vuelve a validar los límites
+#[test]
+fn test_update_metadata_validations() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let metadata = sample_metadata(&env);
+    
+    let long_name = String::from_str(&env, &"a".repeat(MAX_NAME_LENGTH as usize + 1));
+    let long_symbol = String::from_str(&env, &"S".repeat(MAX_SYMBOL_LENGTH as usize + 1));
+    let long_url = String::from_str(&env, &"u".repeat(MAX_URL_LENGTH as usize + 1));
+    let mut too_many = Map::new(&env);
+    for i in 0..=MAX_METADATA_ENTRIES {
+        too_many.set(String::from_str(&env, &std::format!("key{i}")), String::from_str(&env, "v"));
+    }
+    
+    let invalid = [
+        TokenMetadata { name: String::from_str(&env, ""), ..metadata.clone() },
+        TokenMetadata { name: long_name, ..metadata.clone() },
+        TokenMetadata { symbol: long_symbol, ..metadata.clone() },
+        TokenMetadata { icon_url: long_url.clone(), ..metadata.clone() },
+        TokenMetadata { home_domain: long_url, ..metadata.clone() },
+        TokenMetadata { extra: too_many, ..metadata.clone() },
+    ];
+    for bad in invalid {
+        assert_eq!(client.try_update_metadata(&bad), Err(Ok(TokenError::InvalidMetadata)));
+    }
+    
+    // Los decimales no se pueden cambiar
+    let other_decimals = TokenMetadata { decimals: 6, ..metadata };
+    assert_eq!(client.try_update_metadata(&other_decimals), Err(Ok(TokenError::InvalidDecimals)));
+}
+
+/// Test: migrate pasa los metadatos del esquema v1 a `Metadata`
+#[test]
+fn test_migrate_metadata_from_v1_layout() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    
+    // Simular el storage de un contrato inicializado con el esquema v1
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&DataKey::Metadata);
+        env.storage().instance().set(&DataKey::TokenName, &String::from_str(&env, "WORX Token"));
+        env.storage().instance().set(&DataKey::TokenSymbol, &String::from_str(&env, "WORX"));
+        env.storage().instance().set(&DataKey::Decimals, &7u32);
+        env.storage().instance().set(&DataKey::StorageVersion, &1u32);
+    });
+    
+    // Antes de migrar los getters siguen funcionando
+    assert_eq!(client.symbol(), String::from_str(&env, "WORX"));
+    
+    client.migrate();
+    assert_eq!(client.storage_version(), STORAGE_VERSION);
+    assert_eq!(client.decimals(), 7);
+    assert_eq!(client.metadata().name, String::from_str(&env, "WORX Token"));
+    env.as_contract(&client.address, || {
+        assert!(env.storage().instance().has(&DataKey::Metadata));
+        assert!(!env.storage().instance().has(&DataKey::TokenName));
+        assert!(!env.storage().instance().has(&DataKey::TokenSymbol));
+        assert!(!env.storage().instance().has(&DataKey::Decimals));
+    });
+}
+
+// ============================================================
+// Eventos: detallados + estándar SEP-41 en cada método que cambia estado
+// ============================================================
+
+/// Helper: entrada de `env.events().all()` para `event` publicado por `client`
+fn emitted(
+    env: &Env,
+    client: &WorxTokenClient,
+    event: &impl Event
+) -> (Address, soroban_sdk::Vec<soroban_sdk::Val>, soroban_sdk::Val) {
+    (client.address.clone(), event.topics(env), event.data(env))
+}
+
+/// Test: initialize emite InitEvent con el flag de clawback
+#[test]
+fn test_initialize_emits_event() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    
+    // setup_admin ya inicializó: la última invocación fue initialize
+    let event = InitEvent {
+        admin,
+        name: String::from_str(&env, "WORX Token"),
+        symbol: String::from_str(&env, "WORX"),
+        decimals: 7,
+        clawback_enabled: true,
+    };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+}
+
+/// Test: mint emite MintEvent y el `mint` estándar
+#[test]
+fn test_mint_emits_events() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    
+    let detailed = MintEvent {
+        to: alice.clone(),
+        amount: 1000,
+        new_balance: 1000,
+        new_supply: 1000,
+        memo: None,
+    };
+    let standard = Sep41MintEvent { to: alice, amount: 1000 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: burn emite BurnEvent y el `burn` estándar
+#[test]
+fn test_burn_emits_events() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    client.mint(&alice, &1000);
+    
+    client.burn(&alice, &300);
+    
+    let detailed = BurnEvent {
+        from: alice.clone(),
+        amount: 300,
+        new_balance: 700,
+        new_supply: 700,
+    };
+    let standard = Sep41BurnEvent { from: alice, amount: 300 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: burn_from emite BurnFromEvent y el `burn` estándar
+#[test]
+fn test_burn_from_emits_events() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.mint(&alice, &1000);
+    client.approve(&alice, &bob, &500, &1000);
+    
+    client.burn_from(&bob, &alice, &200);
+    
+    let detailed = BurnFromEvent {
+        spender: bob,
+        from: alice.clone(),
+        amount: 200,
+        new_balance: 800,
+        new_supply: 800,
+        new_allowance: 300,
+    };
+    let standard = Sep41BurnEvent { from: alice, amount: 200 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: transfer emite TransferEvent y el `transfer` estándar
+/// 
+/// Con destino muxed el estándar lleva `to_muxed_id` (CAP-67)
+#[test]
+fn test_transfer_emits_events() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.mint(&alice, &1000);
+    
+    client.transfer(&alice, &bob, &300);
+    
+    let detailed = TransferEvent {
+        from: alice.clone(),
+        to: bob.clone(),
+        amount: 300,
+        fee: 0,
+        net_amount: 300,
+        from_new_balance: 700,
+        to_new_balance: 300,
+        memo: None,
+    };
+    let standard = Sep41TransferEvent { from: alice.clone(), to: bob, amount: 300 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+    
+    let muxed = MuxedAddress::generate(&env);
+    client.transfer(&alice, &muxed, &100);
+    
+    let detailed = TransferEvent {
+        from: alice.clone(),
+        to: muxed.address(),
+        amount: 100,
+        fee: 0,
+        net_amount: 100,
+        from_new_balance: 600,
+        to_new_balance: 100,
+        memo: None,
+    };
+    let standard = Sep41TransferMuxedEvent {
+        from: alice,
+        to: muxed.address(),
+        amount: 100,
+        to_muxed_id: muxed.id().unwrap(),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: approve emite ApproveEvent y el `approve` estándar
+#[test]
+fn test_approve_emits_events() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.approve(&alice, &bob, &500, &1000);
+    
+    let detailed = ApproveEvent {
+        from: alice.clone(),
+        spender: bob.clone(),
+        old_allowance: 0,
+        new_allowance: 500,
+        expiration_ledger: 1000,
+    };
+    let standard = Sep41ApproveEvent {
+        from: alice,
+        spender: bob,
+        amount: 500,
+        expiration_ledger: 1000,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: transfer_from emite TransferFromEvent y el `transfer` estándar
+#[test]
+fn test_transfer_from_emits_events() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    client.mint(&alice, &1000);
+    client.approve(&alice, &bob, &500, &1000);
+    
+    client.transfer_from(&bob, &alice, &charlie, &200);
+    
+    let detailed = TransferFromEvent {
+        spender: bob,
+        from: alice.clone(),
+        to: charlie.clone(),
+        amount: 200,
+        fee: 0,
+        net_amount: 200,
+        from_new_balance: 800,
+        to_new_balance: 200,
+        new_allowance: 300,
+        memo: None,
+    };
+    let standard = Sep41TransferEvent { from: alice, to: charlie, amount: 200 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: con destino Burn el fee se reporta como `burn` estándar
+#[test]
+fn test_transfer_fee_burned_emits_events() {
+    let env = Env::default();
+    let (client, alice) = setup_fee(&env, &FeeDestination::Burn);
+    let bob = Address::generate(&env);
+    
+    client.transfer(&alice, &bob, &1_000);
+    
+    let fee_event = FeeCollectedEvent {
+        from: alice.clone(),
+        fee: 25,
+        treasury: None,
+        new_supply: 9_975,
+    };
+    let std_fee = Sep41BurnEvent { from: alice.clone(), amount: 25 };
+    let detailed = TransferEvent {
+        from: alice.clone(),
+        to: bob.clone(),
+        amount: 1_000,
+        fee: 25,
+        net_amount: 975,
+        from_new_balance: 9_000,
+        to_new_balance: 975,
+        memo: None,
+    };
+    let standard = Sep41TransferEvent { from: alice, to: bob, amount: 975 };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            emitted(&env, &client, &fee_event),
+            emitted(&env, &client, &std_fee),
+            emitted(&env, &client, &detailed),
+            emitted(&env, &client, &standard),
+        ]
+    );
+}
+
+/// Test: set_transfer_fee y set_fee_exempt emiten su evento
+#[test]
+fn test_fee_config_emits_events() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.set_transfer_fee(&100, &FeeDestination::Treasury(treasury.clone()));
+    let event = TransferFeeUpdatedEvent {
+        admin: admin.clone(),
+        fee_bps: 100,
+        destination: FeeDestination::Treasury(treasury),
+    };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+    
+    client.set_fee_exempt(&alice, &true);
+    let event = FeeExemptionEvent { admin, account: alice, exempt: true };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+}
+
+/// Test: propose_admin, accept_admin y renounce_admin emiten su evento
+#[test]
+fn test_admin_changes_emit_events() {
+    let env = Env::default();
+    let (client, admin) = setup_admin(&env);
+    let new_admin = Address::generate(&env);
+    
+    client.propose_admin(&new_admin);
+    let event = AdminProposedEvent { admin: admin.clone(), pending_admin: new_admin.clone() };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+    
+    client.accept_admin();
+    let event = AdminTransferredEvent { previous_admin: admin, new_admin: new_admin.clone() };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+    
+    client.renounce_admin();
+    let event = AdminRenouncedEvent { admin: new_admin };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+}
+
+// ============================================================
+// Allowance relativo (increase/decrease) e ilimitado
+// ============================================================
+
+/// Test: increase_allowance suma al valor vigente y fija la nueva expiración
+#[test]
+fn test_increase_allowance() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.approve(&alice, &bob, &100, &1000);
+    client.increase_allowance(&alice, &bob, &50, &2000);
+    
+    let detailed = ApproveEvent {
+        from: alice.clone(),
+        spender: bob.clone(),
+        old_allowance: 100,
+        new_allowance: 150,
+        expiration_ledger: 2000,
+    };
+    let standard = Sep41ApproveEvent {
+        from: alice.clone(),
+        spender: bob.clone(),
+        amount: 150,
+        expiration_ledger: 2000,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+    assert_eq!(client.allowance(&alice, &bob), 150);
+    
+    // Montos no positivos y expiraciones pasadas se rechazan
+    assert_eq!(client.try_increase_allowance(&alice, &bob, &0, &2000), Err(Ok(TokenError::InvalidAmount)));
+    set_sequence(&env, 100);
+    assert_eq!(client.try_increase_allowance(&alice, &bob, &1, &99), Err(Ok(TokenError::InvalidExpiration)));
+}
+
+/// Test: un allowance vencido cuenta como 0 al incrementarlo
+#[test]
+fn test_increase_expired_allowance() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.approve(&alice, &bob, &100, &10);
+    set_sequence(&env, 11);
+    
+    client.increase_allowance(&alice, &bob, &50, &100);
+    assert_eq!(client.allowance(&alice, &bob), 50);
+}
+
+/// Test: increase_allowance usa aritmética verificada
+#[test]
+fn test_increase_allowance_overflow() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.approve(&alice, &bob, &(i128::MAX - 10), &1000);
+    
+    assert_eq!(client.try_increase_allowance(&alice, &bob, &11, &1000), Err(Ok(TokenError::OverflowError)));
+    
+    // Llegar exactamente a i128::MAX lo vuelve ilimitado
+    client.increase_allowance(&alice, &bob, &10, &1000);
+    assert_eq!(client.allowance(&alice, &bob), UNLIMITED_ALLOWANCE);
+}
+
+/// Test: decrease_allowance conserva la expiración y borra la key en 0
+#[test]
+fn test_decrease_allowance() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.approve(&alice, &bob, &100, &1000);
+    client.decrease_allowance(&alice, &bob, &30);
+    
+    let stored = env.as_contract(&client.address, || storage::read_allowance(&env, &alice, &bob));
+    assert_eq!(stored.amount, 70);
+    assert_eq!(stored.expiration_ledger, 1000);
+    
+    // No puede bajar de 0
+    assert_eq!(client.try_decrease_allowance(&alice, &bob, &71), Err(Ok(TokenError::InsufficientAllowance)));
+    assert_eq!(client.try_decrease_allowance(&alice, &bob, &0), Err(Ok(TokenError::InvalidAmount)));
+    
+    client.decrease_allowance(&alice, &bob, &70);
+    assert_eq!(client.allowance(&alice, &bob), 0);
+    env.as_contract(&client.address, || {
+        let key = DataKey::Allowance(alice.clone(), bob.clone());
+        assert!(!env.storage().persistent().has(&key));
+    });
+}
+
+/// Test: un allowance de i128::MAX no se descuenta en transfer_from ni burn_from
+#[test]
+fn test_unlimited_allowance() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    client.mint(&alice, &1000);
+    client.approve(&alice, &bob, &UNLIMITED_ALLOWANCE, &1000);
+    
+    client.transfer_from(&bob, &alice, &charlie, &300);
+    let detailed = TransferFromEvent {
+        spender: bob.clone(),
+        from: alice.clone(),
+        to: charlie.clone(),
+        amount: 300,
+        fee: 0,
+        net_amount: 300,
+        from_new_balance: 700,
+        to_new_balance: 300,
+        new_allowance: UNLIMITED_ALLOWANCE,
+        memo: None,
+    };
+    let standard = Sep41TransferEvent { from: alice.clone(), to: charlie.clone(), amount: 300 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+    
+    client.burn_from(&bob, &alice, &200);
+    
+    assert_eq!(client.balance(&alice), 500);
+    assert_eq!(client.allowance(&alice, &bob), UNLIMITED_ALLOWANCE);
+    let stored = env.as_contract(&client.address, || storage::read_allowance(&env, &alice, &bob));
+    assert_eq!(stored.amount, UNLIMITED_ALLOWANCE);
+    assert_eq!(stored.expiration_ledger, 1000);
+    
+    // Sigue sujeto a la expiración
+    set_sequence(&env, 1001);
+    assert_eq!(client.try_transfer_from(&bob, &alice, &charlie, &1), Err(Ok(TokenError::InsufficientAllowance)));
+}
+
+// ============================================================
+// Allowances periódicos
+// ============================================================
+
+/// Test: approve_periodic guarda el allowance y emite su evento
+#[test]
+fn test_approve_periodic() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let service = Address::generate(&env);
+    set_sequence(&env, 100);
+    
+    client.approve_periodic(&alice, &service, &500, &17_280, &1_000_000);
+    
+    let event = PeriodicApproveEvent {
+        from: alice.clone(),
+        spender: service.clone(),
+        limit: 500,
+        period_ledgers: 17_280,
+        expiration_ledger: 1_000_000,
+    };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+    assert_eq!(
+        client.periodic_allowance(&alice, &service),
+        Some(PeriodicAllowance {
+            limit: 500,
+            period_ledgers: 17_280,
+            period_start: 100,
+            spent: 0,
+            expiration_ledger: 1_000_000,
+        })
+    );
+    assert_eq!(client.spendable_allowance(&alice, &service), 500);
+    
+    // El allowance común no cambia
+    assert_eq!(client.allowance(&alice, &service), 0);
+}
+
+/// Test: parámetros inválidos de approve_periodic
+#[test]
+fn test_approve_periodic_invalid() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let service = Address::generate(&env);
+    set_sequence(&env, 100);
+    
+    assert_eq!(
+        client.try_approve_periodic(&alice, &service, &-1, &10, &1000),
+        Err(Ok(TokenError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_approve_periodic(&alice, &service, &500, &0, &1000),
+        Err(Ok(TokenError::InvalidPeriod))
+    );
+    assert_eq!(
+        client.try_approve_periodic(&alice, &service, &500, &10, &99),
+        Err(Ok(TokenError::InvalidExpiration))
+    );
+}
+
+/// Test: transfer_from respeta el límite y lo renueva en cada período
+#[test]
+fn test_periodic_allowance_resets_each_period() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let service = Address::generate(&env);
+    let payee = Address::generate(&env);
+    client.mint(&alice, &10_000);
+    set_sequence(&env, 100);
+    client.approve_periodic(&alice, &service, &500, &100, &10_000);
+    
+    // Período [100, 200): hasta 500 en total
+    client.transfer_from(&service, &alice, &payee, &300);
+    assert_eq!(client.spendable_allowance(&alice, &service), 200);
+    assert_eq!(
+        client.try_transfer_from(&service, &alice, &payee, &201),
+        Err(Ok(TokenError::InsufficientAllowance))
+    );
+    client.transfer_from(&service, &alice, &payee, &200);
+    assert_eq!(client.spendable_allowance(&alice, &service), 0);
+    
+    // Último ledger del período: sigue agotado
+    set_sequence(&env, 199);
+    assert_eq!(
+        client.try_transfer_from(&service, &alice, &payee, &1),
+        Err(Ok(TokenError::InsufficientAllowance))
+    );
+    
+    // Período [200, 300): el límite se renueva
+    set_sequence(&env, 200);
+    assert_eq!(client.spendable_allowance(&alice, &service), 500);
+    client.transfer_from(&service, &alice, &payee, &400);
+    
+    let event = TransferFromEvent {
+        spender: service.clone(),
+        from: alice.clone(),
+        to: payee.clone(),
+        amount: 400,
+        fee: 0,
+        net_amount: 400,
+        from_new_balance: 9_100,
+        to_new_balance: 900,
+        new_allowance: 100,
+        memo: None,
+    };
+    let standard = Sep41TransferEvent { from: alice.clone(), to: payee.clone(), amount: 400 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &event), emitted(&env, &client, &standard)]
+    );
+    
+    // Saltear períodos sin gastar no acumula límite: [500, 600)
+    set_sequence(&env, 555);
+    assert_eq!(client.spendable_allowance(&alice, &service), 500);
+    assert_eq!(client.periodic_allowance(&alice, &service).unwrap().period_start, 500);
+    assert_eq!(
+        client.try_transfer_from(&service, &alice, &payee, &501),
+        Err(Ok(TokenError::InsufficientAllowance))
+    );
+}
+
+/// Test: burn_from también consume el allowance periódico
+#[test]
+fn test_periodic_allowance_burn_from() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let service = Address::generate(&env);
+    client.mint(&alice, &1_000);
+    client.approve_periodic(&alice, &service, &300, &100, &10_000);
+    
+    client.burn_from(&service, &alice, &250);
+    
+    assert_eq!(client.balance(&alice), 750);
+    assert_eq!(client.spendable_allowance(&alice, &service), 50);
+}
+
+/// Test: revocar con limit = 0 y expiración del allowance periódico
+#[test]
+fn test_periodic_allowance_revoke_and_expire() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let service = Address::generate(&env);
+    let payee = Address::generate(&env);
+    client.mint(&alice, &1_000);
+    
+    // Revocar elimina la key
+    client.approve_periodic(&alice, &service, &300, &100, &10_000);
+    client.approve_periodic(&alice, &service, &0, &0, &0);
+    assert_eq!(client.periodic_allowance(&alice, &service), None);
+    env.as_contract(&client.address, || {
+        let key = DataKey::PeriodicAllowance(alice.clone(), service.clone());
+        assert!(!env.storage().persistent().has(&key));
+    });
+    
+    // Vencido deja de contar y vuelve a usarse el allowance común
+    client.approve_periodic(&alice, &service, &300, &100, &50);
+    client.approve(&alice, &service, &100, &10_000);
+    set_sequence(&env, 51);
+    assert_eq!(client.spendable_allowance(&alice, &service), 0);
+    client.transfer_from(&service, &alice, &payee, &100);
+    assert_eq!(client.allowance(&alice, &service), 0);
+    assert_eq!(client.balance(&payee), 100);
+}
+
+/// Test: el allowance periódico tiene prioridad y no toca el común
+#[test]
+fn test_periodic_allowance_overrides_common() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let service = Address::generate(&env);
+    let payee = Address::generate(&env);
+    client.mint(&alice, &1_000);
+    
+    client.approve(&alice, &service, &400, &10_000);
+    client.approve_periodic(&alice, &service, &100, &100, &10_000);
+    
+    // Se descuenta solo del periódico; el común no se suma al límite
+    client.transfer_from(&service, &alice, &payee, &100);
+    assert_eq!(client.spendable_allowance(&alice, &service), 0);
+    assert_eq!(client.allowance(&alice, &service), 400);
+    assert_eq!(
+        client.try_transfer_from(&service, &alice, &payee, &1),
+        Err(Ok(TokenError::InsufficientAllowance))
+    );
+    
+    // Al revocar el periódico vuelve a usarse el común intacto
+    client.approve_periodic(&alice, &service, &0, &0, &0);
+    client.transfer_from(&service, &alice, &payee, &300);
+    assert_eq!(client.allowance(&alice, &service), 100);
+    assert_eq!(client.balance(&payee), 400);
+}
+
+/// Test: allowances_of lista a los spenders con allowance periódico
+#[test]
+fn test_allowances_of_periodic() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let service = Address::generate(&env);
+    let payee = Address::generate(&env);
+    client.mint(&alice, &1_000);
+    
+    client.approve(&alice, &bob, &100, &10_000);
+    client.approve_periodic(&alice, &service, &300, &100, &50);
+    assert_eq!(client.allowances_of(&alice, &0, &10), vec![&env, bob.clone(), service.clone()]);
+    
+    // Sigue listado aunque haya gastado todo el período
+    client.transfer_from(&service, &alice, &payee, &300);
+    assert_eq!(client.allowances_of(&alice, &0, &10), vec![&env, bob.clone(), service.clone()]);
+    
+    // Con el común en 0 y el periódico vigente sigue listado
+    client.approve(&alice, &service, &200, &10_000);
+    client.approve(&alice, &service, &0, &0);
+    assert_eq!(client.allowances_of(&alice, &0, &10), vec![&env, bob.clone(), service.clone()]);
+    
+    // Vencido deja de listarse; revocarlo lo quita del storage
+    set_sequence(&env, 51);
+    assert_eq!(client.allowances_of(&alice, &0, &10), vec![&env, bob.clone()]);
+    client.approve_periodic(&alice, &service, &0, &0, &0);
+    client.approve(&alice, &bob, &0, &0);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::SpenderCount(alice.clone())));
+    });
+}
+
+// ============================================================
+// Holders y allowances enumerables
+// ============================================================
+
+/// Test: la lista de holders sigue a los balances que pasan por 0
+#[test]
+fn test_holders_follow_balances() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.mint(&alice, &1000);
+    client.mint(&bob, &500);
+    client.transfer(&alice, &charlie, &400);
+    assert_eq!(client.holder_count(), 3);
+    assert_eq!(client.holders(&0, &10), vec![&env, alice.clone(), bob.clone(), charlie.clone()]);
+    
+    // transfer de todo el balance: alice sale y charlie ocupa su lugar
+    client.transfer(&alice, &bob, &600);
+    assert_eq!(client.holders(&0, &10), vec![&env, charlie.clone(), bob.clone()]);
+    
+    // burn de todo el balance
+    client.burn(&bob, &1100);
+    assert_eq!(client.holder_count(), 1);
+    assert_eq!(client.holders(&0, &10), vec![&env, charlie.clone()]);
+    
+    // Volver a recibir lo agrega de nuevo al final
+    client.transfer(&charlie, &alice, &100);
+    assert_eq!(client.holders(&0, &10), vec![&env, charlie, alice]);
+}
+
+/// Test: un clawback de todo el balance también saca a la cuenta de la lista
+#[test]
+fn test_holders_after_clawback() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.mint(&alice, &1000);
+    client.mint(&bob, &1000);
+    
+    client.clawback(&alice, &1000);
+    
+    assert_eq!(client.holder_count(), 1);
+    assert_eq!(client.holders(&0, &10), vec![&env, bob]);
+}
+
+/// Test: holders() pagina y limita el tamaño de página
+#[test]
+fn test_holders_pagination() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let mut accounts = soroban_sdk::Vec::new(&env);
+    for _ in 0..MAX_PAGE_SIZE + 5 {
+        let account = Address::generate(&env);
+        client.mint(&account, &1);
+        accounts.push_back(account);
+    }
+    
+    assert_eq!(client.holder_count(), MAX_PAGE_SIZE + 5);
+    assert_eq!(client.holders(&0, &3), accounts.slice(0..3));
+    assert_eq!(client.holders(&MAX_PAGE_SIZE, &10), accounts.slice(MAX_PAGE_SIZE..MAX_PAGE_SIZE + 5));
+    assert_eq!(client.holders(&0, &u32::MAX).len(), MAX_PAGE_SIZE);
+    assert_eq!(client.holders(&(MAX_PAGE_SIZE + 5), &10).len(), 0);
+}
+
+/// Test: allowances_of lista los spenders con allowance vigente
+#[test]
+fn test_allowances_of() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    let dave = Address::generate(&env);
+    client.mint(&alice, &1000);
+    
+    client.approve(&alice, &bob, &100, &1000);
+    client.approve(&alice, &charlie, &200, &1000);
+    client.approve(&alice, &dave, &300, &50);
+    assert_eq!(client.allowances_of(&alice, &0, &10), vec![&env, bob.clone(), charlie.clone(), dave.clone()]);
+    
+    // approve en 0 lo quita y el último ocupa su posición
+    client.approve(&alice, &bob, &0, &0);
+    assert_eq!(client.allowances_of(&alice, &0, &10), vec![&env, dave.clone(), charlie.clone()]);
+    
+    // transfer_from que consume todo el allowance también
+    client.transfer_from(&charlie, &alice, &bob, &200);
+    assert_eq!(client.allowances_of(&alice, &0, &10), vec![&env, dave.clone()]);
+    
+    // Un allowance vencido sigue en storage pero no se lista
+    set_sequence(&env, 51);
+    assert_eq!(client.allowances_of(&alice, &0, &10), soroban_sdk::Vec::<Address>::new(&env));
+    
+    // Reaprobarlo lo vuelve a listar; decrease hasta 0 lo quita y borra la lista
+    client.approve(&alice, &dave, &300, &1000);
+    client.decrease_allowance(&alice, &dave, &300);
+    assert_eq!(client.allowances_of(&alice, &0, &10), soroban_sdk::Vec::<Address>::new(&env));
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::SpenderCount(alice.clone())));
+    });
+}
+
+// ============================================================
+// Memos y cuentas que exigen memo
+// ============================================================
+
+/// Test: transfer_with_memo lleva el memo en TransferEvent
+#[test]
+fn test_transfer_with_memo() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.mint(&alice, &1000);
+    let memo = String::from_str(&env, "factura-2024-0042");
+    
+    client.transfer_with_memo(&alice, &bob, &300, &memo);
+    
+    let detailed = TransferEvent {
+        from: alice.clone(),
+        to: bob.clone(),
+        amount: 300,
+        fee: 0,
+        net_amount: 300,
+        from_new_balance: 700,
+        to_new_balance: 300,
+        memo: Some(memo),
+    };
+    let standard = Sep41TransferEvent { from: alice, to: bob, amount: 300 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: mint_with_memo lleva el memo en MintEvent
+#[test]
+fn test_mint_with_memo() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let memo = String::from_str(&env, "deposito-7781");
+    
+    client.mint_with_memo(&alice, &1000, &memo);
+    
+    let detailed = MintEvent {
+        to: alice.clone(),
+        amount: 1000,
+        new_balance: 1000,
+        new_supply: 1000,
+        memo: Some(memo),
+    };
+    let standard = Sep41MintEvent { to: alice, amount: 1000 };
+    assert_eq!(
+        env.events().all(),
+        vec![&env, emitted(&env, &client, &detailed), emitted(&env, &client, &standard)]
+    );
+}
+
+/// Test: memos vacíos o más largos que MAX_MEMO_LENGTH se rechazan
+#[test]
+fn test_memo_length_limits() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.mint(&alice, &1000);
+    
+    let empty = String::from_str(&env, "");
+    let too_long = String::from_str(&env, &"x".repeat(MAX_MEMO_LENGTH as usize + 1));
+    let max = String::from_str(&env, &"x".repeat(MAX_MEMO_LENGTH as usize));
+    
+    assert_eq!(client.try_transfer_with_memo(&alice, &bob, &1, &empty), Err(Ok(TokenError::InvalidMemo)));
+    assert_eq!(client.try_transfer_with_memo(&alice, &bob, &1, &too_long), Err(Ok(TokenError::InvalidMemo)));
+    assert_eq!(client.try_mint_with_memo(&bob, &1, &too_long), Err(Ok(TokenError::InvalidMemo)));
+    
+    client.transfer_with_memo(&alice, &bob, &1, &max);
+    assert_eq!(client.balance(&bob), 1);
+}
+
+/// Test: una cuenta que exige memo solo recibe transferencias con memo
+#[test]
+fn test_memo_required() {
+    let env = Env::default();
+    let (client, _admin) = setup_admin(&env);
+    let alice = Address::generate(&env);
+    // Cuenta de depósitos de un exchange; el id muxed identifica al cliente
+    let exchange_muxed = MuxedAddress::generate(&env);
+    let exchange = exchange_muxed.address();
+    let spender = Address::generate(&env);
+    client.mint(&alice, &1000);
+    client.approve(&alice, &spender, &500, &1000);
+    
+    client.set_memo_required(&exchange, &true);
+    let event = MemoRequiredEvent { account: exchange.clone(), required: true };
+    assert_eq!(env.events().all(), vec![&env, emitted(&env, &client, &event)]);
+    assert!(client.memo_required(&exchange));
+    
+    // Sin memo falla en transfer, transfer_from y transfer_batch
+    assert_eq!(client.try_transfer(&alice, &exchange, &100), Err(Ok(TokenError::MemoRequired)));
+    assert_eq!(
+        client.try_transfer_from(&spender, &alice, &exchange, &100),
+        Err(Ok(TokenError::MemoRequired))
+    );
+    assert_eq!(
+        client.try_transfer_batch(&alice, &vec![&env, (exchange.clone(), 100)]),
+        Err(Ok(TokenError::MemoRequired))
+    );
+    
+    // Con memo, o con el id de una dirección muxed, pasa
+    client.transfer_with_memo(&alice, &exchange, &100, &String::from_str(&env, "user-1234"));
+    client.transfer(&alice, &exchange_muxed, &100);
+    assert_eq!(client.balance(&exchange), 200);
+    
+    // Desactivarlo borra la key
+    client.set_memo_required(&exchange, &false);
+    client.transfer(&alice, &exchange, &100);
+    assert!(!client.memo_required(&exchange));
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::MemoRequired(exchange.clone())));
+    });
+}
+
+/// Test: transfer_from y transfer_batch con memo, cobrando el fee
+#[test]
+fn test_memo_required_transfer_from_and_batch() {
+    let env = Env::default();
+    let treasury = Address::generate(&env);
+    let (client, alice) = setup_fee(&env, &FeeDestination::Treasury(treasury.clone()));
+    let exchange = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.approve(&alice, &spender, &1_000, &1000);
+    client.set_memo_required(&exchange, &true);
+    let memo = String::from_str(&env, "user-1234");
+    
+    client.transfer_from_with_memo(&spender, &alice, &exchange, &1_000, &memo);
+    let event = TransferFromEvent {
+        spender: spender.clone(),
+        from: alice.clone(),
+        to: exchange.clone(),
+        amount: 1_000,
+        fee: 25,
+        net_amount: 975,
+        from_new_balance: 9_000,
+        to_new_balance: 975,
+        new_allowance: 0,
+        memo: Some(memo.clone()),
+    };
+    assert!(env.events().all().contains(emitted(&env, &client, &event)));
+    
+    // Un destinatario con memo y otro sin requisito en el mismo lote
+    client.transfer_batch_with_memo(&alice, &vec![
+        &env,
+        (exchange.clone(), 2_000, memo.clone()),
+        (bob.clone(), 1_000, String::from_str(&env, "factura-7")),
+    ]);
+    assert_eq!(client.balance(&exchange), 2_925);
+    assert_eq!(client.balance(&bob), 975);
+    assert_eq!(client.balance(&treasury), 100);
+    assert_eq!(
+        client.try_transfer_batch_with_memo(&alice, &vec![&env, (bob.clone(), 10, String::from_str(&env, ""))]),
+        Err(Ok(TokenError::InvalidMemo))
+    );
+}